@@ -1,9 +1,12 @@
 //! AMM adapter implementations for the DEX router.
 
-use crate::traits::{FeeCollector, AMM};
-use alloc::{boxed::Box, vec};
+use crate::traits::{AMMError, FeeCollector, AMM};
+use alloc::{boxed::Box, vec, vec::Vec};
 use core::marker::PhantomData;
-use frame::prelude::*;
+use frame::{
+  deps::frame_support::traits::{fungibles, tokens::Preservation},
+  prelude::*,
+};
 use polkadot_sdk::{pallet_asset_conversion, pallet_balances};
 
 /// XYK AMM adapter that wraps pallet-asset-conversion.
@@ -25,22 +28,45 @@ impl<T> XYKAdapter<T> {
   }
 }
 
+impl<T: pallet_asset_conversion::Config> XYKAdapter<T> {
+  /// Classify an error of the asset conversion swap, falling back to its token error.
+  fn amm_error(error: DispatchError) -> AMMError {
+    use pallet_asset_conversion::Error;
+
+    let is = |e: Error<T>| error == DispatchError::from(e);
+    if is(Error::PoolNotFound) || is(Error::InvalidAssetPair) {
+      AMMError::PoolNotFound
+    } else if is(Error::ProvidedMinimumNotSufficientForSwap) {
+      AMMError::SlippageExceeded
+    } else if is(Error::AmountOutTooHigh)
+      || is(Error::ReserveLeftLessThanMinimal)
+      || is(Error::ZeroLiquidity)
+    {
+      AMMError::InsufficientLiquidity
+    } else if is(Error::BelowMinimum) {
+      AMMError::BelowMinimum
+    } else {
+      error.into()
+    }
+  }
+}
+
 impl<T, AssetKind, Balance, AccountId> AMM<AssetKind, Balance, AccountId> for XYKAdapter<T>
 where
   T: pallet_asset_conversion::Config<AssetKind = AssetKind, Balance = Balance, AccountId = AccountId>
     + frame_system::Config<AccountId = AccountId>,
-  AssetKind: Clone + Copy,
-  Balance: Zero + From<u32> + Copy + PartialOrd,
+  AssetKind: Clone,
+  Balance: Zero + From<u32> + Copy + PartialOrd + Saturating,
   AccountId: Clone,
 {
-  type Error = DispatchError;
+  type Error = AMMError;
 
   fn can_handle_pair(&self, asset_in: &AssetKind, asset_out: &AssetKind) -> bool {
     // Check if a pool exists for this pair by trying to get a quote
     let test_amount = Balance::from(1u32);
     pallet_asset_conversion::Pallet::<T>::quote_price_exact_tokens_for_tokens(
-      *asset_in,
-      *asset_out,
+      asset_in.clone(),
+      asset_out.clone(),
       test_amount,
       true,
     )
@@ -54,7 +80,10 @@ where
     amount_in: Balance,
   ) -> Option<Balance> {
     pallet_asset_conversion::Pallet::<T>::quote_price_exact_tokens_for_tokens(
-      *asset_in, *asset_out, amount_in, true, // include fees
+      asset_in.clone(),
+      asset_out.clone(),
+      amount_in,
+      true, // include fees
     )
   }
 
@@ -66,22 +95,15 @@ where
     amount_in: Balance,
     min_amount_out: Balance,
   ) -> Result<Balance, Self::Error> {
+    use fungibles::Inspect;
+
+    // The output is measured rather than quoted, so it is exact whatever the transfer does
+    let balance_before = T::Assets::balance(asset_out.clone(), who);
+
     // Create path for the swap - pallet-asset-conversion expects Vec<Box<AssetKind>>
-    let path = vec![Box::new(asset_in), Box::new(asset_out)];
-
-    // Get quote before swap to return actual amount out
-    let expected_amount_out =
-      pallet_asset_conversion::Pallet::<T>::quote_price_exact_tokens_for_tokens(
-        asset_in, asset_out, amount_in, true,
-      )
-      .ok_or(DispatchError::Other("No liquidity available"))?;
-
-    // Check if quote meets minimum requirements
-    if expected_amount_out < min_amount_out {
-      return Err(DispatchError::Other("Insufficient output amount"));
-    }
+    let path = vec![Box::new(asset_in), Box::new(asset_out.clone())];
 
-    // Execute the actual swap using pallet-asset-conversion
+    // Execute the actual swap using pallet-asset-conversion, which enforces `min_amount_out`
     pallet_asset_conversion::Pallet::<T>::swap_exact_tokens_for_tokens(
       frame_system::RawOrigin::Signed(who.clone()).into(),
       path,
@@ -89,9 +111,10 @@ where
       min_amount_out,
       who.clone(),
       false, // keep_alive
-    )?;
+    )
+    .map_err(Self::amm_error)?;
 
-    Ok(expected_amount_out)
+    Ok(T::Assets::balance(asset_out, who).saturating_sub(balance_before))
   }
 
   fn name(&self) -> &'static str {
@@ -119,12 +142,17 @@ impl<T, AssetKind, Balance, AccountId> FeeCollector<AssetKind, Balance, AccountI
 where
   T: pallet_balances::Config<Balance = Balance, AccountId = AccountId>
     + frame_system::Config<AccountId = AccountId>,
-  Balance: Zero,
+  Balance: Zero + Copy,
   AccountId: Clone,
 {
-  fn collect_fee(&self, from: &AccountId, _asset: &AssetKind, amount: Balance) -> DispatchResult {
+  fn collect_fee(
+    &self,
+    from: &AccountId,
+    _asset: &AssetKind,
+    amount: Balance,
+  ) -> Result<Vec<(AccountId, Balance)>, DispatchError> {
     if amount.is_zero() {
-      return Ok(());
+      return Ok(Vec::new());
     }
 
     // For now, assume we're dealing with native tokens
@@ -135,6 +163,116 @@ where
       amount,
     )?;
 
-    Ok(())
+    Ok(vec![(self.fee_collector.clone(), amount)])
+  }
+}
+
+/// Fee collector that takes the router fee in the swapped input asset.
+///
+/// Fails if the fee collector cannot receive the fee, e.g. because it has no account for a
+/// non-sufficient asset or the fee is below the minimum balance of the asset, so a fee is never
+/// skipped. See [`crate::Pallet::touch_fee_accounts`].
+pub struct FungiblesFeeCollector<Assets, AccountId> {
+  fee_collector: AccountId,
+  _phantom: PhantomData<Assets>,
+}
+
+impl<Assets, AccountId> FungiblesFeeCollector<Assets, AccountId> {
+  pub fn new(fee_collector: AccountId) -> Self {
+    Self {
+      fee_collector,
+      _phantom: PhantomData,
+    }
+  }
+}
+
+impl<Assets, AssetKind, Balance, AccountId> FeeCollector<AssetKind, Balance, AccountId>
+  for FungiblesFeeCollector<Assets, AccountId>
+where
+  Assets: fungibles::Mutate<AccountId, AssetId = AssetKind, Balance = Balance>,
+  AssetKind: Clone,
+  Balance: Zero + Copy,
+  AccountId: Eq + Clone,
+{
+  fn collect_fee(
+    &self,
+    from: &AccountId,
+    asset: &AssetKind,
+    amount: Balance,
+  ) -> Result<Vec<(AccountId, Balance)>, DispatchError> {
+    if amount.is_zero() {
+      return Ok(Vec::new());
+    }
+
+    Assets::transfer(
+      asset.clone(),
+      from,
+      &self.fee_collector,
+      amount,
+      Preservation::Expendable,
+    )?;
+
+    Ok(vec![(self.fee_collector.clone(), amount)])
+  }
+}
+
+/// Fee collector that sends a share of the router fee to a treasury and the rest to the fee
+/// collector, both in the swapped input asset.
+///
+/// Fails if either account cannot receive its share, like [`FungiblesFeeCollector`].
+pub struct SplitFeeCollector<Assets, AccountId> {
+  fee_collector: AccountId,
+  treasury: AccountId,
+  treasury_share: Permill,
+  _phantom: PhantomData<Assets>,
+}
+
+impl<Assets, AccountId> SplitFeeCollector<Assets, AccountId> {
+  pub fn new(fee_collector: AccountId, treasury: AccountId, treasury_share: Permill) -> Self {
+    Self {
+      fee_collector,
+      treasury,
+      treasury_share,
+      _phantom: PhantomData,
+    }
+  }
+
+  /// The accounts a router fee of `amount` is split between and their shares, treasury first.
+  pub fn shares<Balance>(&self, amount: Balance) -> [(AccountId, Balance); 2]
+  where
+    Balance: AtLeast32BitUnsigned + Copy,
+    AccountId: Clone,
+  {
+    let treasury_fee = self.treasury_share.mul_floor(amount);
+    [
+      (self.treasury.clone(), treasury_fee),
+      (
+        self.fee_collector.clone(),
+        amount.saturating_sub(treasury_fee),
+      ),
+    ]
+  }
+}
+
+impl<Assets, AssetKind, Balance, AccountId> FeeCollector<AssetKind, Balance, AccountId>
+  for SplitFeeCollector<Assets, AccountId>
+where
+  Assets: fungibles::Mutate<AccountId, AssetId = AssetKind, Balance = Balance>,
+  AssetKind: Clone,
+  Balance: AtLeast32BitUnsigned + Copy,
+  AccountId: Eq + Clone,
+{
+  fn collect_fee(
+    &self,
+    from: &AccountId,
+    asset: &AssetKind,
+    amount: Balance,
+  ) -> Result<Vec<(AccountId, Balance)>, DispatchError> {
+    let mut credited = Vec::new();
+    for (account, share) in self.shares(amount) {
+      credited
+        .extend(FungiblesFeeCollector::<Assets, _>::new(account).collect_fee(from, asset, share)?);
+    }
+    Ok(credited)
   }
 }
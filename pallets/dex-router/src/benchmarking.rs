@@ -0,0 +1,280 @@
+//! Benchmarks for the DEX router pallet.
+
+use super::*;
+use alloc::vec;
+use frame::benchmarking::prelude::*;
+use frame_system::RawOrigin;
+
+/// Sets up the runtime state router benchmarks depend on.
+pub trait BenchmarkHelper<AccountId, AssetKind, Balance> {
+  /// Create a pool for a pair with ample liquidity and fund `caller` with its first asset.
+  ///
+  /// Returns the pair and an amount of the first asset that can be swapped without tripping
+  /// the circuit breaker.
+  fn setup_pair(caller: &AccountId) -> (AssetKind, AssetKind, Balance);
+
+  /// Create pools with ample liquidity for every pair of adjacent assets on a path of `len`
+  /// assets and fund `caller` with its first asset.
+  ///
+  /// Returns the path and an amount of its first asset that can be swapped along it without
+  /// tripping the circuit breaker.
+  fn setup_path(caller: &AccountId, len: u32) -> (Vec<AssetKind>, Balance);
+
+  /// Fund `caller` with two assets that have no pool yet.
+  ///
+  /// Returns the pair and an amount of each asset `caller` can add as liquidity.
+  fn setup_unpooled_pair(caller: &AccountId) -> (AssetKind, AssetKind, Balance);
+}
+
+/// Start an observation of `pair` in the current block, so trades read and update it.
+fn observe_pair<T: Config>(pair: &(T::AssetKind, T::AssetKind)) {
+  let now = frame_system::Pallet::<T>::block_number();
+  let reference_price = Pallet::<T>::pair_reserves(pair)
+    .and_then(|(reserve_a, reserve_b)| circuit_breaker::spot_price(reserve_a, reserve_b))
+    .expect("helper creates a pool with liquidity");
+  PairObservations::<T>::insert(
+    pair,
+    PairObservation {
+      window_start: now,
+      reference_price,
+      volume_block: now,
+      block_volume: Permill::zero(),
+    },
+  );
+}
+
+#[benchmarks]
+mod benchmarks {
+  use super::*;
+
+  /// Worst case: every pair on the path has an observation in the current block that is read
+  /// and updated.
+  #[benchmark]
+  fn swap_exact_tokens_for_tokens(p: Linear<2, { MAX_SUPPORTED_PATH_LENGTH }>) {
+    let caller: T::AccountId = whitelisted_caller();
+    let (path, amount_in) = T::BenchmarkHelper::setup_path(&caller, p);
+    for hop in path.windows(2) {
+      observe_pair::<T>(&Pallet::<T>::pair_key(hop[0].clone(), hop[1].clone()));
+    }
+    let pair = Pallet::<T>::pair_key(path[path.len() - 2].clone(), path[path.len() - 1].clone());
+    let path: BoundedVec<_, _> = path.try_into().unwrap();
+
+    #[extrinsic_call]
+    _(
+      RawOrigin::Signed(caller.clone()),
+      path,
+      amount_in,
+      One::one(),
+      caller.clone(),
+      true,
+    );
+
+    assert!(PairObservations::<T>::get(&pair).is_some_and(|o| !o.block_volume.is_zero()));
+  }
+
+  #[benchmark]
+  fn reset_circuit_breaker() -> Result<(), BenchmarkError> {
+    let caller: T::AccountId = whitelisted_caller();
+    let (asset_a, asset_b, _) = T::BenchmarkHelper::setup_pair(&caller);
+    let pair = Pallet::<T>::pair_key(asset_a.clone(), asset_b.clone());
+    let now = frame_system::Pallet::<T>::block_number();
+    TrippedPairs::<T>::insert(&pair, now);
+    PairObservations::<T>::insert(
+      &pair,
+      PairObservation {
+        window_start: now,
+        reference_price: FixedU128::one(),
+        volume_block: now,
+        block_volume: Permill::one(),
+      },
+    );
+    let origin = T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+
+    #[extrinsic_call]
+    _(origin as T::RuntimeOrigin, asset_a, asset_b);
+
+    assert!(!TrippedPairs::<T>::contains_key(&pair));
+    Ok(())
+  }
+
+  #[benchmark]
+  fn set_router_paused() -> Result<(), BenchmarkError> {
+    let origin = T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+
+    #[extrinsic_call]
+    _(origin as T::RuntimeOrigin, true);
+
+    assert!(RouterPaused::<T>::get());
+    Ok(())
+  }
+
+  #[benchmark]
+  fn set_fee_settings() -> Result<(), BenchmarkError> {
+    let origin = T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+    let settings = FeeSettings {
+      router_fee: Permill::from_percent(1),
+      treasury_fee_share: Permill::from_percent(50),
+      flash_swap_fee: Permill::from_percent(1),
+    };
+
+    #[extrinsic_call]
+    _(origin as T::RuntimeOrigin, settings);
+
+    assert_eq!(FeeSettingsOverride::<T>::get(), Some(settings));
+    Ok(())
+  }
+
+  #[benchmark]
+  fn set_pair_paused() -> Result<(), BenchmarkError> {
+    let caller: T::AccountId = whitelisted_caller();
+    let (asset_a, asset_b, _) = T::BenchmarkHelper::setup_pair(&caller);
+    let pair = Pallet::<T>::pair_key(asset_a.clone(), asset_b.clone());
+    let origin = T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+
+    #[extrinsic_call]
+    _(origin as T::RuntimeOrigin, asset_a, asset_b, true);
+
+    assert!(PausedPairs::<T>::contains_key(&pair));
+    Ok(())
+  }
+
+  #[benchmark]
+  fn set_amm_paused() -> Result<(), BenchmarkError> {
+    let origin = T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+
+    #[extrinsic_call]
+    _(origin as T::RuntimeOrigin, AMMType::XYK, true);
+
+    assert!(PausedAMMs::<T>::contains_key(AMMType::XYK));
+    Ok(())
+  }
+
+  /// Worst case: the pool does not exist yet and is created.
+  #[benchmark]
+  fn add_liquidity() {
+    let caller: T::AccountId = whitelisted_caller();
+    let (asset1, asset2, amount) = T::BenchmarkHelper::setup_unpooled_pair(&caller);
+
+    #[extrinsic_call]
+    _(
+      RawOrigin::Signed(caller.clone()),
+      asset1.clone(),
+      asset2.clone(),
+      amount,
+      amount,
+      One::one(),
+      One::one(),
+    );
+
+    assert!(Pallet::<T>::pair_reserves(&Pallet::<T>::pair_key(asset1, asset2)).is_some());
+  }
+
+  #[benchmark]
+  fn remove_liquidity() -> Result<(), BenchmarkError> {
+    let caller: T::AccountId = whitelisted_caller();
+    let (asset_in, asset_other, amount_in) = T::BenchmarkHelper::setup_pair(&caller);
+    let (lp_minted, _) = Pallet::<T>::do_zap_in(
+      &caller,
+      asset_in.clone(),
+      asset_other.clone(),
+      amount_in,
+      One::one(),
+    )?;
+
+    #[extrinsic_call]
+    _(
+      RawOrigin::Signed(caller.clone()),
+      asset_in,
+      asset_other,
+      lp_minted,
+      One::one(),
+      One::one(),
+    );
+
+    Ok(())
+  }
+
+  #[benchmark]
+  fn zap_in() {
+    let caller: T::AccountId = whitelisted_caller();
+    let (asset_in, asset_other, amount_in) = T::BenchmarkHelper::setup_pair(&caller);
+    let pair = Pallet::<T>::pair_key(asset_in.clone(), asset_other.clone());
+
+    #[extrinsic_call]
+    _(
+      RawOrigin::Signed(caller.clone()),
+      asset_in,
+      asset_other,
+      amount_in,
+      One::one(),
+    );
+
+    assert!(PairObservations::<T>::contains_key(&pair));
+  }
+
+  /// Both sides are withdrawn and the one not requested is swapped through the router.
+  #[benchmark]
+  fn zap_out() -> Result<(), BenchmarkError> {
+    let caller: T::AccountId = whitelisted_caller();
+    let (asset_in, asset_other, amount_in) = T::BenchmarkHelper::setup_pair(&caller);
+    let (lp_minted, _) = Pallet::<T>::do_zap_in(
+      &caller,
+      asset_in.clone(),
+      asset_other.clone(),
+      amount_in,
+      One::one(),
+    )?;
+
+    #[extrinsic_call]
+    _(
+      RawOrigin::Signed(caller.clone()),
+      asset_in.clone(),
+      asset_other,
+      lp_minted,
+      asset_in,
+      One::one(),
+    );
+
+    Ok(())
+  }
+
+  /// Worst case: the pair has an observation in the current block that is read and updated, and
+  /// the caller repays the input with the router and flash swap fees after an empty remark.
+  #[benchmark]
+  fn flash_swap() -> Result<(), BenchmarkError> {
+    use fungibles::Inspect;
+
+    let caller: T::AccountId = whitelisted_caller();
+    let (asset_in, asset_out, amount_in) = T::BenchmarkHelper::setup_pair(&caller);
+    let amount_out =
+      Pallet::<T>::quote_exact_tokens_for_tokens(asset_in.clone(), asset_out.clone(), amount_in)
+        .ok_or(BenchmarkError::Stop("helper creates a pool with liquidity"))?;
+    let pair = Pallet::<T>::pair_key(asset_in.clone(), asset_out.clone());
+    observe_pair::<T>(&pair);
+    let call: <T as Config>::RuntimeCall =
+      frame_system::Call::<T>::remark { remark: vec![] }.into();
+    let balance_in = T::Assets::balance(asset_in.clone(), &caller);
+    let balance_out = T::Assets::balance(asset_out.clone(), &caller);
+
+    #[extrinsic_call]
+    _(
+      RawOrigin::Signed(caller.clone()),
+      asset_in.clone(),
+      asset_out.clone(),
+      amount_out,
+      amount_in.saturating_mul(2u32.into()),
+      Box::new(call),
+    );
+
+    assert!(!Pallet::<T>::flash_swap_in_progress());
+    assert_eq!(
+      T::Assets::balance(asset_out, &caller),
+      balance_out.saturating_add(amount_out)
+    );
+    assert!(T::Assets::balance(asset_in, &caller) < balance_in);
+    assert!(PairObservations::<T>::get(&pair).is_some_and(|o| !o.block_volume.is_zero()));
+    Ok(())
+  }
+
+  impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
+}
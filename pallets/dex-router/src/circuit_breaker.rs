@@ -0,0 +1,75 @@
+//! Circuit breaker primitives for the DEX router.
+//!
+//! The router tracks a reference price and the traded volume for every pair it routes through.
+//! A trade is rejected before it executes if it alone would move the price beyond the configured
+//! threshold, or trade more than the configured share of the pool reserves. When trades move the
+//! price beyond the threshold within the observation window, or the cumulative volume of a block
+//! exceeds the configured share, the pair trips and further swaps are rejected until an admin
+//! resets it or the cooldown passes.
+
+use codec::{Decode, DecodeWithMemTracking, Encode, MaxEncodedLen};
+use frame::prelude::*;
+use scale_info::TypeInfo;
+
+/// Reason a pair circuit breaker tripped
+#[derive(
+  Clone, Copy, Debug, Decode, DecodeWithMemTracking, Encode, Eq, MaxEncodedLen, PartialEq, TypeInfo,
+)]
+pub enum TripReason {
+  /// Price moved more than `MaxPriceMovement` within `PriceWindow`
+  PriceMovement,
+  /// Volume within a single block exceeded `MaxVolumePerBlock`
+  VolumeCap,
+}
+
+/// Price and volume observation for a single pair
+#[derive(Clone, Debug, Decode, Encode, Eq, MaxEncodedLen, PartialEq, TypeInfo)]
+pub struct PairObservation<BlockNumber> {
+  /// Block at which the current observation window started
+  pub window_start: BlockNumber,
+  /// Spot price at the start of the window (second asset per unit of the first asset)
+  pub reference_price: FixedU128,
+  /// Block the accumulated volume refers to
+  pub volume_block: BlockNumber,
+  /// Volume traded in `volume_block`, as a share of the input reserves
+  pub block_volume: Permill,
+}
+
+/// Spot price of a pool expressed as `reserve_b / reserve_a`
+pub fn spot_price<Balance: UniqueSaturatedInto<u128>>(
+  reserve_a: Balance,
+  reserve_b: Balance,
+) -> Option<FixedU128> {
+  FixedU128::checked_from_rational(
+    reserve_b.unique_saturated_into(),
+    reserve_a.unique_saturated_into(),
+  )
+}
+
+/// Relative distance between `current` and `reference`, saturating at 100%
+pub fn price_deviation(reference: FixedU128, current: FixedU128) -> Permill {
+  if reference.is_zero() {
+    return Permill::one();
+  }
+
+  let difference = if current > reference {
+    current.saturating_sub(reference)
+  } else {
+    reference.saturating_sub(current)
+  };
+
+  Permill::from_rational(difference.into_inner(), reference.into_inner())
+}
+
+/// Share of `reserve_in` represented by `amount_in`, saturating at 100%
+pub fn volume_share<Balance: UniqueSaturatedInto<u128>>(
+  amount_in: Balance,
+  reserve_in: Balance,
+) -> Permill {
+  let reserve_in: u128 = reserve_in.unique_saturated_into();
+  if reserve_in.is_zero() {
+    return Permill::one();
+  }
+
+  Permill::from_rational(amount_in.unique_saturated_into(), reserve_in)
+}
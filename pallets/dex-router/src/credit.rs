@@ -0,0 +1,338 @@
+//! Credit swaps through the DEX router.
+//!
+//! Fee payment adapters, such as the asset transaction payment `SwapAssetAdapter` and the XCM
+//! `SwapFirstAssetTrader`, swap credits rather than account balances. Implementing
+//! [`SwapCredit`] and [`QuotePrice`] for the router lets them swap through it, so pauses, circuit
+//! breakers and the router fee apply to fee payments like to any other swap.
+//!
+//! The router fee is taken from the credit and split like the router fee of other swaps. As with
+//! other swaps, the swap fails if the treasury or the fee collector cannot receive its share, and
+//! it is reported by `SwapExecuted`, without a trader.
+
+use super::*;
+use frame::deps::frame_support::storage::{with_transaction, TransactionOutcome};
+use polkadot_sdk::pallet_asset_conversion::{QuotePrice, SwapCredit};
+
+/// Credit of an asset routed by the router.
+pub type CreditOf<T> =
+  fungibles::Credit<<T as frame_system::Config>::AccountId, <T as Config>::Assets>;
+
+/// Output and unused input of a credit swap, or the input and the cause of its failure.
+type CreditSwapResult<T> = Result<(CreditOf<T>, CreditOf<T>), (CreditOf<T>, DispatchError)>;
+
+impl<T: Config> Pallet<T> {
+  /// Check a credit swap of `amount_in` of `asset_in` into the pool for `amount_out`, after a
+  /// router fee of `router_fee`.
+  ///
+  /// Returns the reserves of the pair before the swap, to record the trade against once it has
+  /// executed.
+  fn ensure_credit_swap_allowed(
+    pair: &(T::AssetKind, T::AssetKind),
+    asset_in: &T::AssetKind,
+    asset_out: &T::AssetKind,
+    (amount_in, amount_out): (T::Balance, T::Balance),
+    router_fee: T::Balance,
+  ) -> Result<(T::Balance, T::Balance), DispatchError> {
+    Self::ensure_trading_enabled(pair, asset_in, asset_out)?;
+    Self::ensure_fee_collectable(asset_in, router_fee)?;
+    let reserves_before = Self::pair_reserves(pair).ok_or(Error::<T>::NoLiquidityAvailable)?;
+    Self::ensure_within_circuit_limits(pair, asset_in, reserves_before, amount_in, amount_out)?;
+    Ok(reserves_before)
+  }
+
+  /// Record a credit swap that sent `amount_in` of `asset_in` to the pool for `amount_out`,
+  /// after a router fee of `router_fee`.
+  fn note_credit_swap(
+    pair: &(T::AssetKind, T::AssetKind),
+    asset_in: T::AssetKind,
+    asset_out: T::AssetKind,
+    reserves_before: (T::Balance, T::Balance),
+    (amount_in, amount_out): (T::Balance, T::Balance),
+    router_fee: T::Balance,
+  ) {
+    let volume = Self::trade_volume(pair, &asset_in, reserves_before, amount_in);
+    Self::record_trade(pair, reserves_before, volume);
+    Self::deposit_hop_executed(
+      asset_in.clone(),
+      asset_out.clone(),
+      amount_in,
+      amount_out,
+      AMMType::XYK,
+    );
+    Self::deposit_event(Event::SwapExecuted {
+      who: None,
+      asset_in,
+      asset_out,
+      amount_in: amount_in.saturating_add(router_fee),
+      amount_out,
+      router_fee,
+      amm_used: AMMType::XYK,
+    });
+  }
+
+  /// Pay the router fee `fee` to the treasury and the fee collector, split like the router fee of
+  /// other swaps.
+  ///
+  /// [`Self::ensure_fee_collectable`] must have accepted the fee.
+  fn resolve_router_fee(mut fee: CreditOf<T>) {
+    use fungibles::Balanced;
+
+    let (asset, charged) = (fee.asset(), fee.peek());
+    let mut received = T::Balance::zero();
+    for (destination, amount) in Self::get_fee_collector().shares(charged) {
+      let share = fee.extract(amount);
+      if amount.is_zero() {
+        continue;
+      }
+      match T::Assets::resolve(&destination, share) {
+        Ok(()) => {
+          received = received.saturating_add(amount);
+          Self::deposit_event(Event::FeeCollected {
+            asset: asset.clone(),
+            amount,
+            destination,
+          });
+        }
+        Err(_) => {
+          defensive!("router fee shares are checked to be collectable");
+        }
+      }
+    }
+    Self::note_router_fee(&asset, charged, received);
+  }
+
+  /// Swap exactly `credit_in` of `asset_in` for at least `amount_out_min` of `asset_out`.
+  ///
+  /// Returns `credit_in` unchanged on failure.
+  fn do_swap_exact_credit(
+    asset_in: T::AssetKind,
+    asset_out: T::AssetKind,
+    mut credit_in: CreditOf<T>,
+    amount_out_min: Option<T::Balance>,
+  ) -> Result<CreditOf<T>, (CreditOf<T>, DispatchError)> {
+    let pair = Self::pair_key(asset_in.clone(), asset_out.clone());
+    let router_fee = Self::fee_settings().router_fee.mul_floor(credit_in.peek());
+    let pool_in = credit_in.peek().saturating_sub(router_fee);
+    let checked = Self::get_best_quote(&asset_in, &asset_out, pool_in)
+      .ok_or_else(|| Error::<T>::NoLiquidityAvailable.into())
+      .and_then(|(_, quote)| {
+        Self::ensure_credit_swap_allowed(&pair, &asset_in, &asset_out, (pool_in, quote), router_fee)
+      });
+    let reserves_before = match checked {
+      Ok(reserves_before) => reserves_before,
+      Err(error) => return Err((credit_in, error)),
+    };
+
+    let fee = credit_in.extract(router_fee);
+    let credit_out = match <pallet_asset_conversion::Pallet<T::AssetConversion> as SwapCredit<
+      T::AccountId,
+    >>::swap_exact_tokens_for_tokens(
+      vec![asset_in.clone(), asset_out.clone()],
+      credit_in,
+      amount_out_min,
+    ) {
+      Ok(credit_out) => credit_out,
+      Err((mut credit_in, error)) => {
+        let _ = credit_in.subsume(fee);
+        return Err((credit_in, error));
+      }
+    };
+
+    Self::resolve_router_fee(fee);
+    Self::note_credit_swap(
+      &pair,
+      asset_in,
+      asset_out,
+      reserves_before,
+      (pool_in, credit_out.peek()),
+      router_fee,
+    );
+    Ok(credit_out)
+  }
+
+  /// Swap as much of `credit_in` of `asset_in` as it takes to get `amount_out` of `asset_out`.
+  ///
+  /// Returns the output and the unused part of `credit_in`, or `credit_in` unchanged on failure.
+  fn do_swap_credit_for_exact(
+    asset_in: T::AssetKind,
+    asset_out: T::AssetKind,
+    mut credit_in: CreditOf<T>,
+    amount_out: T::Balance,
+  ) -> CreditSwapResult<T> {
+    let pair = Self::pair_key(asset_in.clone(), asset_out.clone());
+    let checked =
+      pallet_asset_conversion::Pallet::<T::AssetConversion>::quote_price_tokens_for_exact_tokens(
+        asset_in.clone(),
+        asset_out.clone(),
+        amount_out,
+        true,
+      )
+      .ok_or_else(|| Error::<T>::NoLiquidityAvailable.into())
+      .and_then(|pool_in| {
+        // What the pool takes for `amount_out`, grossed up by the router fee like a quote
+        let amount_due = Self::fee_settings()
+          .router_fee
+          .left_from_one()
+          .saturating_reciprocal_mul_ceil(pool_in);
+        ensure!(amount_due <= credit_in.peek(), Error::<T>::SlippageExceeded);
+        let reserves_before = Self::ensure_credit_swap_allowed(
+          &pair,
+          &asset_in,
+          &asset_out,
+          (pool_in, amount_out),
+          amount_due.saturating_sub(pool_in),
+        )?;
+        Ok((pool_in, amount_due, reserves_before))
+      });
+    let (pool_in, amount_due, reserves_before) = match checked {
+      Ok(checked) => checked,
+      Err(error) => return Err((credit_in, error)),
+    };
+
+    let mut pool_credit = credit_in.extract(amount_due);
+    let fee = pool_credit.extract(amount_due.saturating_sub(pool_in));
+    let (credit_out, pool_change) =
+      match <pallet_asset_conversion::Pallet<T::AssetConversion> as SwapCredit<
+        T::AccountId,
+      >>::swap_tokens_for_exact_tokens(
+        vec![asset_in.clone(), asset_out.clone()],
+        pool_credit,
+        amount_out,
+      ) {
+        Ok(swapped) => swapped,
+        Err((pool_credit, error)) => {
+          let _ = credit_in.subsume(pool_credit);
+          let _ = credit_in.subsume(fee);
+          return Err((credit_in, error));
+        }
+      };
+    let _ = credit_in.subsume(pool_change);
+
+    let router_fee = fee.peek();
+    Self::resolve_router_fee(fee);
+    Self::note_credit_swap(
+      &pair,
+      asset_in,
+      asset_out,
+      reserves_before,
+      (pool_in, credit_out.peek()),
+      router_fee,
+    );
+    Ok((credit_out, credit_in))
+  }
+}
+
+impl<T: Config> SwapCredit<T::AccountId> for Pallet<T> {
+  type Balance = T::Balance;
+  type AssetKind = T::AssetKind;
+  type Credit = CreditOf<T>;
+
+  // Credit swaps are direct only
+  fn max_path_len() -> u32 {
+    2
+  }
+
+  fn swap_exact_tokens_for_tokens(
+    path: Vec<Self::AssetKind>,
+    credit_in: Self::Credit,
+    amount_out_min: Option<Self::Balance>,
+  ) -> Result<Self::Credit, (Self::Credit, DispatchError)> {
+    let Ok([asset_in, asset_out]) = <[_; 2]>::try_from(path) else {
+      return Err((credit_in, Error::<T>::InvalidPath.into()));
+    };
+    transactional::<T, _>(credit_in.asset(), || {
+      Self::do_swap_exact_credit(asset_in, asset_out, credit_in, amount_out_min)
+    })
+  }
+
+  fn swap_tokens_for_exact_tokens(
+    path: Vec<Self::AssetKind>,
+    credit_in: Self::Credit,
+    amount_out: Self::Balance,
+  ) -> Result<(Self::Credit, Self::Credit), (Self::Credit, DispatchError)> {
+    let Ok([asset_in, asset_out]) = <[_; 2]>::try_from(path) else {
+      return Err((credit_in, Error::<T>::InvalidPath.into()));
+    };
+    transactional::<T, _>(credit_in.asset(), || {
+      Self::do_swap_credit_for_exact(asset_in, asset_out, credit_in, amount_out)
+    })
+  }
+}
+
+/// Run a credit swap in a storage layer, rolling it back if it fails.
+fn transactional<T: Config, R>(
+  asset: T::AssetKind,
+  swap: impl FnOnce() -> Result<R, (CreditOf<T>, DispatchError)>,
+) -> Result<R, (CreditOf<T>, DispatchError)> {
+  with_transaction(|| {
+    let result = swap();
+    // Wrapped in `Ok`, as the credit in the error cannot be built from a `DispatchError`
+    match &result {
+      Ok(_) => TransactionOutcome::Commit(Ok(result)),
+      Err(_) => TransactionOutcome::Rollback(Ok(result)),
+    }
+  })
+  // `with_transaction` only fails for its own errors, e.g. nesting too deep
+  .map_err(|error: DispatchError| (CreditOf::<T>::zero(asset), error))?
+}
+
+/// Quotes with `include_fee` include the router fee and are `None` when the router would reject
+/// the swap, e.g. while it is paused, so adapters quoting before they swap fail cleanly.
+impl<T: Config> QuotePrice for Pallet<T> {
+  type Balance = T::Balance;
+  type AssetKind = T::AssetKind;
+
+  fn quote_price_exact_tokens_for_tokens(
+    asset1: Self::AssetKind,
+    asset2: Self::AssetKind,
+    amount: Self::Balance,
+    include_fee: bool,
+  ) -> Option<Self::Balance> {
+    if !include_fee {
+      return pallet_asset_conversion::Pallet::<T::AssetConversion>::quote_price_exact_tokens_for_tokens(
+        asset1, asset2, amount, false,
+      );
+    }
+    let amount_out = Self::quote_exact_tokens_for_tokens(asset1.clone(), asset2.clone(), amount)?;
+    let router_fee = Self::fee_settings().router_fee.mul_floor(amount);
+    let pair = Self::pair_key(asset1.clone(), asset2.clone());
+    Self::ensure_credit_swap_allowed(
+      &pair,
+      &asset1,
+      &asset2,
+      (amount.saturating_sub(router_fee), amount_out),
+      router_fee,
+    )
+    .ok()?;
+    Some(amount_out)
+  }
+
+  fn quote_price_tokens_for_exact_tokens(
+    asset1: Self::AssetKind,
+    asset2: Self::AssetKind,
+    amount: Self::Balance,
+    include_fee: bool,
+  ) -> Option<Self::Balance> {
+    let pool_in =
+      pallet_asset_conversion::Pallet::<T::AssetConversion>::quote_price_tokens_for_exact_tokens(
+        asset1.clone(),
+        asset2.clone(),
+        amount,
+        include_fee,
+      )?;
+    if !include_fee {
+      return Some(pool_in);
+    }
+    let amount_in = Self::quote_tokens_for_exact_tokens(asset1.clone(), asset2.clone(), amount)?;
+    let pair = Self::pair_key(asset1.clone(), asset2.clone());
+    Self::ensure_credit_swap_allowed(
+      &pair,
+      &asset1,
+      &asset2,
+      (pool_in, amount),
+      amount_in.saturating_sub(pool_in),
+    )
+    .ok()?;
+    Some(amount_in)
+  }
+}
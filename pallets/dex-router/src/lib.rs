@@ -13,13 +13,39 @@
 //! - **Automatic Best Price Selection**: Compares quotes from all available AMMs
 //! - **Built-in Router Fees**: Collects fees on all transactions
 //! - **Trait-based Architecture**: Easy to extend with new AMMs
+//! - **Circuit Breakers**: Per-pair protection against abnormal price moves and volume spikes
+//! - **Emergency Pause**: Admin can halt the whole router, individual pairs or individual AMMs
+//! - **Liquidity Management**: Add and remove pool liquidity, or zap in and out with a single asset
 
 #![cfg_attr(not(feature = "std"), no_std)]
+// The call and event expansions of FRAME clone `Copy` fields and convert post-dispatch info
+// into itself
+#![allow(clippy::clone_on_copy, clippy::useless_conversion)]
 
 extern crate alloc;
 
-use frame::prelude::*;
-use polkadot_sdk::{pallet_asset_conversion, pallet_balances};
+use alloc::{boxed::Box, vec, vec::Vec};
+#[cfg(any(feature = "try-runtime", feature = "std"))]
+use frame::deps::sp_runtime::TryRuntimeError;
+use frame::{
+  deps::{
+    frame_support::{
+      dispatch::{GetDispatchInfo, PostDispatchInfo, WithPostDispatchInfo},
+      storage::with_storage_layer,
+      traits::{
+        fungibles,
+        tokens::{self, DepositConsequence, Fortitude, Preservation, Provenance},
+        AccountTouch,
+      },
+    },
+    sp_runtime::traits::{Dispatchable, MaybeSerializeDeserialize},
+  },
+  prelude::*,
+};
+use polkadot_sdk::{
+  pallet_asset_conversion::{self, PoolLocator},
+  pallet_balances,
+};
 
 pub mod traits;
 pub use traits::*;
@@ -27,12 +53,35 @@ pub use traits::*;
 pub mod adapters;
 pub use adapters::*;
 
+pub mod circuit_breaker;
+
+pub mod credit;
+pub use circuit_breaker::{PairObservation, TripReason};
+
+pub mod migrations;
+
+pub mod runtime_api;
+
+pub mod zap;
+
+pub mod weights;
+pub use weights::*;
+
+#[cfg(feature = "runtime-benchmarks")]
+pub mod benchmarking;
+
 pub use pallet::*;
 
+/// Longest swap path the router executes, one pool hop between each pair of adjacent assets.
+pub const MAX_SUPPORTED_PATH_LENGTH: u32 = 5;
+
+#[cfg(all(test, feature = "runtime-benchmarks"))]
+mod mock;
+
 #[cfg(test)]
 pub mod tests;
 
-#[frame::pallet(dev_mode)]
+#[frame::pallet]
 pub mod pallet {
   use super::*;
 
@@ -49,13 +98,18 @@ pub mod pallet {
       + AtLeast32BitUnsigned
       + Saturating
       + CheckedSub
-      + PartialOrd;
+      + PartialOrd
+      + MaxEncodedLen
+      + MaybeSerializeDeserialize
+      + tokens::Balance;
 
     /// The asset kind type used by the pallet.
-    type AssetKind: Parameter + Member + Copy;
+    type AssetKind: Parameter + Member + Clone + Ord + MaxEncodedLen + MaybeSerializeDeserialize;
 
     /// Router fee percentage for buyback mechanism (e.g., 20 = 0.2%).
     /// This fee is used for buying back and burning the base network asset.
+    ///
+    /// Applies until `AdminOrigin` changes the fee settings with `set_fee_settings`.
     #[pallet::constant]
     type RouterFee: Get<Permill>;
 
@@ -64,30 +118,306 @@ pub mod pallet {
     #[pallet::constant]
     type RouterFeeCollector: Get<Self::AccountId>;
 
+    /// Account that receives `TreasuryFeeShare` of the router fees, e.g. the treasury.
+    #[pallet::constant]
+    type TreasuryAccount: Get<Self::AccountId>;
+
+    /// Share of each router fee sent to `TreasuryAccount`; the rest goes to
+    /// `RouterFeeCollector`.
+    ///
+    /// Applies until `AdminOrigin` changes the fee settings with `set_fee_settings`.
+    #[pallet::constant]
+    type TreasuryFeeShare: Get<Permill>;
+
+    /// Fee charged on the input of flash swaps on top of the router fee, split like it.
+    ///
+    /// Applies until `AdminOrigin` changes the fee settings with `set_fee_settings`.
+    #[pallet::constant]
+    type FlashSwapFee: Get<Permill>;
+
+    /// Calls dispatched by flash swap borrowers before they repay.
+    type RuntimeCall: Parameter
+      + Dispatchable<RuntimeOrigin = Self::RuntimeOrigin, PostInfo = PostDispatchInfo>
+      + GetDispatchInfo
+      + From<frame_system::Call<Self>>;
+
+    /// Origin allowed to manage the router (pause trading, reset tripped circuit breakers).
+    type AdminOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+    /// Maximum price movement a single trade may cause. A pair trips once its price has moved
+    /// further than this from the price at the start of `PriceWindow`.
+    #[pallet::constant]
+    type MaxPriceMovement: Get<Permill>;
+
+    /// Number of blocks the reference price of a pair is kept for.
+    #[pallet::constant]
+    type PriceWindow: Get<BlockNumberFor<Self>>;
+
+    /// Maximum volume traded on a pair within a single block, as a share of the input reserves.
+    /// A single trade above it is rejected; the pair trips once the block's volume exceeds it.
+    #[pallet::constant]
+    type MaxVolumePerBlock: Get<Permill>;
+
+    /// Number of blocks after which a tripped pair is automatically re-enabled.
+    #[pallet::constant]
+    type CircuitBreakerCooldown: Get<BlockNumberFor<Self>>;
+
     /// Weight information for extrinsics.
     type WeightInfo: WeightInfo;
 
     /// Asset Conversion pallet for XYK AMM integration.
+    ///
+    /// Must hold its pool reserves in `Assets`, so credit swaps can go through its pools, and
+    /// identify pools by their asset pair, so `best_route` can find the assets pooled with the
+    /// input.
     type AssetConversion: pallet_asset_conversion::Config<
       AssetKind = Self::AssetKind,
       Balance = Self::Balance,
       AccountId = Self::AccountId,
+      Assets = Self::Assets,
+      PoolId = (Self::AssetKind, Self::AssetKind),
     >;
 
     /// Balances pallet for fee collection.
     type Balances: pallet_balances::Config<Balance = Self::Balance, AccountId = Self::AccountId>;
+
+    /// Fungible assets routed by the router, addressed by `AssetKind`.
+    ///
+    /// Router fees are collected in the input asset through this implementation, which creates
+    /// the accounts of the fee collector and the treasury for the assets they are charged in.
+    type Assets: fungibles::Mutate<Self::AccountId, AssetId = Self::AssetKind, Balance = Self::Balance>
+      + fungibles::Balanced<Self::AccountId>
+      + AccountTouch<Self::AssetKind, Self::AccountId, Balance = Self::Balance>;
+
+    /// Delivers the output of `swap_and_send` and `remote_swap` to other chains.
+    type CrossChainTransfer: CrossChainTransfer<Self::AccountId, Self::AssetKind, Self::Balance>;
+
+    /// Helper to set up pools for benchmarks.
+    #[cfg(feature = "runtime-benchmarks")]
+    type BenchmarkHelper: benchmarking::BenchmarkHelper<
+      Self::AccountId,
+      Self::AssetKind,
+      Self::Balance,
+    >;
+
+    /// Origin of other chains swapping through `remote_swap`.
+    ///
+    /// Yields the account paying for the swap and the chain the output is sent back to.
+    type RemoteOrigin: EnsureOrigin<
+      Self::RuntimeOrigin,
+      Success = (Self::AccountId, CrossChainLocationOf<Self>),
+    >;
   }
 
+  /// Destination and beneficiary type of cross-chain transfers.
+  pub type CrossChainLocationOf<T> = <<T as Config>::CrossChainTransfer as CrossChainTransfer<
+    <T as frame_system::Config>::AccountId,
+    <T as Config>::AssetKind,
+    <T as Config>::Balance,
+  >>::Location;
+
+  /// LP token type of the pools the router adds liquidity to.
+  pub type PoolAssetIdOf<T> =
+    <<T as Config>::AssetConversion as pallet_asset_conversion::Config>::PoolAssetId;
+
+  /// LP token balances of the pools the router adds liquidity to.
+  type PoolAssetsOf<T> =
+    <<T as Config>::AssetConversion as pallet_asset_conversion::Config>::PoolAssets;
+
+  /// The in-code storage version; see [`crate::migrations`].
+  const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
   #[pallet::pallet]
+  #[pallet::storage_version(STORAGE_VERSION)]
   pub struct Pallet<T>(_);
 
+  /// Price and volume observations per pair, keyed by the ordered asset pair.
+  #[pallet::storage]
+  pub type PairObservations<T: Config> = StorageMap<
+    _,
+    Blake2_128Concat,
+    (T::AssetKind, T::AssetKind),
+    PairObservation<BlockNumberFor<T>>,
+    OptionQuery,
+  >;
+
+  /// Pairs whose circuit breaker has tripped, with the block it tripped at.
+  #[pallet::storage]
+  pub type TrippedPairs<T: Config> =
+    StorageMap<_, Blake2_128Concat, (T::AssetKind, T::AssetKind), BlockNumberFor<T>, OptionQuery>;
+
+  /// Whether all router swaps are paused.
+  #[pallet::storage]
+  pub type RouterPaused<T> = StorageValue<_, bool, ValueQuery>;
+
+  /// Pairs paused by the admin, keyed by the ordered asset pair.
+  #[pallet::storage]
+  pub type PausedPairs<T: Config> =
+    StorageMap<_, Blake2_128Concat, (T::AssetKind, T::AssetKind), (), OptionQuery>;
+
+  /// AMMs paused by the admin; the router does not quote or route through them.
+  #[pallet::storage]
+  pub type PausedAMMs<T> = StorageMap<_, Blake2_128Concat, AMMType, (), OptionQuery>;
+
+  /// Ordered pair whose pool has lent the output of a flash swap that is not repaid yet.
+  ///
+  /// Only set while the borrower's call executes; swaps and liquidity changes on the pair are
+  /// rejected meanwhile.
+  #[pallet::storage]
+  pub type FlashSwapPair<T: Config> = StorageValue<_, (T::AssetKind, T::AssetKind), OptionQuery>;
+
+  /// Fee settings set through `set_fee_settings`, applied instead of the configured ones.
   #[pallet::storage]
-  #[pallet::getter(fn something)]
-  /// A storage item for the pallet.
-  pub type Something<T> = StorageValue<_, u32, ValueQuery>;
+  pub type FeeSettingsOverride<T> = StorageValue<_, FeeSettings, OptionQuery>;
+
+  /// Router fees charged and received per asset, checked against each other by `try_state`.
+  #[pallet::storage]
+  pub type FeeLedger<T: Config> =
+    StorageMap<_, Blake2_128Concat, T::AssetKind, FeeLedgerEntry<T::Balance>, ValueQuery>;
+
+  /// A pool seeded at genesis: `(asset1, asset2, amount1, amount2, provider)`.
+  pub type PoolSeedOf<T> = (
+    <T as Config>::AssetKind,
+    <T as Config>::AssetKind,
+    <T as Config>::Balance,
+    <T as Config>::Balance,
+    <T as frame_system::Config>::AccountId,
+  );
+
+  #[pallet::genesis_config]
+  #[derive(DefaultNoBound)]
+  pub struct GenesisConfig<T: Config> {
+    /// Asset conversion pools to create and seed with liquidity at genesis.
+    ///
+    /// Each entry is `(asset1, asset2, amount1, amount2, provider)`. The provider must hold both
+    /// amounts at genesis, e.g. through the balances and assets genesis configs, and receives the
+    /// LP tokens.
+    pub pools: Vec<PoolSeedOf<T>>,
+  }
+
+  #[pallet::genesis_build]
+  impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
+    fn build(&self) {
+      for (asset1, asset2, amount1, amount2, provider) in &self.pools {
+        Pallet::<T>::do_create_pool(provider, asset1, asset2)
+          .expect("genesis pools must be unique and valid pairs");
+        Pallet::<T>::add_pool_liquidity(
+          provider,
+          asset1.clone(),
+          asset2.clone(),
+          (*amount1, *amount2),
+          (*amount1, *amount2),
+        )
+        .expect("genesis pool providers must hold the seeded liquidity");
+      }
+    }
+  }
 
   #[pallet::hooks]
-  impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+  impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+    #[cfg(feature = "try-runtime")]
+    fn try_state(_n: BlockNumberFor<T>) -> Result<(), TryRuntimeError> {
+      Self::do_try_state()
+    }
+  }
+
+  #[pallet::view_functions_experimental]
+  impl<T: Config> Pallet<T> {
+    /// Quote the output of swapping `amount_in` of `asset_in`, net of router and AMM fees.
+    pub fn quote_exact_tokens_for_tokens(
+      asset_in: T::AssetKind,
+      asset_out: T::AssetKind,
+      amount_in: T::Balance,
+    ) -> Option<T::Balance> {
+      Self::quote_hop(&asset_in, &asset_out, amount_in).map(|(_, quote)| quote)
+    }
+
+    /// Quote the amount of `asset_in` a swap needs to receive at least `amount_out` of
+    /// `asset_out`, including router and AMM fees.
+    pub fn quote_tokens_for_exact_tokens(
+      asset_in: T::AssetKind,
+      asset_out: T::AssetKind,
+      amount_out: T::Balance,
+    ) -> Option<T::Balance> {
+      if Self::is_amm_paused(AMMType::XYK) {
+        return None;
+      }
+      let amount_after_router_fee =
+        pallet_asset_conversion::Pallet::<T::AssetConversion>::quote_price_tokens_for_exact_tokens(
+          asset_in, asset_out, amount_out, true,
+        )?;
+      Some(
+        Self::fee_settings()
+          .router_fee
+          .left_from_one()
+          .saturating_reciprocal_mul_ceil(amount_after_router_fee),
+      )
+    }
+
+    /// Best route for swapping `amount_in` of `asset_in` to `asset_out` and its quoted output.
+    ///
+    /// Compares the direct pair with every two-hop route through an asset pooled with
+    /// `asset_in`, which iterates over all pools. Each hop is charged the router fee, like a swap
+    /// along the route. Returns `None` if no AMM can route the swap. Pauses of the router or the
+    /// pair are reported by `pause_status` instead.
+    pub fn best_route(
+      asset_in: T::AssetKind,
+      asset_out: T::AssetKind,
+      amount_in: T::Balance,
+    ) -> Option<SwapRoute<T::AssetKind, T::Balance>> {
+      let direct =
+        Self::quote_hop(&asset_in, &asset_out, amount_in).map(|(amm, amount_out)| SwapRoute {
+          path: vec![asset_in.clone(), asset_out.clone()],
+          amms: vec![amm],
+          amount_out,
+        });
+      let two_hop = pallet_asset_conversion::Pools::<T::AssetConversion>::iter_keys()
+        .filter_map(
+          |(asset_a, asset_b)| match (asset_a == asset_in, asset_b == asset_in) {
+            (true, _) => Some(asset_b),
+            (_, true) => Some(asset_a),
+            _ => None,
+          },
+        )
+        .filter(|via| *via != asset_out)
+        .filter_map(|via| {
+          let (amm_in, amount_via) = Self::quote_hop(&asset_in, &via, amount_in)?;
+          let (amm_out, amount_out) = Self::quote_hop(&via, &asset_out, amount_via)?;
+          Some(SwapRoute {
+            path: vec![asset_in.clone(), via, asset_out.clone()],
+            amms: vec![amm_in, amm_out],
+            amount_out,
+          })
+        });
+
+      // The direct route wins ties, as it crosses fewer pools
+      direct.into_iter().chain(two_hop).reduce(|best, route| {
+        if route.amount_out > best.amount_out {
+          route
+        } else {
+          best
+        }
+      })
+    }
+
+    /// Fee settings the router currently applies.
+    pub fn fee_settings() -> FeeSettings {
+      FeeSettingsOverride::<T>::get().unwrap_or(FeeSettings {
+        router_fee: T::RouterFee::get(),
+        treasury_fee_share: T::TreasuryFeeShare::get(),
+        flash_swap_fee: T::FlashSwapFee::get(),
+      })
+    }
+
+    /// Pause status of the router as seen by swaps between `asset_a` and `asset_b`.
+    pub fn pause_status(asset_a: T::AssetKind, asset_b: T::AssetKind) -> PauseStatus {
+      PauseStatus {
+        router_paused: RouterPaused::<T>::get(),
+        pair_paused: PausedPairs::<T>::contains_key(Self::pair_key(asset_a, asset_b)),
+        paused_amms: PausedAMMs::<T>::iter_keys().collect(),
+      }
+    }
+  }
 
   impl<T: Config> Pallet<T> {
     /// Get the XYK adapter for Asset Conversion integration.
@@ -95,196 +425,1604 @@ pub mod pallet {
       XYKAdapter::new()
     }
 
-    /// Get the default fee collector.
-    fn get_fee_collector() -> DefaultFeeCollector<T::Balances, T::AccountId> {
-      DefaultFeeCollector::new(T::RouterFeeCollector::get())
+    /// Get the fee collector, which splits router fees in the input asset between the fee
+    /// collector and the treasury.
+    pub(crate) fn get_fee_collector() -> SplitFeeCollector<T::Assets, T::AccountId> {
+      SplitFeeCollector::new(
+        T::RouterFeeCollector::get(),
+        T::TreasuryAccount::get(),
+        Self::fee_settings().treasury_fee_share,
+      )
     }
 
-    /// Get quote from available AMMs for the given asset pair.
-    fn get_best_quote(
+    /// Quote a single hop of a router swap of `amount_in`, net of the router fee, along with the
+    /// AMM quoting it.
+    fn quote_hop(
       asset_in: &T::AssetKind,
       asset_out: &T::AssetKind,
       amount_in: T::Balance,
-    ) -> Option<T::Balance> {
+    ) -> Option<(AMMType, T::Balance)> {
+      let router_fee = Self::fee_settings().router_fee.mul_floor(amount_in);
+      Self::get_best_quote(asset_in, asset_out, amount_in.checked_sub(&router_fee)?)
+    }
+
+    /// Get quote from available AMMs for the given asset pair, along with the AMM quoting it.
+    pub(crate) fn get_best_quote(
+      asset_in: &T::AssetKind,
+      asset_out: &T::AssetKind,
+      amount_in: T::Balance,
+    ) -> Option<(AMMType, T::Balance)> {
       let xyk_adapter = Self::get_xyk_adapter();
 
-      if xyk_adapter.can_handle_pair(asset_in, asset_out) {
-        xyk_adapter.quote_price(asset_in, asset_out, amount_in)
+      if !Self::is_amm_paused(AMMType::XYK) && xyk_adapter.can_handle_pair(asset_in, asset_out) {
+        let quote = xyk_adapter.quote_price(asset_in, asset_out, amount_in)?;
+        Some((AMMType::XYK, quote))
       } else {
         None
       }
     }
 
-    /// Execute swap using the best available AMM.
+    /// Execute swap using the best available AMM, returning the output and the AMM used.
     fn execute_best_swap(
       who: &T::AccountId,
       asset_in: T::AssetKind,
       asset_out: T::AssetKind,
       amount_in: T::Balance,
       min_amount_out: T::Balance,
-    ) -> Result<T::Balance, DispatchError> {
+    ) -> Result<(T::Balance, AMMType), DispatchError> {
       let xyk_adapter = Self::get_xyk_adapter();
 
-      if xyk_adapter.can_handle_pair(&asset_in, &asset_out) {
-        xyk_adapter.execute_swap(who, asset_in, asset_out, amount_in, min_amount_out)
+      if !Self::is_amm_paused(AMMType::XYK) && xyk_adapter.can_handle_pair(&asset_in, &asset_out) {
+        let amount_out = xyk_adapter
+          .execute_swap(
+            who,
+            asset_in.clone(),
+            asset_out.clone(),
+            amount_in,
+            min_amount_out,
+          )
+          .map_err(Self::amm_error)?;
+        Self::deposit_hop_executed(asset_in, asset_out, amount_in, amount_out, AMMType::XYK);
+        Ok((amount_out, AMMType::XYK))
       } else {
         Err(Error::<T>::NoCompatibleAMM.into())
       }
     }
-  }
 
-  #[pallet::event]
-  #[pallet::generate_deposit(pub(super) fn deposit_event)]
-  pub enum Event<T: Config> {
-    /// A swap was executed through the router with dual fee structure.
-    SwapExecuted {
-      /// The account that initiated the swap.
-      who: T::AccountId,
-      /// The input asset.
+    /// Report a swap through a single pool, along with its reserves after the swap.
+    pub(crate) fn deposit_hop_executed(
       asset_in: T::AssetKind,
-      /// The output asset.
       asset_out: T::AssetKind,
-      /// The amount of input asset (total user payment).
       amount_in: T::Balance,
-      /// The amount of output asset received.
       amount_out: T::Balance,
-      /// The router fee collected (0.2% for buyback mechanism).
-      router_fee: T::Balance,
-      /// The AMM that was used.
-      amm_used: AMMType,
-    },
-  }
+      amm: AMMType,
+    ) {
+      let (Ok(pool), Ok((reserve_in, reserve_out))) = (
+        Self::pool_account(&asset_in, &asset_out),
+        pallet_asset_conversion::Pallet::<T::AssetConversion>::get_reserves(
+          asset_in.clone(),
+          asset_out.clone(),
+        ),
+      ) else {
+        return;
+      };
+      Self::deposit_event(Event::HopExecuted {
+        pool,
+        asset_in,
+        asset_out,
+        amount_in,
+        amount_out,
+        amm,
+        reserve_in,
+        reserve_out,
+        price: circuit_breaker::spot_price(amount_in, amount_out).unwrap_or_default(),
+      });
+    }
 
-  #[pallet::error]
-  pub enum Error<T> {
-    /// No compatible AMM found for the given asset pair.
-    NoCompatibleAMM,
-    /// No liquidity available for the swap.
-    NoLiquidityAvailable,
-    /// Invalid swap path provided.
-    InvalidPath,
-    /// Fee calculation failed.
-    FeeCalculationFailed,
-  }
+    /// Account holding the reserves of the asset conversion pool of a pair.
+    pub(crate) fn pool_account(
+      asset_a: &T::AssetKind,
+      asset_b: &T::AssetKind,
+    ) -> Result<T::AccountId, DispatchError> {
+      type Locator<T> =
+        <<T as Config>::AssetConversion as pallet_asset_conversion::Config>::PoolLocator;
+      Locator::<T>::pool_id(asset_a, asset_b)
+        .and_then(|pool_id| Locator::<T>::address(&pool_id))
+        .map_err(|_| Error::<T>::InvalidPath.into())
+    }
 
-  #[pallet::call]
-  impl<T: Config> Pallet<T> {
-    /// Execute a token swap through the best available AMM.
-    #[pallet::call_index(0)]
-    #[pallet::weight(T::WeightInfo::swap_exact_tokens_for_tokens())]
-    pub fn swap_exact_tokens_for_tokens(
-      origin: OriginFor<T>,
-      path: BoundedVec<T::AssetKind, ConstU32<5>>,
-      amount_in: T::Balance,
-      amount_out_min: T::Balance,
-      _send_to: T::AccountId,
-      _keep_alive: bool,
+    /// Collect `amount` of `asset` from `who` as router fee, reporting where each part went.
+    ///
+    /// Fails with `FeeNotCollectable` if the fee collector or the treasury cannot receive its
+    /// share.
+    fn collect_router_fee(
+      who: &T::AccountId,
+      asset: &T::AssetKind,
+      amount: T::Balance,
     ) -> DispatchResult {
-      let who = ensure_signed(origin)?;
+      let credited = Self::get_fee_collector()
+        .collect_fee(who, asset, amount)
+        .map_err(|_| Error::<T>::FeeNotCollectable)?;
+      let received = credited
+        .iter()
+        .fold(T::Balance::zero(), |total, (_, amount)| {
+          total.saturating_add(*amount)
+        });
+      Self::note_router_fee(asset, amount, received);
+      for (destination, amount) in credited {
+        Self::deposit_event(Event::FeeCollected {
+          asset: asset.clone(),
+          amount,
+          destination,
+        });
+      }
+      Ok(())
+    }
 
-      // Currently only support direct swaps (path length = 2)
-      ensure!(path.len() == 2, Error::<T>::InvalidPath);
+    /// Add a router fee of `charged` of `asset`, of which the fee accounts were credited with
+    /// `received`, to the fee ledger.
+    pub(crate) fn note_router_fee(asset: &T::AssetKind, charged: T::Balance, received: T::Balance) {
+      FeeLedger::<T>::mutate(asset, |entry| {
+        entry.charged = entry.charged.saturating_add(charged);
+        entry.received = entry.received.saturating_add(received);
+      });
+    }
 
-      let asset_in = path[0];
-      let asset_out = path[1];
+    /// Reject a router fee of `amount` of `asset` that the fee collector or the treasury could
+    /// not receive its share of.
+    pub(crate) fn ensure_fee_collectable(
+      asset: &T::AssetKind,
+      amount: T::Balance,
+    ) -> DispatchResult {
+      use fungibles::Inspect;
 
-      // DUAL FEE STRUCTURE IMPLEMENTATION (according to tokenomics):
-      //
-      // 1. Router Fee (0.2%): Goes to buyback and burning of base network asset
-      //    - Collected by DEX Router before passing to AssetConversion
-      //    - Used for token buyback mechanism to support token price
-      //
-      // 2. XYK Pool Fee (0.3%): Goes to liquidity providers
-      //    - Handled internally by AssetConversion pallet
-      //    - Increases pool liquidity over time
-      //
-      // 3. Total User Cost: 0.5% (0.2% + 0.3%)
-      //    - User pays full amount_in
-      //    - Router takes 0.2% for buyback
-      //    - Remaining amount goes to AssetConversion (which takes its own 0.3%)
+      for (account, share) in Self::get_fee_collector().shares(amount) {
+        ensure!(
+          share.is_zero()
+            || T::Assets::can_deposit(asset.clone(), &account, share, Provenance::Extant)
+              == DepositConsequence::Success,
+          Error::<T>::FeeNotCollectable
+        );
+      }
+      Ok(())
+    }
 
-      // Calculate router fee (0.2% for buyback mechanism)
-      let router_fee = T::RouterFee::get().mul_floor(amount_in);
-      let amount_after_router_fee = amount_in
-        .checked_sub(&router_fee)
-        .ok_or(Error::<T>::FeeCalculationFailed)?;
+    /// Make sure the fee collector and the treasury can receive router fees of any size in
+    /// `asset`, creating their accounts for it at the expense of `depositor` where needed.
+    ///
+    /// Native fees need both accounts to be endowed, e.g. at genesis.
+    pub fn touch_fee_accounts(asset: &T::AssetKind, depositor: &T::AccountId) -> DispatchResult {
+      use fungibles::Inspect;
 
-      // Get quote from available AMMs (using amount after router fee)
-      // AssetConversion will apply its own 0.3% fee on top of this amount
-      let quote = Self::get_best_quote(&asset_in, &asset_out, amount_after_router_fee)
-        .ok_or(Error::<T>::NoLiquidityAvailable)?;
+      for account in [T::RouterFeeCollector::get(), T::TreasuryAccount::get()] {
+        if T::Assets::can_deposit(asset.clone(), &account, One::one(), Provenance::Extant)
+          != DepositConsequence::Success
+        {
+          T::Assets::touch(asset.clone(), &account, depositor)?;
+        }
+      }
+      Ok(())
+    }
 
-      // Ensure the quote meets minimum requirements
-      ensure!(quote >= amount_out_min, Error::<T>::NoLiquidityAvailable);
+    /// Map why an AMM failed to the router error telling the trader about it.
+    fn amm_error(error: AMMError) -> DispatchError {
+      match error {
+        AMMError::PoolNotFound => Error::<T>::PoolNotFound.into(),
+        AMMError::InsufficientLiquidity => Error::<T>::InsufficientLiquidity.into(),
+        AMMError::SlippageExceeded => Error::<T>::SlippageExceeded.into(),
+        AMMError::InsufficientBalance => Error::<T>::InsufficientBalance.into(),
+        AMMError::BelowMinimum => Error::<T>::BelowMinimum.into(),
+        AMMError::AssetUnavailable => Error::<T>::AssetUnavailable.into(),
+        AMMError::Other(error) => error,
+      }
+    }
 
-      // Execute the swap through the best available AMM
-      // AssetConversion will deduct its 0.3% fee from amount_after_router_fee
-      let actual_amount_out = Self::execute_best_swap(
-        &who,
-        asset_in,
-        asset_out,
-        amount_after_router_fee,
-        amount_out_min,
+    /// Create the pool for a pair on behalf of `who` unless it already exists.
+    fn ensure_pool(
+      who: &T::AccountId,
+      asset1: &T::AssetKind,
+      asset2: &T::AssetKind,
+    ) -> DispatchResult {
+      let pool_id = <T::AssetConversion as pallet_asset_conversion::Config>::PoolLocator::pool_id(
+        asset1, asset2,
       )
-      .map_err(|_| Error::<T>::NoLiquidityAvailable)?;
-
-      // Collect router fees for buyback and burning mechanism (0.2%)
-      // This fee is sent to the configured fee collector account
-      if !router_fee.is_zero() {
-        let fee_collector = Self::get_fee_collector();
-        fee_collector
-          .collect_fee(&who, &asset_in, router_fee)
-          .map_err(|_| Error::<T>::FeeCalculationFailed)?;
+      .map_err(|_| Error::<T>::InvalidPath)?;
+      if !pallet_asset_conversion::Pools::<T::AssetConversion>::contains_key(&pool_id) {
+        Self::do_create_pool(who, asset1, asset2)?;
       }
+      Ok(())
+    }
 
-      // FEE DISTRIBUTION SUMMARY:
-      // - User pays: amount_in (100%)
-      // - Router takes: router_fee (0.2%) → buyback mechanism
-      // - AssetConversion receives: amount_after_router_fee (99.8%)
-      // - AssetConversion takes: 0.3% of amount_after_router_fee → liquidity providers
-      // - Actual swap amount: ~99.5% of original amount_in
-      // - Total effective fee: ~0.5% of amount_in
+    /// Signed origin of `who` for calls into `pallet_asset_conversion`.
+    fn signed_origin(who: &T::AccountId) -> OriginFor<T::AssetConversion> {
+      frame_system::RawOrigin::Signed(who.clone()).into()
+    }
 
-      // Emit event
-      Self::deposit_event(Event::SwapExecuted {
+    /// Create the pool for a pair, paying the pool setup fee from `who`.
+    ///
+    /// `who` also pays for the accounts the fee collector and the treasury need to receive router
+    /// fees in either asset; see [`Pallet::touch_fee_accounts`].
+    pub fn do_create_pool(
+      who: &T::AccountId,
+      asset1: &T::AssetKind,
+      asset2: &T::AssetKind,
+    ) -> DispatchResult {
+      pallet_asset_conversion::Pallet::<T::AssetConversion>::create_pool(
+        Self::signed_origin(who),
+        Box::new(asset1.clone()),
+        Box::new(asset2.clone()),
+      )?;
+      Self::touch_fee_accounts(asset1, who)?;
+      Self::touch_fee_accounts(asset2, who)
+    }
+
+    /// LP token of the pool of a pair.
+    fn lp_token(
+      asset1: &T::AssetKind,
+      asset2: &T::AssetKind,
+    ) -> Result<PoolAssetIdOf<T>, DispatchError> {
+      let pool_id = <T::AssetConversion as pallet_asset_conversion::Config>::PoolLocator::pool_id(
+        asset1, asset2,
+      )
+      .map_err(|_| Error::<T>::InvalidPath)?;
+      pallet_asset_conversion::Pools::<T::AssetConversion>::get(pool_id)
+        .map(|pool| pool.lp_token)
+        .ok_or_else(|| Error::<T>::PoolNotFound.into())
+    }
+
+    /// Add liquidity from `who` to the pool of a pair, taking up to the `desired` amounts at the
+    /// pool ratio and at least the `min` amounts. Returns the LP tokens minted to `who`.
+    fn add_pool_liquidity(
+      who: &T::AccountId,
+      asset1: T::AssetKind,
+      asset2: T::AssetKind,
+      desired: (T::Balance, T::Balance),
+      min: (T::Balance, T::Balance),
+    ) -> Result<T::Balance, DispatchError> {
+      use fungibles::Inspect;
+
+      let lp_token = Self::lp_token(&asset1, &asset2)?;
+      let lp_before = PoolAssetsOf::<T>::balance(lp_token.clone(), who);
+      pallet_asset_conversion::Pallet::<T::AssetConversion>::add_liquidity(
+        Self::signed_origin(who),
+        Box::new(asset1),
+        Box::new(asset2),
+        desired.0,
+        desired.1,
+        min.0,
+        min.1,
+        who.clone(),
+      )?;
+      Ok(PoolAssetsOf::<T>::balance(lp_token, who).saturating_sub(lp_before))
+    }
+
+    /// Burn `lp_token_burn` LP tokens of `who` and withdraw at least the `min` amounts of both
+    /// sides of the pool to `who`. Returns the amounts withdrawn.
+    fn remove_pool_liquidity(
+      who: &T::AccountId,
+      asset1: T::AssetKind,
+      asset2: T::AssetKind,
+      lp_token_burn: T::Balance,
+      min: (T::Balance, T::Balance),
+    ) -> Result<(T::Balance, T::Balance), DispatchError> {
+      use fungibles::Inspect;
+
+      let before = (
+        T::Assets::balance(asset1.clone(), who),
+        T::Assets::balance(asset2.clone(), who),
+      );
+      pallet_asset_conversion::Pallet::<T::AssetConversion>::remove_liquidity(
+        Self::signed_origin(who),
+        Box::new(asset1.clone()),
+        Box::new(asset2.clone()),
+        lp_token_burn,
+        min.0,
+        min.1,
+        who.clone(),
+      )?;
+      Ok((
+        T::Assets::balance(asset1, who).saturating_sub(before.0),
+        T::Assets::balance(asset2, who).saturating_sub(before.1),
+      ))
+    }
+
+    /// Deposit `amount_in` of `asset_in` into its pool with `asset_other` on behalf of `who`.
+    ///
+    /// Swaps the share of the input given by [`zap::optimal_swap_amount`] through the router,
+    /// then adds the rest and the swap output as liquidity. Whatever the pool does not take at
+    /// its current ratio stays with `who`, as does enough of the swap output to keep the account
+    /// of `who` alive. Returns the LP tokens minted and the route of the swap.
+    pub fn do_zap_in(
+      who: &T::AccountId,
+      asset_in: T::AssetKind,
+      asset_other: T::AssetKind,
+      amount_in: T::Balance,
+      min_lp_minted: T::Balance,
+    ) -> Result<(T::Balance, ExecutedRoute), DispatchError> {
+      ensure!(
+        asset_in != asset_other && !amount_in.is_zero(),
+        Error::<T>::InvalidZap
+      );
+
+      let (reserve_in, _) = pallet_asset_conversion::Pallet::<T::AssetConversion>::get_reserves(
+        asset_in.clone(),
+        asset_other.clone(),
+      )
+      .map_err(|_| Error::<T>::NoLiquidityAvailable)?;
+      let swap_fee = zap::combined_swap_fee(
+        Self::fee_settings().router_fee,
+        <T::AssetConversion as pallet_asset_conversion::Config>::LPFee::get(),
+      );
+      let swap_amount = zap::optimal_swap_amount(reserve_in, amount_in, swap_fee);
+      ensure!(!swap_amount.is_zero(), Error::<T>::InvalidZap);
+
+      let (amount_bought, route) = Self::do_swap_with_route(
+        who,
+        asset_in.clone(),
+        asset_other.clone(),
+        swap_amount,
+        One::one(),
+      )?;
+      // The pool only takes what `who` can transfer without being reaped
+      let amount_other = amount_bought.min(
+        <T::Assets as fungibles::Inspect<T::AccountId>>::reducible_balance(
+          asset_other.clone(),
+          who,
+          Preservation::Preserve,
+          Fortitude::Polite,
+        ),
+      );
+      let lp_minted = Self::add_pool_liquidity(
         who,
+        asset_in.clone(),
+        asset_other.clone(),
+        (amount_in.saturating_sub(swap_amount), amount_other),
+        (Zero::zero(), Zero::zero()),
+      )?;
+      ensure!(lp_minted >= min_lp_minted, Error::<T>::SlippageExceeded);
+
+      Self::deposit_event(Event::ZappedIn {
+        who: who.clone(),
         asset_in,
-        asset_out,
+        asset_other,
         amount_in,
-        amount_out: actual_amount_out,
-        router_fee,
-        amm_used: AMMType::XYK,
+        lp_minted,
       });
 
-      Ok(())
+      Ok((lp_minted, route))
     }
-  }
-}
 
-/// Weight information for pallet extrinsics.
-pub trait WeightInfo {
-  fn swap_exact_tokens_for_tokens() -> Weight;
-}
+    /// Burn `lp_token_burn` LP tokens of a pool on behalf of `who` and convert both withdrawn
+    /// sides to `asset_out`, which must be one of the pair.
+    ///
+    /// The other side is swapped through the router. Returns the total amount of `asset_out` and
+    /// the route of the swap.
+    pub fn do_zap_out(
+      who: &T::AccountId,
+      asset1: T::AssetKind,
+      asset2: T::AssetKind,
+      lp_token_burn: T::Balance,
+      asset_out: T::AssetKind,
+      amount_out_min: T::Balance,
+    ) -> Result<(T::Balance, ExecutedRoute), DispatchError> {
+      ensure!(
+        asset1 != asset2 && !lp_token_burn.is_zero(),
+        Error::<T>::InvalidZap
+      );
+      let asset_swapped = if asset_out == asset1 {
+        asset2.clone()
+      } else if asset_out == asset2 {
+        asset1.clone()
+      } else {
+        return Err(Error::<T>::InvalidZap.into());
+      };
+      Self::ensure_not_flash_borrowed(&Self::pair_key(asset1.clone(), asset2.clone()))?;
 
-/// Default weights for the pallet
-pub mod weights {
-  use super::*;
+      let (amount1, amount2) = Self::remove_pool_liquidity(
+        who,
+        asset1.clone(),
+        asset2.clone(),
+        lp_token_burn,
+        (Zero::zero(), Zero::zero()),
+      )?;
+      let (amount_kept, amount_swapped) = if asset_out == asset1 {
+        (amount1, amount2)
+      } else {
+        (amount2, amount1)
+      };
 
-  pub trait WeightInfo {
-    fn swap_exact_tokens_for_tokens() -> Weight;
-  }
+      let (amount_bought, route) = if amount_swapped.is_zero() {
+        (Zero::zero(), ExecutedRoute::default())
+      } else {
+        Self::do_swap_with_route(
+          who,
+          asset_swapped,
+          asset_out.clone(),
+          amount_swapped,
+          One::one(),
+        )?
+      };
+      let amount_out = amount_kept.saturating_add(amount_bought);
+      ensure!(amount_out >= amount_out_min, Error::<T>::SlippageExceeded);
+
+      Self::deposit_event(Event::ZappedOut {
+        who: who.clone(),
+        asset1,
+        asset2,
+        lp_burned: lp_token_burn,
+        asset_out,
+        amount_out,
+      });
 
-  pub struct SubstrateWeight<T>(core::marker::PhantomData<T>);
-  impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
-    fn swap_exact_tokens_for_tokens() -> Weight {
-      Weight::from_parts(10_000, 0)
+      Ok((amount_out, route))
     }
-  }
-}
 
-/// Default weights implementation.
-impl WeightInfo for () {
-  fn swap_exact_tokens_for_tokens() -> Weight {
-    Weight::from_parts(10_000, 0)
+    /// Whether the given AMM has been paused by the admin.
+    pub fn is_amm_paused(amm: AMMType) -> bool {
+      PausedAMMs::<T>::contains_key(amm)
+    }
+
+    /// Weight of a swap that executed `route`; nothing if no hop was executed.
+    pub fn swap_weight(route: &ExecutedRoute) -> Weight {
+      if route.hops.is_zero() {
+        return Weight::zero();
+      }
+      T::WeightInfo::swap_exact_tokens_for_tokens(route.hops.saturating_add(1))
+    }
+
+    /// Weight of an extrinsic benchmarked with a direct swap, when it executed `route` instead.
+    fn with_route_weight(benchmarked: Weight, route: &ExecutedRoute) -> Weight {
+      benchmarked
+        .saturating_sub(T::WeightInfo::swap_exact_tokens_for_tokens(2))
+        .saturating_add(Self::swap_weight(route))
+    }
+
+    /// Reject swaps while the router or the pair is paused, the circuit breaker of the pair has
+    /// tripped, or every AMM for the pair is paused.
+    pub(crate) fn ensure_trading_enabled(
+      pair: &(T::AssetKind, T::AssetKind),
+      asset_in: &T::AssetKind,
+      asset_out: &T::AssetKind,
+    ) -> DispatchResult {
+      ensure!(!RouterPaused::<T>::get(), Error::<T>::RouterPaused);
+      ensure!(
+        !PausedPairs::<T>::contains_key(pair),
+        Error::<T>::PairPaused
+      );
+      ensure!(!Self::is_tripped(pair), Error::<T>::CircuitBreakerTripped);
+      Self::ensure_not_flash_borrowed(pair)?;
+
+      let xyk_adapter = Self::get_xyk_adapter();
+      if xyk_adapter.can_handle_pair(asset_in, asset_out) {
+        ensure!(!Self::is_amm_paused(AMMType::XYK), Error::<T>::AMMPaused);
+      }
+
+      Ok(())
+    }
+
+    /// Whether the circuit breaker of a pair has tripped and its cooldown has not passed yet.
+    pub fn is_tripped(pair: &(T::AssetKind, T::AssetKind)) -> bool {
+      TrippedPairs::<T>::get(pair).is_some_and(|tripped_at| {
+        frame_system::Pallet::<T>::block_number()
+          < tripped_at.saturating_add(T::CircuitBreakerCooldown::get())
+      })
+    }
+
+    /// Whether a flash swap is waiting for its borrower to repay.
+    pub fn flash_swap_in_progress() -> bool {
+      FlashSwapPair::<T>::exists()
+    }
+
+    /// Reject changes to a pool that has lent the output of an unrepaid flash swap.
+    fn ensure_not_flash_borrowed(pair: &(T::AssetKind, T::AssetKind)) -> DispatchResult {
+      ensure!(
+        FlashSwapPair::<T>::get().as_ref() != Some(pair),
+        Error::<T>::FlashSwapInProgress
+      );
+      Ok(())
+    }
+
+    /// Reject new liquidity for a pair while the router or the pair is paused, or its pool has
+    /// lent the output of an unrepaid flash swap. Withdrawals are only subject to the latter.
+    fn ensure_liquidity_accepted(pair: &(T::AssetKind, T::AssetKind)) -> DispatchResult {
+      ensure!(!RouterPaused::<T>::get(), Error::<T>::RouterPaused);
+      ensure!(
+        !PausedPairs::<T>::contains_key(pair),
+        Error::<T>::PairPaused
+      );
+      Self::ensure_not_flash_borrowed(pair)
+    }
+
+    /// Borrow `amount_out` of `asset_out` from its pool with `asset_in` and repay it with
+    /// `asset_in` after `callback` has run.
+    ///
+    /// `who` owes the input an exact-output swap would take, including the router fee, plus
+    /// `T::FlashSwapFee` of it. The pool receives what it would have received from the swap and
+    /// the fees are collected like the router fee. Fails with `SlippageExceeded` if more than
+    /// `amount_in_max` is due and reverts entirely if `callback` fails or `who` cannot pay.
+    /// Returns the amount paid and the weight `callback` consumed.
+    pub fn do_flash_swap(
+      who: &T::AccountId,
+      asset_in: T::AssetKind,
+      asset_out: T::AssetKind,
+      amount_out: T::Balance,
+      amount_in_max: T::Balance,
+      callback: impl FlashSwapCallback<T::AccountId, T::AssetKind, T::Balance>,
+    ) -> Result<(T::Balance, Weight), DispatchError> {
+      with_storage_layer(|| {
+        ensure!(
+          !Self::flash_swap_in_progress(),
+          Error::<T>::FlashSwapInProgress
+        );
+        ensure!(
+          asset_in != asset_out && !amount_out.is_zero(),
+          Error::<T>::InvalidPath
+        );
+        let pair = Self::pair_key(asset_in.clone(), asset_out.clone());
+        Self::ensure_trading_enabled(&pair, &asset_in, &asset_out)?;
+        let reserves_before = Self::pair_reserves(&pair).ok_or(Error::<T>::NoLiquidityAvailable)?;
+
+        // What the pool takes for `amount_out`, grossed up by the router fee like a swap
+        let pool_in =
+          pallet_asset_conversion::Pallet::<T::AssetConversion>::quote_price_tokens_for_exact_tokens(
+            asset_in.clone(),
+            asset_out.clone(),
+            amount_out,
+            true,
+          )
+          .ok_or(Error::<T>::NoLiquidityAvailable)?;
+        let amount_in = Self::fee_settings()
+          .router_fee
+          .left_from_one()
+          .saturating_reciprocal_mul_ceil(pool_in);
+        let flash_fee = Self::fee_settings().flash_swap_fee.mul_ceil(amount_in);
+        let amount_due = amount_in.saturating_add(flash_fee);
+        ensure!(amount_due <= amount_in_max, Error::<T>::SlippageExceeded);
+        Self::ensure_within_circuit_limits(&pair, &asset_in, reserves_before, pool_in, amount_out)?;
+        // A fee failing later is then down to `who` not repaying
+        Self::ensure_fee_collectable(&asset_in, amount_due.saturating_sub(pool_in))?;
+
+        let pool_account = Self::pool_account(&asset_in, &asset_out)?;
+
+        <T::Assets as fungibles::Mutate<_>>::transfer(
+          asset_out.clone(),
+          &pool_account,
+          who,
+          amount_out,
+          Preservation::Preserve,
+        )?;
+        FlashSwapPair::<T>::put(&pair);
+        let callback_weight =
+          callback.on_flash_swap(who, &asset_in, amount_due, &asset_out, amount_out)?;
+        FlashSwapPair::<T>::kill();
+
+        <T::Assets as fungibles::Mutate<_>>::transfer(
+          asset_in.clone(),
+          who,
+          &pool_account,
+          pool_in,
+          Preservation::Expendable,
+        )
+        .map_err(|_| Error::<T>::FlashSwapNotRepaid)?;
+        Self::collect_router_fee(who, &asset_in, amount_due.saturating_sub(pool_in))
+          .map_err(|_| Error::<T>::FlashSwapNotRepaid)?;
+        Self::deposit_hop_executed(
+          asset_in.clone(),
+          asset_out.clone(),
+          pool_in,
+          amount_out,
+          AMMType::XYK,
+        );
+
+        let volume = Self::trade_volume(&pair, &asset_in, reserves_before, pool_in);
+        Self::record_trade(&pair, reserves_before, volume);
+
+        Self::deposit_event(Event::FlashSwapExecuted {
+          who: who.clone(),
+          asset_in,
+          asset_out,
+          amount_in: amount_due,
+          amount_out,
+          flash_fee,
+        });
+
+        Ok((amount_due, callback_weight))
+      })
+    }
+
+    /// Swap an exact amount of `asset_in` for `asset_out` on behalf of `who`.
+    ///
+    /// Applies pause state, circuit breakers and the router fee exactly like the swap extrinsic,
+    /// so runtime integrations (e.g. XCM asset exchange) can route through the same logic.
+    pub fn do_swap_exact_tokens_for_tokens(
+      who: &T::AccountId,
+      asset_in: T::AssetKind,
+      asset_out: T::AssetKind,
+      amount_in: T::Balance,
+      amount_out_min: T::Balance,
+    ) -> Result<T::Balance, DispatchError> {
+      Self::do_swap_with_route(who, asset_in, asset_out, amount_in, amount_out_min)
+        .map(|(amount_out, _)| amount_out)
+    }
+
+    /// Swap an exact amount of the first asset of `path` for its last asset on behalf of `who`,
+    /// one router swap per pair of adjacent assets.
+    ///
+    /// Only the last hop is held to `amount_out_min`. Returns the output and the route that was
+    /// executed. If a hop fails before the last one, the error carries the weight of the hops up
+    /// to and including the failed one.
+    fn do_swap_path(
+      who: &T::AccountId,
+      path: &[T::AssetKind],
+      amount_in: T::Balance,
+      amount_out_min: T::Balance,
+    ) -> Result<(T::Balance, ExecutedRoute), DispatchErrorWithPostInfo<PostDispatchInfo>> {
+      ensure!(
+        (2..=MAX_SUPPORTED_PATH_LENGTH as usize).contains(&path.len())
+          && path.windows(2).all(|hop| hop[0] != hop[1]),
+        Error::<T>::InvalidPath
+      );
+
+      let hops = path.len() as u32 - 1;
+      let mut route = ExecutedRoute::default();
+      let mut amount = amount_in;
+      for hop in path.windows(2) {
+        let min_out = if route.hops + 1 == hops {
+          amount_out_min
+        } else {
+          One::one()
+        };
+        let (amount_out, hop_route) =
+          Self::do_swap_with_route(who, hop[0].clone(), hop[1].clone(), amount, min_out).map_err(
+            |e| {
+              let charged = ExecutedRoute {
+                hops: route.hops + 1,
+              };
+              if charged.hops < hops {
+                e.with_weight(Self::swap_weight(&charged))
+              } else {
+                e.into()
+              }
+            },
+          )?;
+        route.hops += hop_route.hops;
+        amount = amount_out;
+      }
+
+      Ok((amount, route))
+    }
+
+    /// Like `do_swap_exact_tokens_for_tokens`, also returning the route that was executed.
+    fn do_swap_with_route(
+      who: &T::AccountId,
+      asset_in: T::AssetKind,
+      asset_out: T::AssetKind,
+      amount_in: T::Balance,
+      amount_out_min: T::Balance,
+    ) -> Result<(T::Balance, ExecutedRoute), DispatchError> {
+      // Reject swaps while paused
+      let pair = Self::pair_key(asset_in.clone(), asset_out.clone());
+      Self::ensure_trading_enabled(&pair, &asset_in, &asset_out)?;
+      let reserves_before = Self::pair_reserves(&pair);
+
+      // DUAL FEE STRUCTURE IMPLEMENTATION (according to tokenomics):
+      //
+      // 1. Router Fee (0.2%): Goes to buyback and burning of base network asset
+      //    - Collected by DEX Router before passing to AssetConversion
+      //    - Used for token buyback mechanism to support token price
+      //
+      // 2. XYK Pool Fee (0.3%): Goes to liquidity providers
+      //    - Handled internally by AssetConversion pallet
+      //    - Increases pool liquidity over time
+      //
+      // 3. Total User Cost: 0.5% (0.2% + 0.3%)
+      //    - User pays full amount_in
+      //    - Router takes 0.2% for buyback
+      //    - Remaining amount goes to AssetConversion (which takes its own 0.3%)
+
+      // Calculate router fee (0.2% for buyback mechanism)
+      let router_fee = Self::fee_settings().router_fee.mul_floor(amount_in);
+      let amount_after_router_fee = amount_in
+        .checked_sub(&router_fee)
+        .ok_or(Error::<T>::FeeCalculationFailed)?;
+
+      // Get quote from available AMMs (using amount after router fee)
+      // AssetConversion will apply its own 0.3% fee on top of this amount
+      let route = ExecutedRoute { hops: 1 };
+      let (_, quote) = Self::get_best_quote(&asset_in, &asset_out, amount_after_router_fee)
+        .ok_or(Error::<T>::NoLiquidityAvailable)?;
+
+      // Ensure the quote meets minimum requirements
+      ensure!(quote >= amount_out_min, Error::<T>::SlippageExceeded);
+
+      // Reject the trade if it alone would exceed the circuit breaker limits
+      if let Some(reserves_before) = reserves_before {
+        Self::ensure_within_circuit_limits(
+          &pair,
+          &asset_in,
+          reserves_before,
+          amount_after_router_fee,
+          quote,
+        )?;
+      }
+
+      // Some assets fail transfers from frozen accounts with their own errors, so the cause is
+      // taken from a withdrawal check up front
+      <T::Assets as fungibles::Inspect<T::AccountId>>::can_withdraw(
+        asset_in.clone(),
+        who,
+        amount_in,
+      )
+      .into_result(false)
+      .map_err(|e| Self::amm_error(e.into()))?;
+
+      // Execute the swap through the best available AMM
+      // AssetConversion will deduct its 0.3% fee from amount_after_router_fee
+      let (actual_amount_out, amm_used) = Self::execute_best_swap(
+        who,
+        asset_in.clone(),
+        asset_out.clone(),
+        amount_after_router_fee,
+        amount_out_min,
+      )?;
+
+      // Collect router fees for buyback and burning mechanism (0.2%)
+      // This fee is sent to the configured fee collector account
+      if !router_fee.is_zero() {
+        Self::collect_router_fee(who, &asset_in, router_fee)?;
+      }
+
+      // Update the price/volume observation the limits of later trades are checked against
+      if let Some(reserves_before) = reserves_before {
+        let volume = Self::trade_volume(&pair, &asset_in, reserves_before, amount_after_router_fee);
+        Self::record_trade(&pair, reserves_before, volume);
+      }
+
+      // FEE DISTRIBUTION SUMMARY:
+      // - User pays: amount_in (100%)
+      // - Router takes: router_fee (0.2%) → buyback mechanism
+      // - AssetConversion receives: amount_after_router_fee (99.8%)
+      // - AssetConversion takes: 0.3% of amount_after_router_fee → liquidity providers
+      // - Actual swap amount: ~99.5% of original amount_in
+      // - Total effective fee: ~0.5% of amount_in
+
+      // Emit event
+      Self::deposit_event(Event::SwapExecuted {
+        who: Some(who.clone()),
+        asset_in,
+        asset_out,
+        amount_in,
+        amount_out: actual_amount_out,
+        router_fee,
+        amm_used,
+      });
+
+      Ok((actual_amount_out, route))
+    }
+
+    /// Expected output of `swap_and_send` and the delivery fee charged to the sender.
+    ///
+    /// The delivery fee is in the native token; execution on the destination is paid from the
+    /// output itself. Returns `None` if there is no route or the output cannot be delivered.
+    pub fn quote_swap_and_send(
+      asset_in: T::AssetKind,
+      asset_out: T::AssetKind,
+      amount_in: T::Balance,
+      dest: &CrossChainLocationOf<T>,
+      beneficiary: &CrossChainLocationOf<T>,
+    ) -> Option<(T::Balance, T::Balance)> {
+      let amount_out = Self::quote_exact_tokens_for_tokens(asset_in, asset_out.clone(), amount_in)?;
+      let delivery_fee =
+        T::CrossChainTransfer::delivery_fee(&asset_out, amount_out, dest, beneficiary)?;
+      Some((amount_out, delivery_fee))
+    }
+
+    /// Order the assets of a pair so both swap directions share the same storage entries.
+    pub fn pair_key(asset_a: T::AssetKind, asset_b: T::AssetKind) -> (T::AssetKind, T::AssetKind) {
+      if asset_a <= asset_b {
+        (asset_a, asset_b)
+      } else {
+        (asset_b, asset_a)
+      }
+    }
+
+    /// Current pool reserves of an ordered pair.
+    pub(crate) fn pair_reserves(
+      pair: &(T::AssetKind, T::AssetKind),
+    ) -> Option<(T::Balance, T::Balance)> {
+      pallet_asset_conversion::Pallet::<T::AssetConversion>::get_reserves(
+        pair.0.clone(),
+        pair.1.clone(),
+      )
+      .ok()
+    }
+
+    /// Check the router's storage invariants against the pools it trades on.
+    ///
+    /// - every tripped pair still has its observation, and observations are not from the future
+    /// - every pair the router has traded on has an asset conversion pool
+    /// - the reserves of those pools are the full balances of their pool accounts, i.e. nothing
+    ///   in a pool account is held or frozen
+    /// - no flash swap is left waiting for repayment
+    /// - the router fees charged in every asset equal what the fee collector and the treasury were
+    ///   credited with
+    /// - the router fee collector and the treasury are either reaped or hold at least the
+    ///   existential deposit
+    #[cfg(any(feature = "try-runtime", feature = "std"))]
+    pub fn do_try_state() -> Result<(), TryRuntimeError> {
+      use frame::deps::frame_support::traits::fungible::Inspect as _;
+      use fungibles::Inspect as _;
+      use pallet_asset_conversion::PoolLocator;
+      type Locator<T> =
+        <<T as Config>::AssetConversion as pallet_asset_conversion::Config>::PoolLocator;
+
+      let now = frame_system::Pallet::<T>::block_number();
+      for pair in TrippedPairs::<T>::iter_keys() {
+        ensure!(
+          PairObservations::<T>::contains_key(&pair),
+          "tripped pair has no observation"
+        );
+      }
+
+      for (pair, observation) in PairObservations::<T>::iter() {
+        ensure!(
+          observation.window_start <= now && observation.volume_block <= now,
+          "observation is ahead of the current block"
+        );
+
+        let pool_id = Locator::<T>::pool_id(&pair.0, &pair.1)
+          .map_err(|_| TryRuntimeError::Other("observed pair is not a valid pool pair"))?;
+        ensure!(
+          pallet_asset_conversion::Pools::<T::AssetConversion>::contains_key(&pool_id),
+          "observed pair has no pool"
+        );
+
+        let pool_account = Locator::<T>::address(&pool_id)
+          .map_err(|_| TryRuntimeError::Other("pool has no account"))?;
+        let balances = (
+          T::Assets::total_balance(pair.0.clone(), &pool_account),
+          T::Assets::total_balance(pair.1.clone(), &pool_account),
+        );
+        let reserves = Self::pair_reserves(&pair).unwrap_or_default();
+        ensure!(
+          reserves == balances,
+          "pool reserves differ from the pool account balances"
+        );
+      }
+
+      ensure!(!Self::flash_swap_in_progress(), "flash swap left unrepaid");
+
+      for (_, entry) in FeeLedger::<T>::iter() {
+        ensure!(
+          entry.charged == entry.received,
+          "router fees charged differ from the fees received"
+        );
+      }
+
+      for (account, error) in [
+        (
+          T::RouterFeeCollector::get(),
+          "router fee collector is below the existential deposit",
+        ),
+        (
+          T::TreasuryAccount::get(),
+          "treasury is below the existential deposit",
+        ),
+      ] {
+        let balance = pallet_balances::Pallet::<T::Balances>::total_balance(&account);
+        ensure!(
+          balance.is_zero() || balance >= pallet_balances::Pallet::<T::Balances>::minimum_balance(),
+          error
+        );
+      }
+
+      Ok(())
+    }
+
+    /// Share of the input reserve of an ordered pair that `amount_in` of `asset_in` represents.
+    pub(crate) fn trade_volume(
+      pair: &(T::AssetKind, T::AssetKind),
+      asset_in: &T::AssetKind,
+      reserves: (T::Balance, T::Balance),
+      amount_in: T::Balance,
+    ) -> Permill {
+      let reserve_in = if *asset_in == pair.0 {
+        reserves.0
+      } else {
+        reserves.1
+      };
+      circuit_breaker::volume_share(amount_in, reserve_in)
+    }
+
+    /// Reject a trade whose quote exceeds the circuit breaker limits on its own.
+    ///
+    /// The pool is expected to receive `amount_in` of `asset_in` and pay out `amount_out`. The
+    /// trade fails with `CircuitBreakerLimitExceeded` if it would move the price more than
+    /// `MaxPriceMovement`, or trade more than `MaxVolumePerBlock` of the input reserves. Limits
+    /// exceeded by several trades together trip the pair instead; see [`Self::record_trade`].
+    pub(crate) fn ensure_within_circuit_limits(
+      pair: &(T::AssetKind, T::AssetKind),
+      asset_in: &T::AssetKind,
+      reserves_before: (T::Balance, T::Balance),
+      amount_in: T::Balance,
+      amount_out: T::Balance,
+    ) -> DispatchResult {
+      let Some(price_before) = circuit_breaker::spot_price(reserves_before.0, reserves_before.1)
+      else {
+        return Ok(());
+      };
+
+      let reserves_after = if *asset_in == pair.0 {
+        (
+          reserves_before.0.saturating_add(amount_in),
+          reserves_before.1.saturating_sub(amount_out),
+        )
+      } else {
+        (
+          reserves_before.0.saturating_sub(amount_out),
+          reserves_before.1.saturating_add(amount_in),
+        )
+      };
+      let price_moved_too_far = circuit_breaker::spot_price(reserves_after.0, reserves_after.1)
+        .is_some_and(|price| {
+          circuit_breaker::price_deviation(price_before, price) > T::MaxPriceMovement::get()
+        });
+      let volume = Self::trade_volume(pair, asset_in, reserves_before, amount_in);
+
+      ensure!(
+        !price_moved_too_far && volume <= T::MaxVolumePerBlock::get(),
+        Error::<T>::CircuitBreakerLimitExceeded
+      );
+      Ok(())
+    }
+
+    /// Observation of a pair once a trade of `volume` has been added to it.
+    ///
+    /// Returns `None` if the pool had no price before the trade.
+    fn observe_trade(
+      pair: &(T::AssetKind, T::AssetKind),
+      reserves_before: (T::Balance, T::Balance),
+      volume: Permill,
+    ) -> Option<PairObservation<BlockNumberFor<T>>> {
+      let now = frame_system::Pallet::<T>::block_number();
+      let price_before = circuit_breaker::spot_price(reserves_before.0, reserves_before.1)?;
+
+      // Start a new window once the previous one has expired
+      let mut observation = PairObservations::<T>::get(pair)
+        .filter(|observation| {
+          now
+            < observation
+              .window_start
+              .saturating_add(T::PriceWindow::get())
+        })
+        .unwrap_or(PairObservation {
+          window_start: now,
+          reference_price: price_before,
+          volume_block: now,
+          block_volume: Permill::zero(),
+        });
+
+      if observation.volume_block != now {
+        observation.volume_block = now;
+        observation.block_volume = Permill::zero();
+      }
+      observation.block_volume = observation.block_volume.saturating_add(volume);
+
+      Some(observation)
+    }
+
+    /// Record an executed trade against the pair observation and trip the breaker if the trades
+    /// of the window or the block together exceed a limit.
+    ///
+    /// A pair whose cooldown has passed starts over with a new observation.
+    pub(crate) fn record_trade(
+      pair: &(T::AssetKind, T::AssetKind),
+      reserves_before: (T::Balance, T::Balance),
+      volume: Permill,
+    ) {
+      if TrippedPairs::<T>::take(pair).is_some() {
+        PairObservations::<T>::remove(pair);
+        Self::deposit_event(Event::CircuitBreakerReset {
+          asset_a: pair.0.clone(),
+          asset_b: pair.1.clone(),
+        });
+      }
+
+      let Some(observation) = Self::observe_trade(pair, reserves_before, volume) else {
+        return;
+      };
+
+      let price_after = Self::pair_reserves(pair)
+        .and_then(|(reserve_a, reserve_b)| circuit_breaker::spot_price(reserve_a, reserve_b));
+      let trip_reason = if price_after.is_some_and(|price| {
+        circuit_breaker::price_deviation(observation.reference_price, price)
+          > T::MaxPriceMovement::get()
+      }) {
+        Some(TripReason::PriceMovement)
+      } else if observation.block_volume > T::MaxVolumePerBlock::get() {
+        Some(TripReason::VolumeCap)
+      } else {
+        None
+      };
+
+      PairObservations::<T>::insert(pair, observation);
+
+      if let Some(reason) = trip_reason {
+        TrippedPairs::<T>::insert(pair, frame_system::Pallet::<T>::block_number());
+        Self::deposit_event(Event::CircuitBreakerTripped {
+          asset_a: pair.0.clone(),
+          asset_b: pair.1.clone(),
+          reason,
+        });
+      }
+    }
+  }
+
+  #[pallet::event]
+  #[pallet::generate_deposit(pub(super) fn deposit_event)]
+  pub enum Event<T: Config> {
+    /// A swap was executed through the router with dual fee structure.
+    SwapExecuted {
+      /// The account that initiated the swap; `None` for credit swaps, e.g. fee payments.
+      who: Option<T::AccountId>,
+      /// The input asset.
+      asset_in: T::AssetKind,
+      /// The output asset.
+      asset_out: T::AssetKind,
+      /// The amount of input asset (total user payment).
+      amount_in: T::Balance,
+      /// The amount of output asset received.
+      amount_out: T::Balance,
+      /// The router fee collected (0.2% for buyback mechanism).
+      router_fee: T::Balance,
+      /// The AMM that was used.
+      amm_used: AMMType,
+    },
+    /// The circuit breaker of a pair tripped; swaps on it are rejected until reset.
+    CircuitBreakerTripped {
+      /// The first asset of the ordered pair.
+      asset_a: T::AssetKind,
+      /// The second asset of the ordered pair.
+      asset_b: T::AssetKind,
+      /// Why the breaker tripped.
+      reason: TripReason,
+    },
+    /// The circuit breaker of a pair was reset, either by an admin or after the cooldown.
+    CircuitBreakerReset {
+      /// The first asset of the ordered pair.
+      asset_a: T::AssetKind,
+      /// The second asset of the ordered pair.
+      asset_b: T::AssetKind,
+    },
+    /// All router swaps were paused or resumed.
+    RouterPauseChanged {
+      /// Whether the router is now paused.
+      paused: bool,
+    },
+    /// Swaps on a pair were paused or resumed.
+    PairPauseChanged {
+      /// The first asset of the ordered pair.
+      asset_a: T::AssetKind,
+      /// The second asset of the ordered pair.
+      asset_b: T::AssetKind,
+      /// Whether the pair is now paused.
+      paused: bool,
+    },
+    /// Routing through an AMM was paused or resumed.
+    AMMPauseChanged {
+      /// The affected AMM.
+      amm: AMMType,
+      /// Whether the AMM is now paused.
+      paused: bool,
+    },
+    /// The output of a swap was sent to another chain.
+    SwapOutputSent {
+      /// The account that paid for the swap.
+      who: T::AccountId,
+      /// The asset sent.
+      asset: T::AssetKind,
+      /// The amount sent, before fees on the destination.
+      amount: T::Balance,
+      /// The destination chain.
+      dest: CrossChainLocationOf<T>,
+      /// The beneficiary on the destination chain.
+      beneficiary: CrossChainLocationOf<T>,
+    },
+    /// A single asset was deposited into a pool as liquidity.
+    ZappedIn {
+      /// The account that provided the liquidity.
+      who: T::AccountId,
+      /// The asset deposited.
+      asset_in: T::AssetKind,
+      /// The other asset of the pool.
+      asset_other: T::AssetKind,
+      /// The amount of `asset_in` deposited, including the part swapped.
+      amount_in: T::Balance,
+      /// The LP tokens minted to `who`.
+      lp_minted: T::Balance,
+    },
+    /// Liquidity was withdrawn from a pool as a single asset.
+    ZappedOut {
+      /// The account that withdrew the liquidity.
+      who: T::AccountId,
+      /// The first asset of the pool.
+      asset1: T::AssetKind,
+      /// The second asset of the pool.
+      asset2: T::AssetKind,
+      /// The LP tokens burned.
+      lp_burned: T::Balance,
+      /// The asset received.
+      asset_out: T::AssetKind,
+      /// The total amount of `asset_out` received.
+      amount_out: T::Balance,
+    },
+    /// The output of a flash swap was lent to a borrower, who repaid it with the input.
+    FlashSwapExecuted {
+      /// The borrower.
+      who: T::AccountId,
+      /// The asset repaid.
+      asset_in: T::AssetKind,
+      /// The asset lent.
+      asset_out: T::AssetKind,
+      /// The amount of `asset_in` repaid, including all fees.
+      amount_in: T::Balance,
+      /// The amount of `asset_out` lent.
+      amount_out: T::Balance,
+      /// The flash swap fee included in `amount_in`.
+      flash_fee: T::Balance,
+    },
+    /// A swap went through a single pool.
+    HopExecuted {
+      /// The account holding the pool reserves.
+      pool: T::AccountId,
+      /// The asset sold to the pool.
+      asset_in: T::AssetKind,
+      /// The asset bought from the pool.
+      asset_out: T::AssetKind,
+      /// The amount of `asset_in` the pool received, after the router fee.
+      amount_in: T::Balance,
+      /// The amount of `asset_out` the pool paid out.
+      amount_out: T::Balance,
+      /// The AMM of the pool.
+      amm: AMMType,
+      /// The `asset_in` reserve of the pool after the swap.
+      reserve_in: T::Balance,
+      /// The `asset_out` reserve of the pool after the swap.
+      reserve_out: T::Balance,
+      /// The effective price of the swap, in `asset_out` per unit of `asset_in`.
+      price: FixedU128,
+    },
+    /// Router fees were credited to an account.
+    FeeCollected {
+      /// The asset the fee was paid in.
+      asset: T::AssetKind,
+      /// The amount credited.
+      amount: T::Balance,
+      /// The account credited, e.g. the fee collector or the treasury.
+      destination: T::AccountId,
+    },
+    /// `AdminOrigin` changed the fee settings the router applies.
+    FeeSettingsChanged {
+      /// The fee settings now applied.
+      settings: FeeSettings,
+    },
+  }
+
+  #[pallet::error]
+  pub enum Error<T> {
+    /// No compatible AMM found for the given asset pair.
+    NoCompatibleAMM,
+    /// No liquidity available for the swap.
+    NoLiquidityAvailable,
+    /// Invalid swap path provided.
+    InvalidPath,
+    /// Fee calculation failed.
+    FeeCalculationFailed,
+    /// All router swaps are paused.
+    RouterPaused,
+    /// Swaps on this pair are paused.
+    PairPaused,
+    /// Every AMM able to handle this pair is paused.
+    AMMPaused,
+    /// Zaps need two distinct assets, a non-zero amount and an output asset of the pair.
+    InvalidZap,
+    /// The swap or liquidity operation yielded less than the requested minimum.
+    SlippageExceeded,
+    /// The pair has lent the output of a flash swap that is not repaid yet.
+    FlashSwapInProgress,
+    /// The flash swap borrower did not hold the amount due after its call.
+    FlashSwapNotRepaid,
+    /// No pool exists for the pair.
+    PoolNotFound,
+    /// The pool cannot provide the requested output.
+    InsufficientLiquidity,
+    /// The trader does not hold enough of the input asset.
+    InsufficientBalance,
+    /// An amount is below the minimum balance of its asset, or would leave an account below it.
+    BelowMinimum,
+    /// An asset of the swap is frozen, blocked or unknown.
+    AssetUnavailable,
+    /// The trade alone would move the price or volume of its pair beyond the circuit breaker
+    /// limits.
+    CircuitBreakerLimitExceeded,
+    /// The router fee and the flash swap fee must add up to less than 100%.
+    InvalidFeeSettings,
+    /// The fee collector or the treasury cannot receive its share of the router fee.
+    FeeNotCollectable,
+    /// The circuit breaker of this pair has tripped.
+    CircuitBreakerTripped,
+    /// The circuit breaker of this pair is not tripped.
+    CircuitBreakerNotTripped,
+  }
+
+  #[pallet::call]
+  impl<T: Config> Pallet<T> {
+    /// Execute a token swap through the best available AMM.
+    ///
+    /// Charged for the full path and every adapter up front; the weight of the hops executed
+    /// and adapters consulted is returned so the difference is refunded.
+    #[pallet::call_index(0)]
+    #[pallet::weight(T::WeightInfo::swap_exact_tokens_for_tokens(path.len() as u32))]
+    pub fn swap_exact_tokens_for_tokens(
+      origin: OriginFor<T>,
+      path: BoundedVec<T::AssetKind, ConstU32<5>>,
+      amount_in: T::Balance,
+      amount_out_min: T::Balance,
+      _send_to: T::AccountId,
+      _keep_alive: bool,
+    ) -> DispatchResultWithPostInfo {
+      let who = ensure_signed(origin)?;
+
+      let (_, route) = Self::do_swap_path(&who, &path, amount_in, amount_out_min)?;
+
+      Ok(Some(Self::swap_weight(&route)).into())
+    }
+
+    /// Reset the circuit breaker of a tripped pair.
+    #[pallet::call_index(1)]
+    #[pallet::weight(T::WeightInfo::reset_circuit_breaker())]
+    pub fn reset_circuit_breaker(
+      origin: OriginFor<T>,
+      asset_a: T::AssetKind,
+      asset_b: T::AssetKind,
+    ) -> DispatchResult {
+      T::AdminOrigin::ensure_origin(origin)?;
+
+      let pair = Self::pair_key(asset_a, asset_b);
+      ensure!(
+        TrippedPairs::<T>::contains_key(&pair),
+        Error::<T>::CircuitBreakerNotTripped
+      );
+
+      TrippedPairs::<T>::remove(&pair);
+      PairObservations::<T>::remove(&pair);
+
+      Self::deposit_event(Event::CircuitBreakerReset {
+        asset_a: pair.0,
+        asset_b: pair.1,
+      });
+
+      Ok(())
+    }
+
+    /// Pause or resume all router swaps.
+    #[pallet::call_index(2)]
+    #[pallet::weight(T::WeightInfo::set_router_paused())]
+    pub fn set_router_paused(origin: OriginFor<T>, paused: bool) -> DispatchResult {
+      T::AdminOrigin::ensure_origin(origin)?;
+
+      RouterPaused::<T>::put(paused);
+      Self::deposit_event(Event::RouterPauseChanged { paused });
+
+      Ok(())
+    }
+
+    /// Pause or resume swaps on a single pair, in both directions.
+    ///
+    /// Liquidity can still be withdrawn from the pools of a paused pair.
+    #[pallet::call_index(3)]
+    #[pallet::weight(T::WeightInfo::set_pair_paused())]
+    pub fn set_pair_paused(
+      origin: OriginFor<T>,
+      asset_a: T::AssetKind,
+      asset_b: T::AssetKind,
+      paused: bool,
+    ) -> DispatchResult {
+      T::AdminOrigin::ensure_origin(origin)?;
+
+      let pair = Self::pair_key(asset_a, asset_b);
+      if paused {
+        PausedPairs::<T>::insert(&pair, ());
+      } else {
+        PausedPairs::<T>::remove(&pair);
+      }
+
+      Self::deposit_event(Event::PairPauseChanged {
+        asset_a: pair.0,
+        asset_b: pair.1,
+        paused,
+      });
+
+      Ok(())
+    }
+
+    /// Pause or resume routing through a single AMM.
+    #[pallet::call_index(4)]
+    #[pallet::weight(T::WeightInfo::set_amm_paused())]
+    pub fn set_amm_paused(origin: OriginFor<T>, amm: AMMType, paused: bool) -> DispatchResult {
+      T::AdminOrigin::ensure_origin(origin)?;
+
+      if paused {
+        PausedAMMs::<T>::insert(amm, ());
+      } else {
+        PausedAMMs::<T>::remove(amm);
+      }
+
+      Self::deposit_event(Event::AMMPauseChanged { amm, paused });
+
+      Ok(())
+    }
+
+    /// Swap through the best available AMM and send the output to `beneficiary` on `dest`.
+    ///
+    /// The output is delivered through `T::CrossChainTransfer`. If delivery fails the call fails
+    /// and the swap is reverted with it.
+    #[pallet::call_index(5)]
+    #[pallet::weight(
+      T::WeightInfo::swap_exact_tokens_for_tokens(path.len() as u32)
+        .saturating_add(T::CrossChainTransfer::transfer_weight())
+    )]
+    pub fn swap_and_send(
+      origin: OriginFor<T>,
+      path: BoundedVec<T::AssetKind, ConstU32<5>>,
+      amount_in: T::Balance,
+      amount_out_min: T::Balance,
+      dest: Box<CrossChainLocationOf<T>>,
+      beneficiary: Box<CrossChainLocationOf<T>>,
+    ) -> DispatchResultWithPostInfo {
+      let who = ensure_signed(origin)?;
+
+      let (amount_out, route) = Self::do_swap_path(&who, &path, amount_in, amount_out_min)?;
+      let asset_out = path[path.len() - 1].clone();
+      T::CrossChainTransfer::transfer(
+        &who,
+        asset_out.clone(),
+        amount_out,
+        (*dest).clone(),
+        (*beneficiary).clone(),
+      )?;
+
+      Self::deposit_event(Event::SwapOutputSent {
+        who,
+        asset: asset_out,
+        amount: amount_out,
+        dest: *dest,
+        beneficiary: *beneficiary,
+      });
+
+      Ok(
+        Some(Self::swap_weight(&route).saturating_add(T::CrossChainTransfer::transfer_weight()))
+          .into(),
+      )
+    }
+
+    /// Swap on behalf of another chain and send the output back to `beneficiary` on it.
+    ///
+    /// Meant for XCM `Transact` from other chains: the swap is paid from the account yielded by
+    /// `T::RemoteOrigin` (the caller's sovereign account) and the output is delivered to the
+    /// calling chain through `T::CrossChainTransfer`. If delivery fails the swap is reverted.
+    #[pallet::call_index(6)]
+    #[pallet::weight(
+      T::WeightInfo::swap_exact_tokens_for_tokens(path.len() as u32)
+        .saturating_add(T::CrossChainTransfer::transfer_weight())
+    )]
+    pub fn remote_swap(
+      origin: OriginFor<T>,
+      path: BoundedVec<T::AssetKind, ConstU32<5>>,
+      amount_in: T::Balance,
+      amount_out_min: T::Balance,
+      beneficiary: Box<CrossChainLocationOf<T>>,
+    ) -> DispatchResultWithPostInfo {
+      let (who, dest) = T::RemoteOrigin::ensure_origin(origin)?;
+
+      let (amount_out, route) = Self::do_swap_path(&who, &path, amount_in, amount_out_min)?;
+      let asset_out = path[path.len() - 1].clone();
+      T::CrossChainTransfer::transfer(
+        &who,
+        asset_out.clone(),
+        amount_out,
+        dest.clone(),
+        (*beneficiary).clone(),
+      )?;
+
+      Self::deposit_event(Event::SwapOutputSent {
+        who,
+        asset: asset_out,
+        amount: amount_out,
+        dest,
+        beneficiary: *beneficiary,
+      });
+
+      Ok(
+        Some(Self::swap_weight(&route).saturating_add(T::CrossChainTransfer::transfer_weight()))
+          .into(),
+      )
+    }
+
+    /// Add liquidity to the pool of a pair, creating the pool if it does not exist yet.
+    ///
+    /// The pool takes the desired amounts at its current ratio; the LP tokens are minted to the
+    /// caller. Fails while the router or the pair is paused.
+    #[pallet::call_index(7)]
+    #[pallet::weight(T::WeightInfo::add_liquidity())]
+    pub fn add_liquidity(
+      origin: OriginFor<T>,
+      asset1: T::AssetKind,
+      asset2: T::AssetKind,
+      amount1_desired: T::Balance,
+      amount2_desired: T::Balance,
+      amount1_min: T::Balance,
+      amount2_min: T::Balance,
+    ) -> DispatchResult {
+      let who = ensure_signed(origin)?;
+
+      Self::ensure_liquidity_accepted(&Self::pair_key(asset1.clone(), asset2.clone()))?;
+      Self::ensure_pool(&who, &asset1, &asset2)?;
+      Self::add_pool_liquidity(
+        &who,
+        asset1,
+        asset2,
+        (amount1_desired, amount2_desired),
+        (amount1_min, amount2_min),
+      )?;
+
+      Ok(())
+    }
+
+    /// Burn LP tokens of the caller and withdraw both sides of the pool to the caller.
+    #[pallet::call_index(8)]
+    #[pallet::weight(T::WeightInfo::remove_liquidity())]
+    pub fn remove_liquidity(
+      origin: OriginFor<T>,
+      asset1: T::AssetKind,
+      asset2: T::AssetKind,
+      lp_token_burn: T::Balance,
+      amount1_min: T::Balance,
+      amount2_min: T::Balance,
+    ) -> DispatchResult {
+      let who = ensure_signed(origin)?;
+
+      Self::ensure_not_flash_borrowed(&Self::pair_key(asset1.clone(), asset2.clone()))?;
+      Self::remove_pool_liquidity(
+        &who,
+        asset1,
+        asset2,
+        lp_token_burn,
+        (amount1_min, amount2_min),
+      )?;
+
+      Ok(())
+    }
+
+    /// Provide liquidity to the pool of `asset_in` and `asset_other` with `asset_in` only.
+    ///
+    /// See [`Pallet::do_zap_in`]. The swap goes through the router, so it pays the router fee
+    /// and fails while the pair is paused or tripped, or if the swap exceeds the circuit breaker
+    /// limits.
+    #[pallet::call_index(9)]
+    #[pallet::weight(T::WeightInfo::zap_in())]
+    pub fn zap_in(
+      origin: OriginFor<T>,
+      asset_in: T::AssetKind,
+      asset_other: T::AssetKind,
+      amount_in: T::Balance,
+      min_lp_minted: T::Balance,
+    ) -> DispatchResultWithPostInfo {
+      let who = ensure_signed(origin)?;
+
+      let (_, route) = Self::do_zap_in(&who, asset_in, asset_other, amount_in, min_lp_minted)?;
+
+      Ok(Some(Self::with_route_weight(T::WeightInfo::zap_in(), &route)).into())
+    }
+
+    /// Withdraw liquidity from the pool of `asset1` and `asset2` entirely as `asset_out`.
+    ///
+    /// See [`Pallet::do_zap_out`].
+    #[pallet::call_index(10)]
+    #[pallet::weight(T::WeightInfo::zap_out())]
+    pub fn zap_out(
+      origin: OriginFor<T>,
+      asset1: T::AssetKind,
+      asset2: T::AssetKind,
+      lp_token_burn: T::Balance,
+      asset_out: T::AssetKind,
+      amount_out_min: T::Balance,
+    ) -> DispatchResultWithPostInfo {
+      let who = ensure_signed(origin)?;
+
+      let (_, route) = Self::do_zap_out(
+        &who,
+        asset1,
+        asset2,
+        lp_token_burn,
+        asset_out,
+        amount_out_min,
+      )?;
+
+      Ok(Some(Self::with_route_weight(T::WeightInfo::zap_out(), &route)).into())
+    }
+
+    /// Borrow `amount_out` of `asset_out` from its pool with `asset_in`, dispatch `call` as the
+    /// caller and repay with `asset_in`.
+    ///
+    /// See [`Pallet::do_flash_swap`]. Swaps and liquidity changes on the pair are rejected while
+    /// `call` executes, and nested flash swaps are not allowed.
+    #[pallet::call_index(11)]
+    #[pallet::weight(
+      T::WeightInfo::flash_swap().saturating_add(call.get_dispatch_info().call_weight)
+    )]
+    pub fn flash_swap(
+      origin: OriginFor<T>,
+      asset_in: T::AssetKind,
+      asset_out: T::AssetKind,
+      amount_out: T::Balance,
+      amount_in_max: T::Balance,
+      call: Box<<T as Config>::RuntimeCall>,
+    ) -> DispatchResultWithPostInfo {
+      let who = ensure_signed(origin)?;
+
+      let (_, call_weight) = Self::do_flash_swap(
+        &who,
+        asset_in,
+        asset_out,
+        amount_out,
+        amount_in_max,
+        DispatchFlashCall::<T>(*call),
+      )?;
+
+      Ok(Some(T::WeightInfo::flash_swap().saturating_add(call_weight)).into())
+    }
+
+    /// Set the router fee, the treasury share of router fees and the flash swap fee.
+    ///
+    /// The settings apply from the next swap on, instead of those in the pallet configuration.
+    /// A flash swap pays both the router fee and the flash swap fee, so together they must stay
+    /// below 100%. The treasury share is a share of the router fee, so it can never exceed it;
+    /// shares above 100% do not decode.
+    #[pallet::call_index(12)]
+    #[pallet::weight(T::WeightInfo::set_fee_settings())]
+    pub fn set_fee_settings(origin: OriginFor<T>, settings: FeeSettings) -> DispatchResult {
+      T::AdminOrigin::ensure_origin(origin)?;
+      ensure!(
+        settings.router_fee.saturating_add(settings.flash_swap_fee) < Permill::one(),
+        Error::<T>::InvalidFeeSettings
+      );
+
+      FeeSettingsOverride::<T>::put(settings);
+      Self::deposit_event(Event::FeeSettingsChanged { settings });
+
+      Ok(())
+    }
+  }
+}
+
+/// Flash swap callback that dispatches a call as the borrower.
+pub struct DispatchFlashCall<T: Config>(pub <T as Config>::RuntimeCall);
+
+impl<T: Config> FlashSwapCallback<T::AccountId, T::AssetKind, T::Balance> for DispatchFlashCall<T> {
+  fn on_flash_swap(
+    self,
+    who: &T::AccountId,
+    _asset_in: &T::AssetKind,
+    _amount_due: T::Balance,
+    _asset_out: &T::AssetKind,
+    _amount_out: T::Balance,
+  ) -> Result<Weight, DispatchError> {
+    let info = self.0.get_dispatch_info();
+    self
+      .0
+      .dispatch(frame_system::RawOrigin::Signed(who.clone()).into())
+      .map(|post_info| post_info.calc_actual_weight(&info))
+      .map_err(|e| e.error)
   }
 }
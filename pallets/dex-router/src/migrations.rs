@@ -0,0 +1,61 @@
+//! Storage migrations for the DEX router pallet.
+//!
+//! Each storage version gets a module with an unchecked migration and a `VersionedMigration`
+//! wrapper that only runs it when the on-chain version matches. Runtimes should register the
+//! versioned wrappers in their `Migrations`.
+
+use super::*;
+use frame::deps::frame_support::{
+  migrations::VersionedMigration, storage_alias, traits::UncheckedOnRuntimeUpgrade,
+};
+#[cfg(feature = "try-runtime")]
+use {
+  alloc::vec::Vec, frame::deps::frame_support::traits::GetStorageVersion,
+  frame::deps::sp_runtime::TryRuntimeError,
+};
+
+/// Version 1: removes the `Something` value left over from the pallet template.
+pub mod v1 {
+  use super::*;
+
+  /// The template storage value removed in version 1.
+  #[storage_alias]
+  pub type Something<T: Config> = StorageValue<Pallet<T>, u32>;
+
+  /// Removes `Something`; use [`MigrateV0ToV1`] instead.
+  pub struct UncheckedMigrateV0ToV1<T>(PhantomData<T>);
+
+  impl<T: Config> UncheckedOnRuntimeUpgrade for UncheckedMigrateV0ToV1<T> {
+    fn on_runtime_upgrade() -> Weight {
+      Something::<T>::kill();
+      T::DbWeight::get().writes(1)
+    }
+
+    #[cfg(feature = "try-runtime")]
+    fn pre_upgrade() -> Result<Vec<u8>, TryRuntimeError> {
+      ensure!(
+        Pallet::<T>::on_chain_storage_version() == 0,
+        TryRuntimeError::Other("router must be at storage version 0")
+      );
+      Ok(Vec::new())
+    }
+
+    #[cfg(feature = "try-runtime")]
+    fn post_upgrade(_state: Vec<u8>) -> Result<(), TryRuntimeError> {
+      ensure!(
+        !Something::<T>::exists(),
+        TryRuntimeError::Other("`Something` must be removed")
+      );
+      Ok(())
+    }
+  }
+
+  /// Migrates the router from storage version 0 to 1, removing `Something`.
+  pub type MigrateV0ToV1<T> = VersionedMigration<
+    0,
+    1,
+    UncheckedMigrateV0ToV1<T>,
+    Pallet<T>,
+    <T as frame_system::Config>::DbWeight,
+  >;
+}
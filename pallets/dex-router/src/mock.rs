@@ -0,0 +1,276 @@
+//! Test environment for the benchmark test suite of the DEX router pallet.
+//!
+//! Asset `0` is the native token; any other id is a local asset. Pools always hold the native
+//! token, as in the runtime.
+
+use crate as pallet_dex_router;
+use frame::{
+  deps::{
+    frame_support::{
+      instances::{Instance1, Instance2},
+      parameter_types,
+      traits::{fungible, AsEnsureOriginWithArg, ConstU128, ConstU32, NeverEnsureOrigin},
+      PalletId,
+    },
+    sp_runtime::{traits::IdentityLookup, Either, Permill},
+  },
+  testing_prelude::*,
+};
+use polkadot_sdk::{pallet_asset_conversion, pallet_assets, pallet_balances, sp_core};
+
+type Block = frame_system::mocking::MockBlock<Test>;
+type AccountId = u64;
+type Balance = u128;
+
+/// Asset id of the native token
+pub const NATIVE: u32 = 0;
+/// Existential deposit of the native token and minimum balance of the local assets
+pub const ED: Balance = 1_000;
+/// Receives the router fees not sent to the treasury
+pub const FEE_COLLECTOR: AccountId = 100;
+/// Receives the treasury share of the router fees
+pub const TREASURY: AccountId = 101;
+
+construct_runtime!(
+  pub enum Test {
+    System: frame_system,
+    Balances: pallet_balances,
+    Assets: pallet_assets::<Instance1>,
+    PoolAssets: pallet_assets::<Instance2>,
+    AssetConversion: pallet_asset_conversion,
+    DexRouter: pallet_dex_router,
+  }
+);
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Test {
+  type AccountId = AccountId;
+  type Lookup = IdentityLookup<AccountId>;
+  type Block = Block;
+  type AccountData = pallet_balances::AccountData<Balance>;
+}
+
+#[derive_impl(pallet_balances::config_preludes::TestDefaultConfig)]
+impl pallet_balances::Config for Test {
+  type Balance = Balance;
+  type ExistentialDeposit = ConstU128<ED>;
+  type AccountStore = System;
+}
+
+impl pallet_assets::Config<Instance1> for Test {
+  type RuntimeEvent = RuntimeEvent;
+  type Balance = Balance;
+  type RemoveItemsLimit = ConstU32<1000>;
+  type AssetId = u32;
+  type AssetIdParameter = u32;
+  type Currency = Balances;
+  type CreateOrigin = AsEnsureOriginWithArg<frame_system::EnsureSigned<AccountId>>;
+  type ForceOrigin = frame_system::EnsureRoot<AccountId>;
+  type AssetDeposit = ConstU128<ED>;
+  type AssetAccountDeposit = ConstU128<ED>;
+  type MetadataDepositBase = ConstU128<ED>;
+  type MetadataDepositPerByte = ConstU128<ED>;
+  type ApprovalDeposit = ConstU128<ED>;
+  type StringLimit = ConstU32<50>;
+  type Holder = ();
+  type Freezer = ();
+  type Extra = ();
+  type WeightInfo = ();
+  type CallbackHandle = ();
+  type BenchmarkHelper = ();
+}
+
+impl pallet_assets::Config<Instance2> for Test {
+  type RuntimeEvent = RuntimeEvent;
+  type Balance = Balance;
+  type RemoveItemsLimit = ConstU32<1000>;
+  type AssetId = u32;
+  type AssetIdParameter = u32;
+  type Currency = Balances;
+  type CreateOrigin = AsEnsureOriginWithArg<NeverEnsureOrigin<AccountId>>;
+  type ForceOrigin = frame_system::EnsureRoot<AccountId>;
+  type AssetDeposit = ConstU128<0>;
+  type AssetAccountDeposit = ConstU128<ED>;
+  type MetadataDepositBase = ConstU128<0>;
+  type MetadataDepositPerByte = ConstU128<0>;
+  type ApprovalDeposit = ConstU128<ED>;
+  type StringLimit = ConstU32<50>;
+  type Holder = ();
+  type Freezer = ();
+  type Extra = ();
+  type WeightInfo = ();
+  type CallbackHandle = ();
+  type BenchmarkHelper = ();
+}
+
+parameter_types! {
+  pub const AssetConversionPalletId: PalletId = PalletId(*b"py/ascon");
+  pub const Native: u32 = NATIVE;
+  pub const RouterFee: Permill = Permill::from_parts(2000);
+  pub const RouterFeeCollector: AccountId = FEE_COLLECTOR;
+  pub const TreasuryAccount: AccountId = TREASURY;
+  pub const TreasuryFeeShare: Permill = Permill::from_percent(20);
+  pub const FlashSwapFee: Permill = Permill::from_parts(1000);
+  pub const MaxPriceMovement: Permill = Permill::from_percent(20);
+  pub const MaxVolumePerBlock: Permill = Permill::from_percent(10);
+}
+
+/// Splits asset ids into the native token and local assets
+pub struct NativeOrLocal;
+impl Convert<u32, Either<(), u32>> for NativeOrLocal {
+  fn convert(asset: u32) -> Either<(), u32> {
+    match asset {
+      NATIVE => Either::Left(()),
+      id => Either::Right(id),
+    }
+  }
+}
+
+pub type NativeAndAssets = fungible::UnionOf<Balances, Assets, NativeOrLocal, u32, AccountId>;
+
+impl pallet_asset_conversion::Config for Test {
+  type RuntimeEvent = RuntimeEvent;
+  type Balance = Balance;
+  type HigherPrecisionBalance = sp_core::U256;
+  type AssetKind = u32;
+  type Assets = NativeAndAssets;
+  type PoolId = (u32, u32);
+  type PoolLocator = pallet_asset_conversion::WithFirstAsset<
+    Native,
+    AccountId,
+    u32,
+    pallet_asset_conversion::AccountIdConverter<AssetConversionPalletId, (u32, u32)>,
+  >;
+  type PoolAssetId = u32;
+  type PoolAssets = PoolAssets;
+  type LPFee = ConstU32<3>;
+  type PoolSetupFee = ConstU128<0>;
+  type PoolSetupFeeAsset = Native;
+  type PoolSetupFeeTarget = ();
+  type LiquidityWithdrawalFee = ();
+  type MintMinLiquidity = ConstU128<100>;
+  type MaxSwapPathLength = ConstU32<4>;
+  type PalletId = AssetConversionPalletId;
+  type WeightInfo = ();
+  type BenchmarkHelper = ();
+}
+
+impl pallet_dex_router::Config for Test {
+  type RuntimeEvent = RuntimeEvent;
+  type Balance = Balance;
+  type AssetKind = u32;
+  type RouterFee = RouterFee;
+  type RouterFeeCollector = RouterFeeCollector;
+  type TreasuryAccount = TreasuryAccount;
+  type TreasuryFeeShare = TreasuryFeeShare;
+  type FlashSwapFee = FlashSwapFee;
+  type RuntimeCall = RuntimeCall;
+  type AdminOrigin = frame_system::EnsureRoot<AccountId>;
+  type MaxPriceMovement = MaxPriceMovement;
+  type PriceWindow = ConstU64<100>;
+  type MaxVolumePerBlock = MaxVolumePerBlock;
+  type CircuitBreakerCooldown = ConstU64<300>;
+  type WeightInfo = ();
+  type AssetConversion = Test;
+  type Balances = Test;
+  type Assets = NativeAndAssets;
+  type CrossChainTransfer = ();
+  type RemoteOrigin = NeverEnsureOrigin<(AccountId, ())>;
+  type BenchmarkHelper = BenchmarkHelper;
+}
+
+/// Creates native pools for local assets, like the runtime's benchmark helper
+pub struct BenchmarkHelper;
+
+impl crate::benchmarking::BenchmarkHelper<AccountId, u32, Balance> for BenchmarkHelper {
+  fn setup_pair(caller: &AccountId) -> (u32, u32, Balance) {
+    const ASSET_ID: u32 = 1;
+    let liquidity = Self::create_pool(caller, ASSET_ID);
+
+    // Small enough to stay within the circuit breaker's volume and price limits
+    (NATIVE, ASSET_ID, liquidity / 1_000)
+  }
+
+  fn setup_path(caller: &AccountId, len: u32) -> (Vec<u32>, Balance) {
+    // Pools always hold the native token, so the path alternates between local assets and it
+    const FIRST_ASSET_ID: u32 = 10;
+    let path: Vec<_> = (0..len)
+      .map(|i| match i % 2 {
+        0 => FIRST_ASSET_ID + i / 2,
+        _ => NATIVE,
+      })
+      .collect();
+    let mut liquidity = 0;
+    for asset in &path {
+      if *asset != NATIVE {
+        liquidity = Self::create_pool(caller, *asset);
+      }
+    }
+
+    (path, liquidity / 1_000)
+  }
+
+  fn setup_unpooled_pair(caller: &AccountId) -> (u32, u32, Balance) {
+    const ASSET_ID: u32 = 2;
+    let liquidity = Self::fund(caller, ASSET_ID);
+
+    (NATIVE, ASSET_ID, liquidity)
+  }
+}
+
+impl BenchmarkHelper {
+  /// Create a native pool for local asset `asset_id` funded by `caller` and return the amount of
+  /// each side added; `caller` keeps as much again of both
+  fn create_pool(caller: &AccountId, asset_id: u32) -> Balance {
+    let liquidity = Self::fund(caller, asset_id);
+    assert_ok!(DexRouter::do_create_pool(caller, &NATIVE, &asset_id));
+    assert_ok!(AssetConversion::add_liquidity(
+      RuntimeOrigin::signed(*caller),
+      Box::new(NATIVE),
+      Box::new(asset_id),
+      liquidity,
+      liquidity,
+      1,
+      1,
+      *caller,
+    ));
+
+    liquidity
+  }
+
+  /// Create local asset `asset_id` and fund `caller` with twice the returned amount of it and
+  /// of the native token
+  fn fund(caller: &AccountId, asset_id: u32) -> Balance {
+    use frame::deps::frame_support::traits::{fungible::Mutate as _, fungibles::Mutate as _};
+
+    let liquidity = 1_000_000 * ED;
+    assert_ok!(Assets::force_create(
+      RuntimeOrigin::root(),
+      asset_id,
+      *caller,
+      true,
+      1,
+    ));
+    assert_ok!(Balances::mint_into(caller, 2 * liquidity));
+    assert_ok!(Assets::mint_into(asset_id, caller, 2 * liquidity));
+
+    liquidity
+  }
+}
+
+pub fn new_test_ext() -> TestState {
+  let mut storage = frame_system::GenesisConfig::<Test>::default()
+    .build_storage()
+    .unwrap();
+  // The fee accounts must exist to receive native fees below the existential deposit
+  pallet_balances::GenesisConfig::<Test> {
+    balances: vec![(FEE_COLLECTOR, ED), (TREASURY, ED)],
+    ..Default::default()
+  }
+  .assimilate_storage(&mut storage)
+  .unwrap();
+
+  let mut ext = TestState::new(storage);
+  ext.execute_with(|| System::set_block_number(1));
+  ext
+}
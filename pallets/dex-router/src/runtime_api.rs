@@ -0,0 +1,35 @@
+//! Runtime API definition for the DEX router.
+
+use crate::traits::PauseStatus;
+use codec::Codec;
+
+polkadot_sdk::sp_api::decl_runtime_apis! {
+  /// Queries exposed by the DEX router to clients.
+  pub trait DexRouterApi<AssetKind>
+  where
+    AssetKind: Codec,
+  {
+    /// Whether swaps between `asset_a` and `asset_b` are currently paused, and by what.
+    fn pause_status(asset_a: AssetKind, asset_b: AssetKind) -> PauseStatus;
+  }
+
+  /// Estimates for swapping and sending the output to another chain in one call.
+  pub trait SwapAndSendApi<AssetKind, Balance, Location>
+  where
+    AssetKind: Codec,
+    Balance: Codec,
+    Location: Codec,
+  {
+    /// Expected output of `swap_and_send` and the delivery fee charged to the sender, in the
+    /// native token.
+    ///
+    /// Returns `None` if there is no route or the output cannot be delivered to `dest`.
+    fn quote_swap_and_send(
+      asset_in: AssetKind,
+      asset_out: AssetKind,
+      amount_in: Balance,
+      dest: Location,
+      beneficiary: Location,
+    ) -> Option<(Balance, Balance)>;
+  }
+}
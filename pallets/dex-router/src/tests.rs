@@ -100,9 +100,8 @@ fn weight_info_trait() {
   // Test that WeightInfo trait is properly defined
   use crate::WeightInfo;
 
-  let weight = <() as WeightInfo>::swap_exact_tokens_for_tokens();
-  assert_eq!(weight.ref_time(), 10_000);
-  assert_eq!(weight.proof_size(), 0);
+  let weight = <() as WeightInfo>::swap_exact_tokens_for_tokens(2);
+  assert!(weight.ref_time() > 0);
 }
 
 #[test]
@@ -138,7 +137,7 @@ fn amm_trait_name_method() {
   struct MockAMM;
 
   impl AMM<(), u128, u64> for MockAMM {
-    type Error = &'static str;
+    type Error = AMMError;
 
     fn can_handle_pair(&self, _asset_in: &(), _asset_out: &()) -> bool {
       true
@@ -184,14 +183,17 @@ fn fee_collector_trait_exists() {
       &self,
       _from: &u64,
       _asset: &(),
-      _amount: u128,
-    ) -> frame::prelude::DispatchResult {
-      Ok(())
+      amount: u128,
+    ) -> Result<Vec<(u64, u128)>, DispatchError> {
+      Ok(vec![(0, amount)])
     }
   }
 
   let mock_collector = MockFeeCollector;
-  assert_eq!(mock_collector.collect_fee(&1u64, &(), 100), Ok(()));
+  assert_eq!(
+    mock_collector.collect_fee(&1u64, &(), 100),
+    Ok(vec![(0, 100)])
+  );
 }
 
 #[test]
@@ -208,3 +210,124 @@ fn permill_operations() {
   let fee_parts = fee_rate.deconstruct();
   assert_eq!(fee_parts, 3000u32); // 0.3% = 3000 parts per million
 }
+
+#[test]
+fn circuit_breaker_spot_price() {
+  use crate::circuit_breaker::spot_price;
+
+  // Price is expressed as second reserve per unit of the first
+  assert_eq!(
+    spot_price(1_000u128, 2_000u128),
+    Some(FixedU128::from_u32(2))
+  );
+  assert_eq!(
+    spot_price(2_000u128, 1_000u128),
+    Some(FixedU128::from_rational(1, 2))
+  );
+
+  // Empty pools have no price
+  assert_eq!(spot_price(0u128, 1_000u128), None);
+}
+
+#[test]
+fn circuit_breaker_price_deviation() {
+  use crate::circuit_breaker::price_deviation;
+
+  let reference = FixedU128::from_u32(2);
+
+  // Deviation is symmetric around the reference price
+  assert_eq!(price_deviation(reference, reference), Permill::zero());
+  assert_eq!(
+    price_deviation(reference, FixedU128::from_rational(5, 2)),
+    Permill::from_percent(25)
+  );
+  assert_eq!(
+    price_deviation(reference, FixedU128::from_rational(3, 2)),
+    Permill::from_percent(25)
+  );
+
+  // Moves larger than 100% saturate
+  assert_eq!(
+    price_deviation(reference, FixedU128::from_u32(10)),
+    Permill::one()
+  );
+  assert_eq!(
+    price_deviation(FixedU128::zero(), reference),
+    Permill::one()
+  );
+}
+
+#[test]
+fn circuit_breaker_volume_share() {
+  use crate::circuit_breaker::volume_share;
+
+  assert_eq!(volume_share(100u128, 1_000u128), Permill::from_percent(10));
+  assert_eq!(volume_share(0u128, 1_000u128), Permill::zero());
+
+  // Volume above the reserves or against an empty pool saturates
+  assert_eq!(volume_share(5_000u128, 1_000u128), Permill::one());
+  assert_eq!(volume_share(1u128, 0u128), Permill::one());
+}
+
+#[test]
+fn zap_combined_swap_fee() {
+  use crate::zap::combined_swap_fee;
+
+  // 0.2% router fee on top of a 0.3% pool fee
+  assert_eq!(
+    combined_swap_fee(Permill::from_parts(2_000), 3),
+    Permill::from_parts(4_994)
+  );
+  assert_eq!(combined_swap_fee(Permill::zero(), 0), Permill::zero());
+}
+
+#[test]
+fn zap_optimal_swap_amount() {
+  use crate::zap::optimal_swap_amount;
+
+  let fee = Permill::from_parts(4_994);
+
+  // Without fees, a deposit as large as the reserves swaps (√2 - 1) of it
+  assert_eq!(
+    optimal_swap_amount(1_000u128, 1_000u128, Permill::zero()),
+    414
+  );
+
+  // Small deposits swap about half, slightly more to cover the fee
+  assert_eq!(optimal_swap_amount(1_000_000u128, 10_000u128, fee), 5_000);
+  assert_eq!(
+    optimal_swap_amount(1_000_000u128, 10_000u128, Permill::zero()),
+    4_987
+  );
+
+  // Empty pools cannot be zapped into
+  assert_eq!(optimal_swap_amount(0u128, 10_000u128, fee), 0);
+}
+
+#[test]
+fn amm_error_from_token_errors() {
+  use frame::deps::sp_runtime::TokenError;
+
+  let amm_error = |e: TokenError| AMMError::from(DispatchError::Token(e));
+  assert_eq!(
+    amm_error(TokenError::FundsUnavailable),
+    AMMError::InsufficientBalance
+  );
+  assert_eq!(amm_error(TokenError::BelowMinimum), AMMError::BelowMinimum);
+  assert_eq!(amm_error(TokenError::NotExpendable), AMMError::BelowMinimum);
+  assert_eq!(amm_error(TokenError::Frozen), AMMError::AssetUnavailable);
+  assert_eq!(
+    amm_error(TokenError::UnknownAsset),
+    AMMError::AssetUnavailable
+  );
+
+  // Anything else is passed on unchanged
+  assert_eq!(
+    AMMError::from(DispatchError::BadOrigin),
+    AMMError::Other(DispatchError::BadOrigin)
+  );
+  assert_eq!(
+    amm_error(TokenError::CannotCreate),
+    AMMError::Other(DispatchError::Token(TokenError::CannotCreate))
+  );
+}
@@ -2,12 +2,12 @@
 
 use alloc::vec::Vec;
 use codec::{Decode, DecodeWithMemTracking, Encode, MaxEncodedLen};
-use frame::prelude::*;
+use frame::{deps::sp_runtime::TokenError, prelude::*};
 use scale_info::TypeInfo;
 
 /// Main trait for Automated Market Makers (AMMs)
 pub trait AMM<AssetKind, Balance, AccountId> {
-  type Error: Into<DispatchError>;
+  type Error: Into<AMMError>;
 
   /// Check if this AMM can handle the given asset pair
   fn can_handle_pair(&self, asset_in: &AssetKind, asset_out: &AssetKind) -> bool;
@@ -23,6 +23,8 @@ pub trait AMM<AssetKind, Balance, AccountId> {
 
   /// Execute a token swap
   /// Note: This receives `amount_in` after router fee has been deducted
+  ///
+  /// Returns the amount of `asset_out` that `who` actually received, not a quote.
   fn execute_swap(
     &self,
     who: &AccountId,
@@ -36,6 +38,43 @@ pub trait AMM<AssetKind, Balance, AccountId> {
   fn name(&self) -> &'static str;
 }
 
+/// Why an AMM could not execute a swap, mapped to a router error of its own.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AMMError {
+  /// No pool exists for the pair
+  PoolNotFound,
+  /// The pool cannot provide the output, e.g. it would drain its reserves
+  InsufficientLiquidity,
+  /// The output would be below the requested minimum
+  SlippageExceeded,
+  /// The trader does not hold the input
+  InsufficientBalance,
+  /// An amount is below the minimum balance of its asset, or would leave an account below it
+  BelowMinimum,
+  /// An asset is frozen, blocked or unknown
+  AssetUnavailable,
+  /// Any other failure, passed on unchanged
+  Other(DispatchError),
+}
+
+impl From<DispatchError> for AMMError {
+  fn from(error: DispatchError) -> Self {
+    match error {
+      DispatchError::Token(TokenError::FundsUnavailable) => Self::InsufficientBalance,
+      DispatchError::Token(
+        TokenError::BelowMinimum | TokenError::OnlyProvider | TokenError::NotExpendable,
+      ) => Self::BelowMinimum,
+      DispatchError::Token(
+        TokenError::Frozen
+        | TokenError::Blocked
+        | TokenError::UnknownAsset
+        | TokenError::Unsupported,
+      ) => Self::AssetUnavailable,
+      other => Self::Other(other),
+    }
+  }
+}
+
 /// AMM types supported by the router
 #[derive(
   Clone, Copy, Debug, Decode, DecodeWithMemTracking, Encode, Eq, MaxEncodedLen, PartialEq, TypeInfo,
@@ -47,10 +86,132 @@ pub enum AMMType {
   TBC,
 }
 
+/// Pause status of the router for a given pair
+#[derive(Clone, Debug, Decode, Encode, Eq, PartialEq, TypeInfo)]
+pub struct PauseStatus {
+  /// All router swaps are paused
+  pub router_paused: bool,
+  /// Swaps on the pair are paused
+  pub pair_paused: bool,
+  /// AMMs the router does not route through
+  pub paused_amms: Vec<AMMType>,
+}
+
+/// Route the router would take for a swap, as quoted by its AMMs
+#[derive(Clone, Debug, Decode, Encode, Eq, PartialEq, TypeInfo)]
+pub struct SwapRoute<AssetKind, Balance> {
+  /// Assets swapped through, from the input to the output asset
+  pub path: Vec<AssetKind>,
+  /// AMM of each hop of `path`
+  pub amms: Vec<AMMType>,
+  /// Quoted output, net of router and AMM fees
+  pub amount_out: Balance,
+}
+
+/// What a router swap actually executed, used to charge its actual weight
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct ExecutedRoute {
+  /// Pool hops executed
+  pub hops: u32,
+}
+
+/// Fee settings the router currently applies
+#[derive(
+  Clone, Copy, Debug, Decode, DecodeWithMemTracking, Encode, Eq, MaxEncodedLen, PartialEq, TypeInfo,
+)]
+pub struct FeeSettings {
+  /// Router fee taken from the input of every swap
+  pub router_fee: Permill,
+  /// Share of the router fee sent to the treasury
+  pub treasury_fee_share: Permill,
+  /// Fee charged on the input of flash swaps on top of the router fee
+  pub flash_swap_fee: Permill,
+}
+
+/// Router fees of an asset charged to traders and received by the fee accounts
+#[derive(Clone, Debug, Decode, Default, Encode, Eq, MaxEncodedLen, PartialEq, TypeInfo)]
+pub struct FeeLedgerEntry<Balance> {
+  /// Router fees charged, as reported by `SwapExecuted` and `FlashSwapExecuted`
+  pub charged: Balance,
+  /// Router fees the fee collector and the treasury were credited with
+  pub received: Balance,
+}
+
 /// Trait for collecting router fees
 pub trait FeeCollector<AssetKind, Balance, AccountId> {
   /// Collect a fee from the specified account
-  fn collect_fee(&self, from: &AccountId, asset: &AssetKind, amount: Balance) -> DispatchResult;
+  ///
+  /// Returns each account credited with the amount it received.
+  fn collect_fee(
+    &self,
+    from: &AccountId,
+    asset: &AssetKind,
+    amount: Balance,
+  ) -> Result<Vec<(AccountId, Balance)>, DispatchError>;
+}
+
+/// Receives the output of a flash swap before its input has been paid.
+pub trait FlashSwapCallback<AccountId, AssetKind, Balance> {
+  /// Called once `amount_out` of `asset_out` has been sent to `who`.
+  ///
+  /// `who` must hold `amount_due` of `asset_in` when this returns; otherwise, or if this fails,
+  /// the whole flash swap is reverted. Returns the weight the callback consumed.
+  fn on_flash_swap(
+    self,
+    who: &AccountId,
+    asset_in: &AssetKind,
+    amount_due: Balance,
+    asset_out: &AssetKind,
+    amount_out: Balance,
+  ) -> Result<Weight, DispatchError>;
+}
+
+/// Delivers assets held by a local account to a beneficiary on another chain.
+///
+/// Used by `swap_and_send` to deliver the swap output; `()` disables cross-chain delivery.
+pub trait CrossChainTransfer<AccountId, AssetKind, Balance> {
+  /// Identifies destinations and beneficiaries, e.g. a versioned XCM location.
+  type Location: Parameter;
+
+  /// Transfer `amount` of `asset` from `who` to `beneficiary` on `dest`.
+  ///
+  /// Execution on `dest` is paid from the transferred asset, delivery fees are charged to `who`.
+  fn transfer(
+    who: &AccountId,
+    asset: AssetKind,
+    amount: Balance,
+    dest: Self::Location,
+    beneficiary: Self::Location,
+  ) -> DispatchResult;
+
+  /// Weight of `transfer`.
+  fn transfer_weight() -> Weight;
+
+  /// Delivery fee, in the native token, that `transfer` would charge to the sender.
+  ///
+  /// Returns `None` if the transfer cannot be delivered.
+  fn delivery_fee(
+    asset: &AssetKind,
+    amount: Balance,
+    dest: &Self::Location,
+    beneficiary: &Self::Location,
+  ) -> Option<Balance>;
+}
+
+impl<AccountId, AssetKind, Balance> CrossChainTransfer<AccountId, AssetKind, Balance> for () {
+  type Location = ();
+
+  fn transfer(_: &AccountId, _: AssetKind, _: Balance, _: (), _: ()) -> DispatchResult {
+    Err(DispatchError::Other("cross-chain transfers are disabled"))
+  }
+
+  fn transfer_weight() -> Weight {
+    Weight::zero()
+  }
+
+  fn delivery_fee(_: &AssetKind, _: Balance, _: &(), _: &()) -> Option<Balance> {
+    None
+  }
 }
 
 /// Trait for routing strategies
@@ -0,0 +1,292 @@
+//! Weights for `pallet_dex_router`.
+//!
+//! These are placeholders that have not been benchmarked: the storage accesses are counted from
+//! the code, the execution times and proof sizes are estimates. Replace this file with the output
+//! of the benchmarks on reference hardware, from the project root:
+//!
+//! ```sh
+//! ./scripts/benchmark-weights.sh
+//! ```
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use core::marker::PhantomData;
+use frame::{deps::frame_support::weights::constants::RocksDbWeight, prelude::*};
+
+/// Weight functions needed for `pallet_dex_router`.
+pub trait WeightInfo {
+  fn swap_exact_tokens_for_tokens(p: u32) -> Weight;
+  fn reset_circuit_breaker() -> Weight;
+  fn set_router_paused() -> Weight;
+  fn set_pair_paused() -> Weight;
+  fn set_amm_paused() -> Weight;
+  fn add_liquidity() -> Weight;
+  fn remove_liquidity() -> Weight;
+  fn zap_in() -> Weight;
+  fn zap_out() -> Weight;
+  fn flash_swap() -> Weight;
+  fn set_fee_settings() -> Weight;
+}
+
+/// Placeholder weights for `pallet_dex_router`, see the module documentation.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+  /// Storage: `DexRouter::RouterPaused` (r:1 w:0)
+  /// Storage: `DexRouter::PausedPairs` (r:4 w:0)
+  /// Storage: `DexRouter::PausedAMMs` (r:1 w:0)
+  /// Storage: `DexRouter::TrippedPairs` (r:4 w:0)
+  /// Storage: `DexRouter::PairObservations` (r:4 w:4)
+  /// Storage: `DexRouter::FeeLedger` (r:4 w:4)
+  /// Storage: `AssetConversion::Pools` (r:4 w:0)
+  /// Storage: `System::Account` (r:7 w:7)
+  /// Storage: `Assets::Asset` (r:3 w:3)
+  /// Storage: `Assets::Account` (r:8 w:7)
+  fn swap_exact_tokens_for_tokens(p: u32) -> Weight {
+    // Placeholder, not benchmarked
+    Weight::from_parts(22_000_000, 1492)
+      .saturating_add(Weight::from_parts(95_000_000, 0).saturating_mul(p.into()))
+      .saturating_add(T::DbWeight::get().reads(2_u64))
+      .saturating_add(T::DbWeight::get().reads((8_u64).saturating_mul(p.into())))
+      .saturating_add(T::DbWeight::get().writes(1_u64))
+      .saturating_add(T::DbWeight::get().writes((5_u64).saturating_mul(p.into())))
+      .saturating_add(Weight::from_parts(0, 4957).saturating_mul(p.into()))
+  }
+  /// Storage: `DexRouter::TrippedPairs` (r:1 w:1)
+  /// Storage: `DexRouter::PairObservations` (r:0 w:1)
+  fn reset_circuit_breaker() -> Weight {
+    // Placeholder, not benchmarked
+    Weight::from_parts(15_000_000, 4703)
+      .saturating_add(T::DbWeight::get().reads(1_u64))
+      .saturating_add(T::DbWeight::get().writes(2_u64))
+  }
+  /// Storage: `DexRouter::RouterPaused` (r:0 w:1)
+  fn set_router_paused() -> Weight {
+    // Placeholder, not benchmarked
+    Weight::from_parts(7_000_000, 0).saturating_add(T::DbWeight::get().writes(1_u64))
+  }
+  /// Storage: `DexRouter::PausedPairs` (r:0 w:1)
+  fn set_pair_paused() -> Weight {
+    // Placeholder, not benchmarked
+    Weight::from_parts(9_000_000, 0).saturating_add(T::DbWeight::get().writes(1_u64))
+  }
+  /// Storage: `DexRouter::PausedAMMs` (r:0 w:1)
+  fn set_amm_paused() -> Weight {
+    // Placeholder, not benchmarked
+    Weight::from_parts(8_000_000, 0).saturating_add(T::DbWeight::get().writes(1_u64))
+  }
+  /// Storage: `AssetConversion::Pools` (r:1 w:1)
+  /// Storage: `AssetConversion::NextPoolAssetId` (r:1 w:1)
+  /// Storage: `PoolAssets::Asset` (r:1 w:1)
+  /// Storage: `PoolAssets::Account` (r:2 w:2)
+  /// Storage: `System::Account` (r:3 w:3)
+  /// Storage: `Assets::Asset` (r:1 w:1)
+  /// Storage: `Assets::Account` (r:2 w:2)
+  fn add_liquidity() -> Weight {
+    // Placeholder, not benchmarked
+    Weight::from_parts(168_000_000, 7404)
+      .saturating_add(T::DbWeight::get().reads(11_u64))
+      .saturating_add(T::DbWeight::get().writes(11_u64))
+  }
+  /// Storage: `AssetConversion::Pools` (r:1 w:0)
+  /// Storage: `PoolAssets::Asset` (r:1 w:1)
+  /// Storage: `PoolAssets::Account` (r:2 w:2)
+  /// Storage: `System::Account` (r:2 w:2)
+  /// Storage: `Assets::Asset` (r:1 w:1)
+  /// Storage: `Assets::Account` (r:2 w:2)
+  fn remove_liquidity() -> Weight {
+    // Placeholder, not benchmarked
+    Weight::from_parts(142_000_000, 6208)
+      .saturating_add(T::DbWeight::get().reads(9_u64))
+      .saturating_add(T::DbWeight::get().writes(8_u64))
+  }
+  /// Storage: `DexRouter::RouterPaused` (r:1 w:0)
+  /// Storage: `DexRouter::PausedPairs` (r:1 w:0)
+  /// Storage: `DexRouter::PausedAMMs` (r:1 w:0)
+  /// Storage: `DexRouter::TrippedPairs` (r:1 w:0)
+  /// Storage: `DexRouter::PairObservations` (r:1 w:1)
+  /// Storage: `DexRouter::FeeLedger` (r:1 w:1)
+  /// Storage: `AssetConversion::Pools` (r:1 w:0)
+  /// Storage: `System::Account` (r:4 w:4)
+  /// Storage: `Assets::Asset` (r:1 w:1)
+  /// Storage: `Assets::Account` (r:3 w:3)
+  /// Storage: `PoolAssets::Asset` (r:1 w:1)
+  /// Storage: `PoolAssets::Account` (r:2 w:2)
+  fn zap_in() -> Weight {
+    // Placeholder, not benchmarked
+    Weight::from_parts(346_000_000, 11406)
+      .saturating_add(T::DbWeight::get().reads(18_u64))
+      .saturating_add(T::DbWeight::get().writes(13_u64))
+  }
+  /// Storage: `DexRouter::RouterPaused` (r:1 w:0)
+  /// Storage: `DexRouter::PausedPairs` (r:1 w:0)
+  /// Storage: `DexRouter::PausedAMMs` (r:1 w:0)
+  /// Storage: `DexRouter::TrippedPairs` (r:1 w:0)
+  /// Storage: `DexRouter::PairObservations` (r:1 w:1)
+  /// Storage: `DexRouter::FeeLedger` (r:1 w:1)
+  /// Storage: `AssetConversion::Pools` (r:1 w:0)
+  /// Storage: `System::Account` (r:4 w:4)
+  /// Storage: `Assets::Asset` (r:1 w:1)
+  /// Storage: `Assets::Account` (r:3 w:3)
+  /// Storage: `PoolAssets::Asset` (r:1 w:1)
+  /// Storage: `PoolAssets::Account` (r:2 w:2)
+  fn zap_out() -> Weight {
+    // Placeholder, not benchmarked
+    Weight::from_parts(330_000_000, 11406)
+      .saturating_add(T::DbWeight::get().reads(18_u64))
+      .saturating_add(T::DbWeight::get().writes(13_u64))
+  }
+  /// Storage: `DexRouter::FlashSwapPair` (r:1 w:1)
+  /// Storage: `DexRouter::RouterPaused` (r:1 w:0)
+  /// Storage: `DexRouter::PausedPairs` (r:1 w:0)
+  /// Storage: `DexRouter::PausedAMMs` (r:1 w:0)
+  /// Storage: `DexRouter::TrippedPairs` (r:1 w:0)
+  /// Storage: `DexRouter::PairObservations` (r:1 w:1)
+  /// Storage: `DexRouter::FeeLedger` (r:1 w:1)
+  /// Storage: `AssetConversion::Pools` (r:1 w:0)
+  /// Storage: `System::Account` (r:4 w:4)
+  /// Storage: `Assets::Asset` (r:1 w:1)
+  /// Storage: `Assets::Account` (r:2 w:2)
+  fn flash_swap() -> Weight {
+    // Placeholder, not benchmarked
+    Weight::from_parts(214_000_000, 11406)
+      .saturating_add(T::DbWeight::get().reads(15_u64))
+      .saturating_add(T::DbWeight::get().writes(10_u64))
+  }
+  /// Storage: `DexRouter::FeeSettingsOverride` (r:0 w:1)
+  fn set_fee_settings() -> Weight {
+    // Placeholder, not benchmarked
+    Weight::from_parts(7_000_000, 0).saturating_add(T::DbWeight::get().writes(1_u64))
+  }
+}
+
+// For backwards compatibility and tests.
+impl WeightInfo for () {
+  /// Storage: `DexRouter::RouterPaused` (r:1 w:0)
+  /// Storage: `DexRouter::PausedPairs` (r:4 w:0)
+  /// Storage: `DexRouter::PausedAMMs` (r:1 w:0)
+  /// Storage: `DexRouter::TrippedPairs` (r:4 w:0)
+  /// Storage: `DexRouter::PairObservations` (r:4 w:4)
+  /// Storage: `DexRouter::FeeLedger` (r:4 w:4)
+  /// Storage: `AssetConversion::Pools` (r:4 w:0)
+  /// Storage: `System::Account` (r:7 w:7)
+  /// Storage: `Assets::Asset` (r:3 w:3)
+  /// Storage: `Assets::Account` (r:8 w:7)
+  fn swap_exact_tokens_for_tokens(p: u32) -> Weight {
+    // Placeholder, not benchmarked
+    Weight::from_parts(22_000_000, 1492)
+      .saturating_add(Weight::from_parts(95_000_000, 0).saturating_mul(p.into()))
+      .saturating_add(RocksDbWeight::get().reads(2_u64))
+      .saturating_add(RocksDbWeight::get().reads((8_u64).saturating_mul(p.into())))
+      .saturating_add(RocksDbWeight::get().writes(1_u64))
+      .saturating_add(RocksDbWeight::get().writes((5_u64).saturating_mul(p.into())))
+      .saturating_add(Weight::from_parts(0, 4957).saturating_mul(p.into()))
+  }
+  /// Storage: `DexRouter::TrippedPairs` (r:1 w:1)
+  /// Storage: `DexRouter::PairObservations` (r:0 w:1)
+  fn reset_circuit_breaker() -> Weight {
+    // Placeholder, not benchmarked
+    Weight::from_parts(15_000_000, 4703)
+      .saturating_add(RocksDbWeight::get().reads(1_u64))
+      .saturating_add(RocksDbWeight::get().writes(2_u64))
+  }
+  /// Storage: `DexRouter::RouterPaused` (r:0 w:1)
+  fn set_router_paused() -> Weight {
+    // Placeholder, not benchmarked
+    Weight::from_parts(7_000_000, 0).saturating_add(RocksDbWeight::get().writes(1_u64))
+  }
+  /// Storage: `DexRouter::PausedPairs` (r:0 w:1)
+  fn set_pair_paused() -> Weight {
+    // Placeholder, not benchmarked
+    Weight::from_parts(9_000_000, 0).saturating_add(RocksDbWeight::get().writes(1_u64))
+  }
+  /// Storage: `DexRouter::PausedAMMs` (r:0 w:1)
+  fn set_amm_paused() -> Weight {
+    // Placeholder, not benchmarked
+    Weight::from_parts(8_000_000, 0).saturating_add(RocksDbWeight::get().writes(1_u64))
+  }
+  /// Storage: `AssetConversion::Pools` (r:1 w:1)
+  /// Storage: `AssetConversion::NextPoolAssetId` (r:1 w:1)
+  /// Storage: `PoolAssets::Asset` (r:1 w:1)
+  /// Storage: `PoolAssets::Account` (r:2 w:2)
+  /// Storage: `System::Account` (r:3 w:3)
+  /// Storage: `Assets::Asset` (r:1 w:1)
+  /// Storage: `Assets::Account` (r:2 w:2)
+  fn add_liquidity() -> Weight {
+    // Placeholder, not benchmarked
+    Weight::from_parts(168_000_000, 7404)
+      .saturating_add(RocksDbWeight::get().reads(11_u64))
+      .saturating_add(RocksDbWeight::get().writes(11_u64))
+  }
+  /// Storage: `AssetConversion::Pools` (r:1 w:0)
+  /// Storage: `PoolAssets::Asset` (r:1 w:1)
+  /// Storage: `PoolAssets::Account` (r:2 w:2)
+  /// Storage: `System::Account` (r:2 w:2)
+  /// Storage: `Assets::Asset` (r:1 w:1)
+  /// Storage: `Assets::Account` (r:2 w:2)
+  fn remove_liquidity() -> Weight {
+    // Placeholder, not benchmarked
+    Weight::from_parts(142_000_000, 6208)
+      .saturating_add(RocksDbWeight::get().reads(9_u64))
+      .saturating_add(RocksDbWeight::get().writes(8_u64))
+  }
+  /// Storage: `DexRouter::RouterPaused` (r:1 w:0)
+  /// Storage: `DexRouter::PausedPairs` (r:1 w:0)
+  /// Storage: `DexRouter::PausedAMMs` (r:1 w:0)
+  /// Storage: `DexRouter::TrippedPairs` (r:1 w:0)
+  /// Storage: `DexRouter::PairObservations` (r:1 w:1)
+  /// Storage: `DexRouter::FeeLedger` (r:1 w:1)
+  /// Storage: `AssetConversion::Pools` (r:1 w:0)
+  /// Storage: `System::Account` (r:4 w:4)
+  /// Storage: `Assets::Asset` (r:1 w:1)
+  /// Storage: `Assets::Account` (r:3 w:3)
+  /// Storage: `PoolAssets::Asset` (r:1 w:1)
+  /// Storage: `PoolAssets::Account` (r:2 w:2)
+  fn zap_in() -> Weight {
+    // Placeholder, not benchmarked
+    Weight::from_parts(346_000_000, 11406)
+      .saturating_add(RocksDbWeight::get().reads(18_u64))
+      .saturating_add(RocksDbWeight::get().writes(13_u64))
+  }
+  /// Storage: `DexRouter::RouterPaused` (r:1 w:0)
+  /// Storage: `DexRouter::PausedPairs` (r:1 w:0)
+  /// Storage: `DexRouter::PausedAMMs` (r:1 w:0)
+  /// Storage: `DexRouter::TrippedPairs` (r:1 w:0)
+  /// Storage: `DexRouter::PairObservations` (r:1 w:1)
+  /// Storage: `DexRouter::FeeLedger` (r:1 w:1)
+  /// Storage: `AssetConversion::Pools` (r:1 w:0)
+  /// Storage: `System::Account` (r:4 w:4)
+  /// Storage: `Assets::Asset` (r:1 w:1)
+  /// Storage: `Assets::Account` (r:3 w:3)
+  /// Storage: `PoolAssets::Asset` (r:1 w:1)
+  /// Storage: `PoolAssets::Account` (r:2 w:2)
+  fn zap_out() -> Weight {
+    // Placeholder, not benchmarked
+    Weight::from_parts(330_000_000, 11406)
+      .saturating_add(RocksDbWeight::get().reads(18_u64))
+      .saturating_add(RocksDbWeight::get().writes(13_u64))
+  }
+  /// Storage: `DexRouter::FlashSwapPair` (r:1 w:1)
+  /// Storage: `DexRouter::RouterPaused` (r:1 w:0)
+  /// Storage: `DexRouter::PausedPairs` (r:1 w:0)
+  /// Storage: `DexRouter::PausedAMMs` (r:1 w:0)
+  /// Storage: `DexRouter::TrippedPairs` (r:1 w:0)
+  /// Storage: `DexRouter::PairObservations` (r:1 w:1)
+  /// Storage: `DexRouter::FeeLedger` (r:1 w:1)
+  /// Storage: `AssetConversion::Pools` (r:1 w:0)
+  /// Storage: `System::Account` (r:4 w:4)
+  /// Storage: `Assets::Asset` (r:1 w:1)
+  /// Storage: `Assets::Account` (r:2 w:2)
+  fn flash_swap() -> Weight {
+    // Placeholder, not benchmarked
+    Weight::from_parts(214_000_000, 11406)
+      .saturating_add(RocksDbWeight::get().reads(15_u64))
+      .saturating_add(RocksDbWeight::get().writes(10_u64))
+  }
+  /// Storage: `DexRouter::FeeSettingsOverride` (r:0 w:1)
+  fn set_fee_settings() -> Weight {
+    // Placeholder, not benchmarked
+    Weight::from_parts(7_000_000, 0).saturating_add(RocksDbWeight::get().writes(1_u64))
+  }
+}
@@ -0,0 +1,55 @@
+//! Single-asset liquidity primitives for the DEX router.
+//!
+//! Zapping in deposits a single asset into a two-sided pool: part of the input is swapped for
+//! the other asset of the pool and both sides are added as liquidity. The swapped part is chosen
+//! so that, after the swap has moved the price, the remaining input and the swap output match the
+//! pool ratio and as little as possible is left over.
+
+use frame::{deps::sp_core::U256, prelude::*};
+
+/// Share of the input the swap fee scale is expressed in.
+const FEE_SCALE: u128 = 1_000_000;
+
+/// Combined share of a swap taken by the router fee and the pool fee.
+///
+/// `lp_fee_per_mille` is the pool fee in tenths of a percent, as configured in
+/// `pallet_asset_conversion`.
+pub fn combined_swap_fee(router_fee: Permill, lp_fee_per_mille: u32) -> Permill {
+  let lp_fee = Permill::from_perthousand(lp_fee_per_mille);
+  let kept = router_fee.left_from_one() * lp_fee.left_from_one();
+  kept.left_from_one()
+}
+
+/// Amount of `amount_in` to swap into the other asset of a pool before adding liquidity.
+///
+/// Solves `s² (1 - f) + s r (2 - f) - a r = 0` for the swapped amount `s`, where `a` is
+/// `amount_in`, `r` is `reserve_in` and `f` is `swap_fee`, and rounds down. Returns zero for an
+/// empty pool.
+pub fn optimal_swap_amount<Balance>(
+  reserve_in: Balance,
+  amount_in: Balance,
+  swap_fee: Permill,
+) -> Balance
+where
+  Balance: UniqueSaturatedInto<u128> + UniqueSaturatedFrom<u128>,
+{
+  let reserve_in: u128 = reserve_in.unique_saturated_into();
+  let amount_in: u128 = amount_in.unique_saturated_into();
+  let kept = u128::from(swap_fee.left_from_one().deconstruct());
+  if reserve_in.is_zero() || kept.is_zero() {
+    return Balance::unique_saturated_from(0u128);
+  }
+
+  // Everything is scaled by `FEE_SCALE` so the fee stays an integer
+  let b = U256::from(reserve_in).saturating_mul(U256::from(FEE_SCALE.saturating_add(kept)));
+  let four_ac = U256::from(4u8)
+    .saturating_mul(U256::from(kept))
+    .saturating_mul(U256::from(FEE_SCALE))
+    .saturating_mul(U256::from(amount_in))
+    .saturating_mul(U256::from(reserve_in));
+  let root = b.saturating_mul(b).saturating_add(four_ac).integer_sqrt();
+  let swap = root.saturating_sub(b) / U256::from(kept.saturating_mul(2));
+
+  let swap = u128::try_from(swap).unwrap_or(amount_in).min(amount_in);
+  Balance::unique_saturated_from(swap)
+}
@@ -0,0 +1,142 @@
+//! Benchmarks for the farming pallet.
+
+use super::*;
+use frame::benchmarking::prelude::*;
+use frame_system::RawOrigin;
+
+/// Sets up the runtime state farming benchmarks depend on.
+pub trait BenchmarkHelper<AccountId, PoolAssetId, AssetId, Balance> {
+  /// Create an LP token and a reward asset and fund `caller` with both.
+  ///
+  /// Returns the LP token, the reward asset and an amount of each that `caller` holds.
+  fn setup_assets(caller: &AccountId) -> (PoolAssetId, AssetId, Balance);
+}
+
+/// Create a program for the helper's assets emitting a thousandth of `amount` per block.
+fn setup_program<T: Config>(
+  caller: &T::AccountId,
+) -> Result<(ProgramId, T::Balance), BenchmarkError> {
+  let (pool_asset, reward_asset, amount) = T::BenchmarkHelper::setup_assets(caller);
+  let origin = T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+  let end_block = frame_system::Pallet::<T>::block_number() + 100u32.into();
+
+  let program_id = NextProgramId::<T>::get();
+  Pallet::<T>::create_program(
+    origin,
+    pool_asset,
+    reward_asset,
+    amount / 1_000u32.into(),
+    end_block,
+  )?;
+  Ok((program_id, amount))
+}
+
+/// Advance to the next block so stakes have rewards to settle.
+fn next_block<T: Config>() {
+  let now = frame_system::Pallet::<T>::block_number();
+  frame_system::Pallet::<T>::set_block_number(now + One::one());
+}
+
+#[benchmarks]
+mod benchmarks {
+  use super::*;
+
+  #[benchmark]
+  fn create_program() -> Result<(), BenchmarkError> {
+    let caller: T::AccountId = whitelisted_caller();
+    let (pool_asset, reward_asset, amount) = T::BenchmarkHelper::setup_assets(&caller);
+    let origin = T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+    let end_block = frame_system::Pallet::<T>::block_number() + 100u32.into();
+
+    #[extrinsic_call]
+    _(
+      origin as T::RuntimeOrigin,
+      pool_asset,
+      reward_asset,
+      amount,
+      end_block,
+    );
+
+    assert!(Programs::<T>::contains_key(0));
+    Ok(())
+  }
+
+  /// Worst case: the caller already has a stake with rewards to settle.
+  #[benchmark]
+  fn stake() -> Result<(), BenchmarkError> {
+    let caller: T::AccountId = whitelisted_caller();
+    let (program_id, amount) = setup_program::<T>(&caller)?;
+    let half = amount / 2u32.into();
+    Pallet::<T>::stake(RawOrigin::Signed(caller.clone()).into(), program_id, half)?;
+    next_block::<T>();
+
+    #[extrinsic_call]
+    _(RawOrigin::Signed(caller.clone()), program_id, half);
+
+    assert!(Stakes::<T>::get(program_id, &caller).is_some_and(|s| !s.pending.is_zero()));
+    Ok(())
+  }
+
+  #[benchmark]
+  fn unstake() -> Result<(), BenchmarkError> {
+    let caller: T::AccountId = whitelisted_caller();
+    let (program_id, amount) = setup_program::<T>(&caller)?;
+    Pallet::<T>::stake(RawOrigin::Signed(caller.clone()).into(), program_id, amount)?;
+    next_block::<T>();
+
+    #[extrinsic_call]
+    _(
+      RawOrigin::Signed(caller.clone()),
+      program_id,
+      amount / 2u32.into(),
+    );
+
+    assert!(Stakes::<T>::get(program_id, &caller).is_some_and(|s| !s.pending.is_zero()));
+    Ok(())
+  }
+
+  #[benchmark]
+  fn harvest() -> Result<(), BenchmarkError> {
+    let caller: T::AccountId = whitelisted_caller();
+    let (program_id, amount) = setup_program::<T>(&caller)?;
+    let half = amount / 2u32.into();
+    Pallet::<T>::stake(RawOrigin::Signed(caller.clone()).into(), program_id, half)?;
+    Pallet::<T>::do_fund_program(&caller, program_id, half)?;
+    next_block::<T>();
+
+    #[extrinsic_call]
+    _(RawOrigin::Signed(caller.clone()), program_id);
+
+    assert!(Stakes::<T>::get(program_id, &caller).is_some_and(|s| s.pending.is_zero()));
+    Ok(())
+  }
+
+  #[benchmark]
+  fn fund_program() -> Result<(), BenchmarkError> {
+    let caller: T::AccountId = whitelisted_caller();
+    let (program_id, amount) = setup_program::<T>(&caller)?;
+
+    #[extrinsic_call]
+    _(
+      RawOrigin::Signed(caller.clone()),
+      program_id,
+      amount / 2u32.into(),
+    );
+
+    Ok(())
+  }
+
+  #[benchmark]
+  fn set_program_emission() -> Result<(), BenchmarkError> {
+    let caller: T::AccountId = whitelisted_caller();
+    let (program_id, amount) = setup_program::<T>(&caller)?;
+    let origin = T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+    let end_block = frame_system::Pallet::<T>::block_number() + 200u32.into();
+
+    #[extrinsic_call]
+    _(origin as T::RuntimeOrigin, program_id, amount, end_block);
+
+    assert!(Programs::<T>::get(program_id).is_some_and(|p| p.end_block == end_block));
+    Ok(())
+  }
+}
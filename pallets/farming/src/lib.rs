@@ -0,0 +1,536 @@
+//! # Farming Pallet
+//!
+//! Liquidity mining rewards for LP token stakers.
+//!
+//! ## Overview
+//!
+//! Pools need incentives to bootstrap their liquidity. An admin creates reward programs for the
+//! LP token of a pool, each with a reward asset and a fixed emission per block. Liquidity
+//! providers stake their LP tokens in a program and earn its emission pro rata to their stake.
+//!
+//! ## Features
+//!
+//! - **Reward Programs**: Per pool, with any reward asset, emission rate and end block
+//! - **Accumulator Accounting**: Rewards accrue per block in constant time, see [`rewards`]
+//! - **Program Funding**: Anyone can fund a program; the admin can fund programs from the router
+//!   fees held by `FeeSource`
+//!
+//! Staked LP tokens and the rewards of a program are held by the program's account, derived from
+//! `PalletId`. Rewards are only paid out while that account holds enough of the reward asset, so
+//! programs should be funded for their whole duration.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+// The call and event expansions of FRAME clone `Copy` fields
+#![allow(clippy::clone_on_copy)]
+
+use frame::{
+  deps::{
+    frame_support::{
+      traits::{
+        fungibles::{self, Mutate as _},
+        tokens::Preservation,
+      },
+      PalletId,
+    },
+    sp_runtime::traits::AccountIdConversion,
+  },
+  prelude::*,
+};
+
+pub mod rewards;
+pub use rewards::{ProgramInfo, StakeInfo};
+
+pub mod weights;
+pub use weights::*;
+
+#[cfg(feature = "runtime-benchmarks")]
+pub mod benchmarking;
+
+pub use pallet::*;
+
+#[cfg(test)]
+pub mod tests;
+
+/// Identifier of a reward program.
+pub type ProgramId = u32;
+
+#[frame::pallet]
+pub mod pallet {
+  use super::*;
+
+  #[pallet::config]
+  pub trait Config: frame_system::Config {
+    type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+    /// The balance type of LP tokens and rewards.
+    type Balance: Parameter + Member + Copy + Default + AtLeast32BitUnsigned + MaxEncodedLen;
+
+    /// Identifier of the LP token of a pool.
+    type PoolAssetId: Parameter + Member + Clone + MaxEncodedLen;
+
+    /// Identifier of the assets rewards are paid in.
+    type AssetId: Parameter + Member + Clone + MaxEncodedLen;
+
+    /// LP tokens staked in programs.
+    type PoolAssets: fungibles::Mutate<
+      Self::AccountId,
+      AssetId = Self::PoolAssetId,
+      Balance = Self::Balance,
+    >;
+
+    /// LP tokens of existing pools, the only ones programs can be created for.
+    type LpTokens: Contains<Self::PoolAssetId>;
+
+    /// Assets rewards are paid in.
+    type RewardAssets: fungibles::Mutate<
+      Self::AccountId,
+      AssetId = Self::AssetId,
+      Balance = Self::Balance,
+    >;
+
+    /// Origin allowed to create, update and fund programs from the router fees.
+    type AdminOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+    /// Derives the accounts holding the stakes and rewards of each program.
+    #[pallet::constant]
+    type PalletId: Get<PalletId>;
+
+    /// Account holding the router fees that `fund_program_from_fees` draws on.
+    #[pallet::constant]
+    type FeeSource: Get<Self::AccountId>;
+
+    /// Weight information for extrinsics.
+    type WeightInfo: WeightInfo;
+
+    /// Helper to set up LP tokens and reward assets for benchmarks.
+    #[cfg(feature = "runtime-benchmarks")]
+    type BenchmarkHelper: benchmarking::BenchmarkHelper<
+      Self::AccountId,
+      Self::PoolAssetId,
+      Self::AssetId,
+      Self::Balance,
+    >;
+  }
+
+  /// Program information stored on chain.
+  pub type ProgramInfoOf<T> = ProgramInfo<
+    <T as Config>::PoolAssetId,
+    <T as Config>::AssetId,
+    <T as Config>::Balance,
+    BlockNumberFor<T>,
+  >;
+
+  #[pallet::pallet]
+  pub struct Pallet<T>(_);
+
+  /// Identifier of the next program created.
+  #[pallet::storage]
+  pub type NextProgramId<T> = StorageValue<_, ProgramId, ValueQuery>;
+
+  /// Reward programs by identifier.
+  #[pallet::storage]
+  pub type Programs<T: Config> = StorageMap<_, Blake2_128Concat, ProgramId, ProgramInfoOf<T>>;
+
+  /// Stakes by program and account.
+  #[pallet::storage]
+  pub type Stakes<T: Config> = StorageDoubleMap<
+    _,
+    Blake2_128Concat,
+    ProgramId,
+    Blake2_128Concat,
+    T::AccountId,
+    StakeInfo<T::Balance>,
+  >;
+
+  #[pallet::event]
+  #[pallet::generate_deposit(pub(super) fn deposit_event)]
+  pub enum Event<T: Config> {
+    /// A reward program was created.
+    ProgramCreated {
+      /// The new program.
+      program_id: ProgramId,
+      /// The LP token stakers deposit.
+      pool_asset: T::PoolAssetId,
+      /// The asset rewards are paid in.
+      reward_asset: T::AssetId,
+      /// Rewards emitted per block.
+      reward_per_block: T::Balance,
+      /// Last block rewards are emitted for.
+      end_block: BlockNumberFor<T>,
+    },
+    /// The emission of a program was changed.
+    ProgramUpdated {
+      /// The updated program.
+      program_id: ProgramId,
+      /// Rewards emitted per block from now on.
+      reward_per_block: T::Balance,
+      /// Last block rewards are emitted for.
+      end_block: BlockNumberFor<T>,
+    },
+    /// Rewards were deposited into a program.
+    ProgramFunded {
+      /// The funded program.
+      program_id: ProgramId,
+      /// The account the rewards came from.
+      from: T::AccountId,
+      /// The amount of the reward asset deposited.
+      amount: T::Balance,
+    },
+    /// LP tokens were staked in a program.
+    Staked {
+      /// The staker.
+      who: T::AccountId,
+      /// The program staked in.
+      program_id: ProgramId,
+      /// The amount of LP tokens staked.
+      amount: T::Balance,
+    },
+    /// LP tokens were withdrawn from a program.
+    Unstaked {
+      /// The staker.
+      who: T::AccountId,
+      /// The program withdrawn from.
+      program_id: ProgramId,
+      /// The amount of LP tokens withdrawn.
+      amount: T::Balance,
+    },
+    /// Rewards were paid out to a staker.
+    RewardsHarvested {
+      /// The staker.
+      who: T::AccountId,
+      /// The program the rewards were earned in.
+      program_id: ProgramId,
+      /// The amount of the reward asset paid.
+      amount: T::Balance,
+    },
+  }
+
+  #[pallet::error]
+  pub enum Error<T> {
+    /// No program exists with this identifier.
+    UnknownProgram,
+    /// The end block of a program must be in the future.
+    InvalidEndBlock,
+    /// Amounts staked, withdrawn or funded must be non-zero.
+    ZeroAmount,
+    /// The account has not staked this much in the program.
+    InsufficientStake,
+    /// There are no rewards to harvest.
+    NoRewards,
+    /// The program account does not hold enough of the reward asset to pay out.
+    InsufficientRewards,
+    /// Program identifiers are exhausted.
+    Overflow,
+    /// The pool asset is not the LP token of an existing pool.
+    UnknownPoolAsset,
+  }
+
+  #[pallet::call]
+  impl<T: Config> Pallet<T> {
+    /// Create a reward program for the stakers of `pool_asset`, the LP token of an existing pool.
+    ///
+    /// Rewards are emitted from the next block until `end_block`. Fund the program with
+    /// `fund_program` or `fund_program_from_fees` before stakers harvest.
+    #[pallet::call_index(0)]
+    #[pallet::weight(T::WeightInfo::create_program())]
+    pub fn create_program(
+      origin: OriginFor<T>,
+      pool_asset: T::PoolAssetId,
+      reward_asset: T::AssetId,
+      reward_per_block: T::Balance,
+      end_block: BlockNumberFor<T>,
+    ) -> DispatchResult {
+      T::AdminOrigin::ensure_origin(origin)?;
+
+      let now = frame_system::Pallet::<T>::block_number();
+      ensure!(end_block > now, Error::<T>::InvalidEndBlock);
+      ensure!(
+        T::LpTokens::contains(&pool_asset),
+        Error::<T>::UnknownPoolAsset
+      );
+
+      let program_id = NextProgramId::<T>::get();
+      NextProgramId::<T>::put(program_id.checked_add(1).ok_or(Error::<T>::Overflow)?);
+
+      // Keep the program account alive regardless of the assets it holds
+      frame_system::Pallet::<T>::inc_providers(&Self::program_account(program_id));
+
+      Programs::<T>::insert(
+        program_id,
+        ProgramInfo {
+          pool_asset: pool_asset.clone(),
+          reward_asset: reward_asset.clone(),
+          reward_per_block,
+          end_block,
+          total_staked: Zero::zero(),
+          reward_per_token: FixedU128::zero(),
+          last_update: now,
+        },
+      );
+
+      Self::deposit_event(Event::ProgramCreated {
+        program_id,
+        pool_asset,
+        reward_asset,
+        reward_per_block,
+        end_block,
+      });
+
+      Ok(())
+    }
+
+    /// Stake LP tokens of the program's pool.
+    #[pallet::call_index(1)]
+    #[pallet::weight(T::WeightInfo::stake())]
+    pub fn stake(
+      origin: OriginFor<T>,
+      program_id: ProgramId,
+      amount: T::Balance,
+    ) -> DispatchResult {
+      let who = ensure_signed(origin)?;
+      ensure!(!amount.is_zero(), Error::<T>::ZeroAmount);
+
+      let mut program = Self::updated_program(program_id)?;
+      let mut stake = Self::settled_stake(program_id, &who, &program);
+
+      T::PoolAssets::transfer(
+        program.pool_asset.clone(),
+        &who,
+        &Self::program_account(program_id),
+        amount,
+        Preservation::Expendable,
+      )?;
+      stake.amount = stake.amount.saturating_add(amount);
+      program.total_staked = program.total_staked.saturating_add(amount);
+
+      Stakes::<T>::insert(program_id, &who, stake);
+      Programs::<T>::insert(program_id, program);
+
+      Self::deposit_event(Event::Staked {
+        who,
+        program_id,
+        amount,
+      });
+
+      Ok(())
+    }
+
+    /// Withdraw staked LP tokens; pending rewards stay harvestable.
+    #[pallet::call_index(2)]
+    #[pallet::weight(T::WeightInfo::unstake())]
+    pub fn unstake(
+      origin: OriginFor<T>,
+      program_id: ProgramId,
+      amount: T::Balance,
+    ) -> DispatchResult {
+      let who = ensure_signed(origin)?;
+      ensure!(!amount.is_zero(), Error::<T>::ZeroAmount);
+
+      let mut program = Self::updated_program(program_id)?;
+      let mut stake = Self::settled_stake(program_id, &who, &program);
+      ensure!(stake.amount >= amount, Error::<T>::InsufficientStake);
+
+      T::PoolAssets::transfer(
+        program.pool_asset.clone(),
+        &Self::program_account(program_id),
+        &who,
+        amount,
+        Preservation::Expendable,
+      )?;
+      stake.amount = stake.amount.saturating_sub(amount);
+      program.total_staked = program.total_staked.saturating_sub(amount);
+
+      Self::put_stake(program_id, &who, stake);
+      Programs::<T>::insert(program_id, program);
+
+      Self::deposit_event(Event::Unstaked {
+        who,
+        program_id,
+        amount,
+      });
+
+      Ok(())
+    }
+
+    /// Pay out the rewards the caller has earned in a program.
+    #[pallet::call_index(3)]
+    #[pallet::weight(T::WeightInfo::harvest())]
+    pub fn harvest(origin: OriginFor<T>, program_id: ProgramId) -> DispatchResult {
+      let who = ensure_signed(origin)?;
+
+      let program = Self::updated_program(program_id)?;
+      let mut stake = Self::settled_stake(program_id, &who, &program);
+      let amount = stake.pending;
+      ensure!(!amount.is_zero(), Error::<T>::NoRewards);
+
+      T::RewardAssets::transfer(
+        program.reward_asset.clone(),
+        &Self::program_account(program_id),
+        &who,
+        amount,
+        Preservation::Expendable,
+      )
+      .map_err(|_| Error::<T>::InsufficientRewards)?;
+      stake.pending = Zero::zero();
+
+      Self::put_stake(program_id, &who, stake);
+      Programs::<T>::insert(program_id, program);
+
+      Self::deposit_event(Event::RewardsHarvested {
+        who,
+        program_id,
+        amount,
+      });
+
+      Ok(())
+    }
+
+    /// Deposit rewards into a program from the caller.
+    #[pallet::call_index(4)]
+    #[pallet::weight(T::WeightInfo::fund_program())]
+    pub fn fund_program(
+      origin: OriginFor<T>,
+      program_id: ProgramId,
+      amount: T::Balance,
+    ) -> DispatchResult {
+      let who = ensure_signed(origin)?;
+
+      Self::do_fund_program(&who, program_id, amount)
+    }
+
+    /// Deposit rewards into a program from the router fees held by `FeeSource`.
+    #[pallet::call_index(5)]
+    #[pallet::weight(T::WeightInfo::fund_program())]
+    pub fn fund_program_from_fees(
+      origin: OriginFor<T>,
+      program_id: ProgramId,
+      amount: T::Balance,
+    ) -> DispatchResult {
+      T::AdminOrigin::ensure_origin(origin)?;
+
+      Self::do_fund_program(&T::FeeSource::get(), program_id, amount)
+    }
+
+    /// Change the emission of a program; rewards accrued so far are kept.
+    #[pallet::call_index(6)]
+    #[pallet::weight(T::WeightInfo::set_program_emission())]
+    pub fn set_program_emission(
+      origin: OriginFor<T>,
+      program_id: ProgramId,
+      reward_per_block: T::Balance,
+      end_block: BlockNumberFor<T>,
+    ) -> DispatchResult {
+      T::AdminOrigin::ensure_origin(origin)?;
+      ensure!(
+        end_block > frame_system::Pallet::<T>::block_number(),
+        Error::<T>::InvalidEndBlock
+      );
+
+      let mut program = Self::updated_program(program_id)?;
+      program.reward_per_block = reward_per_block;
+      program.end_block = end_block;
+      Programs::<T>::insert(program_id, program);
+
+      Self::deposit_event(Event::ProgramUpdated {
+        program_id,
+        reward_per_block,
+        end_block,
+      });
+
+      Ok(())
+    }
+  }
+
+  impl<T: Config> Pallet<T> {
+    /// Account holding the staked LP tokens and the rewards of a program.
+    pub fn program_account(program_id: ProgramId) -> T::AccountId {
+      T::PalletId::get().into_sub_account_truncating(program_id)
+    }
+
+    /// Rewards `who` could harvest from a program at the current block.
+    pub fn pending_rewards(program_id: ProgramId, who: &T::AccountId) -> Option<T::Balance> {
+      let program = Programs::<T>::get(program_id)?;
+      let program = Self::accrue(program, frame_system::Pallet::<T>::block_number());
+      Some(Self::settled_stake(program_id, who, &program).pending)
+    }
+
+    /// Deposit `amount` of a program's reward asset from `from`.
+    pub fn do_fund_program(
+      from: &T::AccountId,
+      program_id: ProgramId,
+      amount: T::Balance,
+    ) -> DispatchResult {
+      ensure!(!amount.is_zero(), Error::<T>::ZeroAmount);
+      let program = Programs::<T>::get(program_id).ok_or(Error::<T>::UnknownProgram)?;
+
+      T::RewardAssets::transfer(
+        program.reward_asset,
+        from,
+        &Self::program_account(program_id),
+        amount,
+        Preservation::Preserve,
+      )?;
+
+      Self::deposit_event(Event::ProgramFunded {
+        program_id,
+        from: from.clone(),
+        amount,
+      });
+
+      Ok(())
+    }
+
+    /// The program with its accumulator brought up to date with the current block.
+    fn updated_program(program_id: ProgramId) -> Result<ProgramInfoOf<T>, DispatchError> {
+      let program = Programs::<T>::get(program_id).ok_or(Error::<T>::UnknownProgram)?;
+      Ok(Self::accrue(
+        program,
+        frame_system::Pallet::<T>::block_number(),
+      ))
+    }
+
+    /// Accrue the emission between the last update and `now` to the accumulator.
+    fn accrue(mut program: ProgramInfoOf<T>, now: BlockNumberFor<T>) -> ProgramInfoOf<T> {
+      let until = now.min(program.end_block);
+      if until > program.last_update {
+        let blocks = until.saturating_sub(program.last_update);
+        program.reward_per_token =
+          program
+            .reward_per_token
+            .saturating_add(rewards::reward_per_token_increase(
+              program.reward_per_block,
+              blocks.unique_saturated_into(),
+              program.total_staked,
+            ));
+      }
+      program.last_update = program.last_update.max(now);
+      program
+    }
+
+    /// The stake of `who` with the rewards earned up to the program's accumulator settled.
+    fn settled_stake(
+      program_id: ProgramId,
+      who: &T::AccountId,
+      program: &ProgramInfoOf<T>,
+    ) -> StakeInfo<T::Balance> {
+      let mut stake = Stakes::<T>::get(program_id, who).unwrap_or_default();
+      let earned = rewards::earned(
+        stake.amount,
+        program.reward_per_token,
+        stake.reward_per_token_paid,
+      );
+      stake.pending = stake.pending.saturating_add(earned);
+      stake.reward_per_token_paid = program.reward_per_token;
+      stake
+    }
+
+    /// Store a stake, removing it once nothing is staked or pending.
+    fn put_stake(program_id: ProgramId, who: &T::AccountId, stake: StakeInfo<T::Balance>) {
+      if stake.amount.is_zero() && stake.pending.is_zero() {
+        Stakes::<T>::remove(program_id, who);
+      } else {
+        Stakes::<T>::insert(program_id, who, stake);
+      }
+    }
+  }
+}
@@ -0,0 +1,67 @@
+//! Reward accounting for farming programs.
+//!
+//! Every program keeps a reward-per-token accumulator: the rewards one staked LP token has
+//! earned since the program was created. Each staker records the accumulator value their rewards
+//! were last settled at, so their earnings are their stake times the growth of the accumulator
+//! since then. Updating a program or a stake is constant time regardless of the number of
+//! stakers.
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame::prelude::*;
+use scale_info::TypeInfo;
+
+/// A reward program for the stakers of one pool's LP token
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct ProgramInfo<PoolAssetId, AssetId, Balance, BlockNumber> {
+  /// LP token stakers deposit.
+  pub pool_asset: PoolAssetId,
+  /// Asset the rewards are paid in.
+  pub reward_asset: AssetId,
+  /// Rewards emitted per block, shared pro rata between stakers.
+  pub reward_per_block: Balance,
+  /// Last block rewards are emitted for.
+  pub end_block: BlockNumber,
+  /// Total amount of LP tokens staked.
+  pub total_staked: Balance,
+  /// Rewards accrued per staked LP token since the program was created.
+  pub reward_per_token: FixedU128,
+  /// Block the accumulator was last brought up to date at.
+  pub last_update: BlockNumber,
+}
+
+/// The stake of one account in a program
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen, Default)]
+pub struct StakeInfo<Balance> {
+  /// Amount of LP tokens staked.
+  pub amount: Balance,
+  /// Accumulator value the pending rewards were last settled at.
+  pub reward_per_token_paid: FixedU128,
+  /// Rewards settled but not harvested yet.
+  pub pending: Balance,
+}
+
+/// Growth of the reward-per-token accumulator over `blocks` blocks.
+///
+/// Nothing accrues while nothing is staked; those rewards are not emitted.
+pub fn reward_per_token_increase<Balance: UniqueSaturatedInto<u128>>(
+  reward_per_block: Balance,
+  blocks: u128,
+  total_staked: Balance,
+) -> FixedU128 {
+  let total_staked: u128 = total_staked.unique_saturated_into();
+  if total_staked.is_zero() {
+    return FixedU128::zero();
+  }
+
+  let reward_per_block: u128 = reward_per_block.unique_saturated_into();
+  FixedU128::saturating_from_rational(reward_per_block.saturating_mul(blocks), total_staked)
+}
+
+/// Rewards earned by `amount` staked tokens while the accumulator grew from `paid` to `current`.
+pub fn earned<Balance>(amount: Balance, current: FixedU128, paid: FixedU128) -> Balance
+where
+  Balance: UniqueSaturatedInto<u128> + UniqueSaturatedFrom<u128>,
+{
+  let amount: u128 = amount.unique_saturated_into();
+  Balance::unique_saturated_from(current.saturating_sub(paid).saturating_mul_int(amount))
+}
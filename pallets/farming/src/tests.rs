@@ -0,0 +1,50 @@
+//! Unit tests for the farming pallet.
+
+use crate::rewards::*;
+use frame::prelude::*;
+
+#[test]
+fn reward_per_token_accrues_pro_rata() {
+  // 100 per block for 10 blocks over 500 staked tokens
+  assert_eq!(
+    reward_per_token_increase(100u128, 10, 500u128),
+    FixedU128::from_u32(2)
+  );
+  assert_eq!(
+    reward_per_token_increase(1u128, 1, 3u128),
+    FixedU128::from_rational(1, 3)
+  );
+
+  // Nothing accrues while nothing is staked
+  assert_eq!(
+    reward_per_token_increase(100u128, 10, 0u128),
+    FixedU128::zero()
+  );
+}
+
+#[test]
+fn earned_rewards_follow_the_accumulator() {
+  let paid = FixedU128::from_u32(2);
+  let current = FixedU128::from_rational(7, 2);
+
+  assert_eq!(earned(200u128, current, paid), 300);
+  assert_eq!(earned(200u128, paid, paid), 0);
+  assert_eq!(earned(0u128, current, paid), 0);
+}
+
+#[test]
+fn stakers_share_emission_by_stake() {
+  // Alice stakes 300 and Bob 100 of a program emitting 40 per block
+  let mut reward_per_token = FixedU128::zero();
+  reward_per_token =
+    reward_per_token.saturating_add(reward_per_token_increase(40u128, 10, 400u128));
+
+  let alice = earned(300u128, reward_per_token, FixedU128::zero());
+  let bob = earned(100u128, reward_per_token, FixedU128::zero());
+  assert_eq!((alice, bob), (300, 100));
+
+  // Bob joins late and only earns from the accumulator value he joined at
+  let joined_at = reward_per_token;
+  reward_per_token = reward_per_token.saturating_add(reward_per_token_increase(40u128, 5, 400u128));
+  assert_eq!(earned(100u128, reward_per_token, joined_at), 50);
+}
@@ -0,0 +1,148 @@
+//! Weights for `pallet_farming`.
+//!
+//! These are placeholders that have not been benchmarked: the storage accesses are counted from
+//! the code, the execution times and proof sizes are estimates. `create_program` counts one read
+//! of `AssetConversion::Pools` for the runtime's `LpTokens` check. Replace this file with the
+//! output of the benchmarks on reference hardware, from the project root:
+//!
+//! ```sh
+//! ./scripts/benchmark-weights.sh
+//! ```
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use core::marker::PhantomData;
+use frame::{deps::frame_support::weights::constants::RocksDbWeight, prelude::*};
+
+/// Weight functions needed for `pallet_farming`.
+pub trait WeightInfo {
+  fn create_program() -> Weight;
+  fn stake() -> Weight;
+  fn unstake() -> Weight;
+  fn harvest() -> Weight;
+  fn fund_program() -> Weight;
+  fn set_program_emission() -> Weight;
+}
+
+/// Placeholder weights for `pallet_farming`, see the module documentation.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+  /// Storage: `Farming::NextProgramId` (r:1 w:1)
+  /// Storage: `System::Account` (r:1 w:1)
+  /// Storage: `AssetConversion::Pools` (r:1 w:0)
+  /// Storage: `Farming::Programs` (r:0 w:1)
+  fn create_program() -> Weight {
+    // Placeholder, not benchmarked
+    Weight::from_parts(19_000_000, 3593)
+      .saturating_add(T::DbWeight::get().reads(3_u64))
+      .saturating_add(T::DbWeight::get().writes(3_u64))
+  }
+  /// Storage: `Farming::Programs` (r:1 w:1)
+  /// Storage: `Farming::Stakes` (r:1 w:1)
+  /// Storage: `PoolAssets::Asset` (r:1 w:1)
+  /// Storage: `PoolAssets::Account` (r:2 w:2)
+  fn stake() -> Weight {
+    // Placeholder, not benchmarked
+    Weight::from_parts(61_000_000, 6208)
+      .saturating_add(T::DbWeight::get().reads(5_u64))
+      .saturating_add(T::DbWeight::get().writes(5_u64))
+  }
+  /// Storage: `Farming::Programs` (r:1 w:1)
+  /// Storage: `Farming::Stakes` (r:1 w:1)
+  /// Storage: `PoolAssets::Asset` (r:1 w:1)
+  /// Storage: `PoolAssets::Account` (r:2 w:2)
+  fn unstake() -> Weight {
+    // Placeholder, not benchmarked
+    Weight::from_parts(63_000_000, 6208)
+      .saturating_add(T::DbWeight::get().reads(5_u64))
+      .saturating_add(T::DbWeight::get().writes(5_u64))
+  }
+  /// Storage: `Farming::Programs` (r:1 w:1)
+  /// Storage: `Farming::Stakes` (r:1 w:1)
+  /// Storage: `Assets::Asset` (r:1 w:1)
+  /// Storage: `Assets::Account` (r:2 w:2)
+  fn harvest() -> Weight {
+    // Placeholder, not benchmarked
+    Weight::from_parts(64_000_000, 6208)
+      .saturating_add(T::DbWeight::get().reads(5_u64))
+      .saturating_add(T::DbWeight::get().writes(5_u64))
+  }
+  /// Storage: `Farming::Programs` (r:1 w:0)
+  /// Storage: `Assets::Asset` (r:1 w:1)
+  /// Storage: `Assets::Account` (r:2 w:2)
+  fn fund_program() -> Weight {
+    // Placeholder, not benchmarked
+    Weight::from_parts(49_000_000, 6208)
+      .saturating_add(T::DbWeight::get().reads(4_u64))
+      .saturating_add(T::DbWeight::get().writes(3_u64))
+  }
+  /// Storage: `Farming::Programs` (r:1 w:1)
+  fn set_program_emission() -> Weight {
+    // Placeholder, not benchmarked
+    Weight::from_parts(14_000_000, 4164)
+      .saturating_add(T::DbWeight::get().reads(1_u64))
+      .saturating_add(T::DbWeight::get().writes(1_u64))
+  }
+}
+
+// For backwards compatibility and tests.
+impl WeightInfo for () {
+  /// Storage: `Farming::NextProgramId` (r:1 w:1)
+  /// Storage: `System::Account` (r:1 w:1)
+  /// Storage: `AssetConversion::Pools` (r:1 w:0)
+  /// Storage: `Farming::Programs` (r:0 w:1)
+  fn create_program() -> Weight {
+    // Placeholder, not benchmarked
+    Weight::from_parts(19_000_000, 3593)
+      .saturating_add(RocksDbWeight::get().reads(3_u64))
+      .saturating_add(RocksDbWeight::get().writes(3_u64))
+  }
+  /// Storage: `Farming::Programs` (r:1 w:1)
+  /// Storage: `Farming::Stakes` (r:1 w:1)
+  /// Storage: `PoolAssets::Asset` (r:1 w:1)
+  /// Storage: `PoolAssets::Account` (r:2 w:2)
+  fn stake() -> Weight {
+    // Placeholder, not benchmarked
+    Weight::from_parts(61_000_000, 6208)
+      .saturating_add(RocksDbWeight::get().reads(5_u64))
+      .saturating_add(RocksDbWeight::get().writes(5_u64))
+  }
+  /// Storage: `Farming::Programs` (r:1 w:1)
+  /// Storage: `Farming::Stakes` (r:1 w:1)
+  /// Storage: `PoolAssets::Asset` (r:1 w:1)
+  /// Storage: `PoolAssets::Account` (r:2 w:2)
+  fn unstake() -> Weight {
+    // Placeholder, not benchmarked
+    Weight::from_parts(63_000_000, 6208)
+      .saturating_add(RocksDbWeight::get().reads(5_u64))
+      .saturating_add(RocksDbWeight::get().writes(5_u64))
+  }
+  /// Storage: `Farming::Programs` (r:1 w:1)
+  /// Storage: `Farming::Stakes` (r:1 w:1)
+  /// Storage: `Assets::Asset` (r:1 w:1)
+  /// Storage: `Assets::Account` (r:2 w:2)
+  fn harvest() -> Weight {
+    // Placeholder, not benchmarked
+    Weight::from_parts(64_000_000, 6208)
+      .saturating_add(RocksDbWeight::get().reads(5_u64))
+      .saturating_add(RocksDbWeight::get().writes(5_u64))
+  }
+  /// Storage: `Farming::Programs` (r:1 w:0)
+  /// Storage: `Assets::Asset` (r:1 w:1)
+  /// Storage: `Assets::Account` (r:2 w:2)
+  fn fund_program() -> Weight {
+    // Placeholder, not benchmarked
+    Weight::from_parts(49_000_000, 6208)
+      .saturating_add(RocksDbWeight::get().reads(4_u64))
+      .saturating_add(RocksDbWeight::get().writes(3_u64))
+  }
+  /// Storage: `Farming::Programs` (r:1 w:1)
+  fn set_program_emission() -> Weight {
+    // Placeholder, not benchmarked
+    Weight::from_parts(14_000_000, 4164)
+      .saturating_add(RocksDbWeight::get().reads(1_u64))
+      .saturating_add(RocksDbWeight::get().writes(1_u64))
+  }
+}
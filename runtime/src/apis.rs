@@ -3,6 +3,7 @@ use alloc::vec::Vec;
 
 use polkadot_sdk::*;
 
+use codec::Codec;
 use frame_support::{
   genesis_builder_helper::{build_state, get_preset},
   weights::Weight,
@@ -12,7 +13,7 @@ use sp_api::impl_runtime_apis;
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
 use sp_core::{crypto::KeyTypeId, OpaqueMetadata};
 use sp_runtime::{
-  traits::Block as BlockT,
+  traits::{Block as BlockT, MaybeDisplay},
   transaction_validity::{TransactionSource, TransactionValidity},
   ApplyExtrinsicResult,
 };
@@ -20,11 +21,36 @@ use sp_version::RuntimeVersion;
 
 // Local module imports
 use super::{
-  AccountId, AssetConversion, Balance, Block, ConsensusHook, Executive, InherentDataExt, Nonce,
-  ParachainSystem, Runtime, RuntimeCall, RuntimeGenesisConfig, SessionKeys, System,
+  AccountId, AssetConversion, Balance, Block, ConsensusHook, DexRouter, Executive, InherentDataExt,
+  Nonce, ParachainSystem, Runtime, RuntimeCall, RuntimeGenesisConfig, SessionKeys, System,
   TransactionPayment, SLOT_DURATION, VERSION,
 };
-use crate::configs::AssetKind;
+use crate::configs::{native_fee_in_asset, AssetKind};
+use staging_xcm::VersionedLocation;
+
+sp_api::decl_runtime_apis! {
+  /// Fee estimates for transactions paid in an asset other than the native token, implemented
+  /// next to `TransactionPaymentApi` and taking the same arguments as its `query_info`.
+  ///
+  /// Upstream has no runtime API for `ChargeAssetTxPayment` fees. Wallets on Asset Hub quote the
+  /// native fee with `AssetConversionApi`, which misses the router fee charged here, and
+  /// `XcmPaymentApi::query_weight_to_asset_fee` prices weight alone, without the length fee.
+  pub trait AssetFeePaymentApi<Balance, AssetKind>
+  where
+    Balance: Codec + MaybeDisplay,
+    AssetKind: Codec,
+  {
+    /// Fee of `uxt`, excluding the tip, in units of `asset` at current pool prices.
+    ///
+    /// This is what `ChargeAssetTxPayment` withdraws from the signer when `uxt` pays in `asset`.
+    /// Returns `None` if the fee cannot be paid in `asset`.
+    fn query_fee_in_asset(
+      uxt: <Block as BlockT>::Extrinsic,
+      len: u32,
+      asset: AssetKind,
+    ) -> Option<Balance>;
+  }
+}
 
 // we move some impls outside so we can easily use them with `docify`.
 impl Runtime {
@@ -195,6 +221,17 @@ impl_runtime_apis! {
       }
     }
 
+    impl self::AssetFeePaymentApi<Block, Balance, AssetKind> for Runtime {
+      fn query_fee_in_asset(
+        uxt: <Block as BlockT>::Extrinsic,
+        len: u32,
+        asset: AssetKind,
+      ) -> Option<Balance> {
+        let native_fee = TransactionPayment::query_info(uxt, len).partial_fee;
+        native_fee_in_asset(asset, native_fee)
+      }
+    }
+
     impl pallet_asset_conversion::AssetConversionApi<Block, Balance, AssetKind> for Runtime {
       fn quote_price_exact_tokens_for_tokens(
         asset1: AssetKind,
@@ -219,7 +256,23 @@ impl_runtime_apis! {
       }
     }
 
+    impl pallet_dex_router::runtime_api::DexRouterApi<Block, AssetKind> for Runtime {
+      fn pause_status(asset_a: AssetKind, asset_b: AssetKind) -> pallet_dex_router::PauseStatus {
+        DexRouter::pause_status(asset_a, asset_b)
+      }
+    }
 
+    impl pallet_dex_router::runtime_api::SwapAndSendApi<Block, AssetKind, Balance, VersionedLocation> for Runtime {
+      fn quote_swap_and_send(
+        asset_in: AssetKind,
+        asset_out: AssetKind,
+        amount_in: Balance,
+        dest: VersionedLocation,
+        beneficiary: VersionedLocation,
+      ) -> Option<(Balance, Balance)> {
+        DexRouter::quote_swap_and_send(asset_in, asset_out, amount_in, &dest, &beneficiary)
+      }
+    }
 
     impl cumulus_primitives_core::CollectCollationInfo<Block> for Runtime {
         fn collect_collation_info(header: &<Block as BlockT>::Header) -> cumulus_primitives_core::CollationInfo {
@@ -9,4 +9,12 @@ polkadot_sdk::frame_benchmarking::define_benchmarks!(
   [cumulus_pallet_parachain_system, ParachainSystem]
   [cumulus_pallet_xcmp_queue, XcmpQueue]
   [cumulus_pallet_weight_reclaim, WeightReclaim]
+  [pallet_assets, Assets]
+  [pallet_assets, ForeignAssets]
+  [pallet_assets, PoolAssets]
+  [pallet_asset_conversion, AssetConversion]
+  [pallet_asset_conversion_tx_payment, AssetTxPayment]
+  [pallet_dex_router, DexRouter]
+  [pallet_farming, Farming]
+  [pallet_treasury, Treasury]
 );
@@ -1,15 +1,24 @@
 //! Asset-related pallet configurations for the parachain runtime.
 //!
 //! Configures:
-//! - `pallet-assets`: Fungible asset management
+//! - `pallet-assets`: Fungible asset management (local assets)
+//! - `pallet-assets` (`ForeignAssetsInstance`): Assets from other consensus systems, keyed by
+//!   XCM `Location`
 //! - `pallet-asset-conversion`: Uniswap V2-like DEX functionality
+//! - `pallet-asset-conversion-tx-payment`: Transaction fees payable in any pooled asset
 
 use codec::{Decode, DecodeWithMemTracking, Encode, MaxEncodedLen};
 use frame_support::traits::*;
+use pallet_asset_conversion::QuotePrice;
 use polkadot_sdk::*;
 use scale_info::TypeInfo;
+use serde::{Deserialize, Serialize};
+use sp_runtime::Either;
+// Pinned to an XCM version: locations are SCALE-encoded into `ForeignAssets` keys, pool ids and
+// router storage, and the encoding of `latest` changes whenever a new XCM version is released.
+use staging_xcm::v5::Location;
 
-use crate::{AccountId, Balance, Balances, Runtime, RuntimeEvent, EXISTENTIAL_DEPOSIT};
+use crate::{AccountId, Balance, Balances, DexRouter, Runtime, RuntimeEvent, EXISTENTIAL_DEPOSIT};
 
 /// Asset ID type used throughout the runtime
 pub type AssetId = u32;
@@ -18,9 +27,10 @@ pub type AssetId = u32;
 ///
 /// - Native: Parachain's native token (pallet-balances)
 /// - Local(u32): Local assets (pallet-assets)
+/// - Foreign(Location): Assets from other consensus systems (pallet-assets, foreign instance),
+///   identified by an XCM v5 `Location`
 #[derive(
   Clone,
-  Copy,
   Debug,
   Decode,
   DecodeWithMemTracking,
@@ -31,12 +41,16 @@ pub type AssetId = u32;
   PartialEq,
   PartialOrd,
   TypeInfo,
+  Serialize,
+  Deserialize,
 )]
 pub enum AssetKind {
   /// Native token managed by pallet-balances
   Native,
   /// Local asset managed by pallet-assets
   Local(u32),
+  /// Foreign asset managed by the foreign instance of pallet-assets, identified by its location
+  Foreign(Location),
 }
 
 impl From<u32> for AssetKind {
@@ -73,6 +87,34 @@ frame_support::parameter_types! {
   pub const PoolSetupFee: Balance = 0;
 }
 
+/// Instance of `pallet-assets` holding foreign assets
+pub type ForeignAssetsInstance = pallet_assets::Instance2;
+
+/// Instance of `pallet-assets` holding the LP tokens of asset conversion pools
+///
+/// Kept apart from local assets so LP token ids, which asset conversion numbers from zero, can
+/// never collide with local asset ids.
+pub type PoolAssetsInstance = pallet_assets::Instance3;
+
+/// Local and foreign assets, keyed by `AssetKind`
+pub type LocalAndForeignAssets = fungibles::UnionOf<
+  pallet_assets::Pallet<Runtime>,
+  pallet_assets::Pallet<Runtime, ForeignAssetsInstance>,
+  LocalOrForeignAssetIdConverter,
+  AssetKind,
+  AccountId,
+>;
+
+/// Native token, local and foreign assets unified behind a single `fungibles` interface keyed by
+/// `AssetKind`
+pub type NativeAndAssets = fungible::UnionOf<
+  Balances,
+  LocalAndForeignAssets,
+  NativeOrAssetIdConverter,
+  AssetKind,
+  AccountId,
+>;
+
 /// Ensure that the asset operations can only be performed by root or the asset owner
 pub type AssetsForceOrigin = frame_system::EnsureRoot<AccountId>;
 
@@ -91,7 +133,57 @@ impl pallet_assets::Config for Runtime {
   type StringLimit = StringLimit;
   type Freezer = ();
   type Extra = ();
-  type WeightInfo = ();
+  type WeightInfo = crate::weights::pallet_assets_local::WeightInfo<Runtime>;
+  type RemoveItemsLimit = ConstU32<1000>;
+  type AssetAccountDeposit = AssetAccountDeposit;
+  type CallbackHandle = ();
+  type Holder = ();
+  #[cfg(feature = "runtime-benchmarks")]
+  type BenchmarkHelper = ();
+}
+
+impl pallet_assets::Config<ForeignAssetsInstance> for Runtime {
+  type RuntimeEvent = RuntimeEvent;
+  type Balance = Balance;
+  type AssetId = Location;
+  type AssetIdParameter = Location;
+  type Currency = Balances;
+  // Foreign assets are registered through `ForceOrigin` only
+  type CreateOrigin = AsEnsureOriginWithArg<NeverEnsureOrigin<AccountId>>;
+  type ForceOrigin = AssetsForceOrigin;
+  type AssetDeposit = AssetDeposit;
+  type MetadataDepositBase = MetadataDepositBase;
+  type MetadataDepositPerByte = MetadataDepositPerByte;
+  type ApprovalDeposit = ApprovalDeposit;
+  type StringLimit = StringLimit;
+  type Freezer = ();
+  type Extra = ();
+  type WeightInfo = crate::weights::pallet_assets_foreign::WeightInfo<Runtime>;
+  type RemoveItemsLimit = ConstU32<1000>;
+  type AssetAccountDeposit = AssetAccountDeposit;
+  type CallbackHandle = ();
+  type Holder = ();
+  #[cfg(feature = "runtime-benchmarks")]
+  type BenchmarkHelper = ForeignAssetBenchmarkHelper;
+}
+
+impl pallet_assets::Config<PoolAssetsInstance> for Runtime {
+  type RuntimeEvent = RuntimeEvent;
+  type Balance = Balance;
+  type AssetId = AssetId;
+  type AssetIdParameter = AssetId;
+  type Currency = Balances;
+  // LP tokens are created by asset conversion only
+  type CreateOrigin = AsEnsureOriginWithArg<NeverEnsureOrigin<AccountId>>;
+  type ForceOrigin = AssetsForceOrigin;
+  type AssetDeposit = ConstU128<0>;
+  type MetadataDepositBase = ConstU128<0>;
+  type MetadataDepositPerByte = ConstU128<0>;
+  type ApprovalDeposit = ApprovalDeposit;
+  type StringLimit = StringLimit;
+  type Freezer = ();
+  type Extra = ();
+  type WeightInfo = crate::weights::pallet_assets_pool::WeightInfo<Runtime>;
   type RemoveItemsLimit = ConstU32<1000>;
   type AssetAccountDeposit = AssetAccountDeposit;
   type CallbackHandle = ();
@@ -105,13 +197,7 @@ impl pallet_asset_conversion::Config for Runtime {
   type Balance = Balance;
   type HigherPrecisionBalance = sp_core::U256;
   type AssetKind = AssetKind;
-  type Assets = frame_support::traits::fungible::UnionOf<
-    Balances,
-    pallet_assets::Pallet<Runtime>,
-    NativeOrAssetIdConverter,
-    AssetKind,
-    AccountId,
-  >;
+  type Assets = NativeAndAssets;
   type PoolId = (AssetKind, AssetKind);
   type PoolLocator = pallet_asset_conversion::WithFirstAsset<
     NativeAssetId,
@@ -120,7 +206,7 @@ impl pallet_asset_conversion::Config for Runtime {
     pallet_asset_conversion::AccountIdConverter<AssetConversionPalletId, (AssetKind, AssetKind)>,
   >;
   type PoolAssetId = u32;
-  type PoolAssets = pallet_assets::Pallet<Runtime>;
+  type PoolAssets = pallet_assets::Pallet<Runtime, PoolAssetsInstance>;
   type LPFee = ConstU32<3>;
   type PoolSetupFee = PoolSetupFee;
   type PoolSetupFeeAsset = NativeAssetId;
@@ -129,19 +215,125 @@ impl pallet_asset_conversion::Config for Runtime {
   type MintMinLiquidity = MintMinLiquidity;
   type MaxSwapPathLength = ConstU32<4>;
   type PalletId = AssetConversionPalletId;
-  type WeightInfo = ();
+  type WeightInfo = crate::weights::pallet_asset_conversion::WeightInfo<Runtime>;
+  #[cfg(feature = "runtime-benchmarks")]
+  type BenchmarkHelper = AssetKindBenchmarkHelper;
+}
+
+impl pallet_asset_conversion_tx_payment::Config for Runtime {
+  type RuntimeEvent = RuntimeEvent;
+  type AssetId = AssetKind;
+  /// Fees paid in a local asset are swapped to native through the DEX router at dispatch, so
+  /// pauses, circuit breakers and the router fee apply; unused fees are swapped back and refunded
+  /// in the paying asset. Native fees are burned, matching
+  /// `pallet_transaction_payment::FungibleAdapter<Balances, ()>`.
+  type OnChargeAssetTransaction = pallet_asset_conversion_tx_payment::SwapAssetAdapter<
+    NativeAssetId,
+    NativeAndAssets,
+    DexRouter,
+    (),
+  >;
+  type WeightInfo = crate::weights::pallet_asset_conversion_tx_payment::WeightInfo<Runtime>;
   #[cfg(feature = "runtime-benchmarks")]
   type BenchmarkHelper = AssetKindBenchmarkHelper;
 }
 
+/// Amount of `asset` needed to pay a fee of `native_fee`, at current pool prices and including
+/// the router fee
+///
+/// Returns `None` if the fee cannot be converted, e.g. because `asset` has no native pool or the
+/// router rejects the swap.
+pub fn native_fee_in_asset(asset: AssetKind, native_fee: Balance) -> Option<Balance> {
+  match asset {
+    AssetKind::Native => Some(native_fee),
+    asset => <DexRouter as QuotePrice>::quote_price_tokens_for_exact_tokens(
+      asset,
+      NativeAssetId::get(),
+      native_fee,
+      true,
+    ),
+  }
+}
+
 /// Benchmark helper for AssetKind
 #[cfg(feature = "runtime-benchmarks")]
 pub struct AssetKindBenchmarkHelper;
 
+/// Pools must contain the native asset, so every benchmark pair is native against a local asset
+///
+/// The order alternates with `seed1` so both pool orientations are exercised.
 #[cfg(feature = "runtime-benchmarks")]
 impl pallet_asset_conversion::BenchmarkHelper<AssetKind> for AssetKindBenchmarkHelper {
-  fn create_pair(asset1: u32, asset2: u32) -> (AssetKind, AssetKind) {
-    (AssetKind::Local(asset1), AssetKind::Local(asset2))
+  fn create_pair(seed1: u32, seed2: u32) -> (AssetKind, AssetKind) {
+    let asset = AssetKind::Local(seed2);
+    if seed1 % 2 == 0 {
+      (asset, AssetKind::Native)
+    } else {
+      (AssetKind::Native, asset)
+    }
+  }
+}
+
+/// Benchmark helper for foreign asset ids
+#[cfg(feature = "runtime-benchmarks")]
+pub struct ForeignAssetBenchmarkHelper;
+
+#[cfg(feature = "runtime-benchmarks")]
+impl pallet_assets::BenchmarkHelper<Location> for ForeignAssetBenchmarkHelper {
+  fn create_asset_id_parameter(id: u32) -> Location {
+    Location::new(1, [staging_xcm::v5::Junction::Parachain(id)])
+  }
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+impl pallet_asset_conversion_tx_payment::BenchmarkHelperTrait<AccountId, AssetKind, AssetKind>
+  for AssetKindBenchmarkHelper
+{
+  fn create_asset_id_parameter(seed: u32) -> (AssetKind, AssetKind) {
+    (AssetKind::Local(seed), AssetKind::Local(seed))
+  }
+
+  fn setup_balances_and_pool(asset_id: AssetKind, account: AccountId) {
+    use crate::{AssetConversion, Assets, RuntimeOrigin};
+    use frame_support::{assert_ok, traits::fungible::Mutate as _};
+    use sp_runtime::MultiAddress;
+
+    let AssetKind::Local(id) = asset_id else {
+      return;
+    };
+    assert_ok!(Assets::force_create(
+      RuntimeOrigin::root(),
+      id,
+      MultiAddress::Id(account.clone()),
+      true,
+      1,
+    ));
+
+    let lp_provider = account.clone();
+    assert_ok!(Balances::mint_into(&lp_provider, u64::MAX.into()));
+    assert_ok!(<Assets as fungibles::Mutate<_>>::mint_into(
+      id,
+      &lp_provider,
+      u64::MAX.into()
+    ));
+
+    let native = alloc::boxed::Box::new(AssetKind::Native);
+    let asset = alloc::boxed::Box::new(asset_id);
+    assert_ok!(AssetConversion::create_pool(
+      RuntimeOrigin::signed(lp_provider.clone()),
+      native.clone(),
+      asset.clone(),
+    ));
+    assert_ok!(AssetConversion::add_liquidity(
+      RuntimeOrigin::signed(lp_provider.clone()),
+      native,
+      asset,
+      (u32::MAX / 2).into(),
+      u32::MAX.into(),
+      1,
+      1,
+      lp_provider,
+    ));
   }
 }
 
@@ -150,16 +342,32 @@ frame_support::parameter_types! {
   pub const NativeAssetId: AssetKind = AssetKind::Native;
 }
 
-/// Converter to distinguish between native and asset tokens
+/// Converter to distinguish between the native token and other assets
 pub struct NativeOrAssetIdConverter;
 
-impl sp_runtime::traits::Convert<AssetKind, sp_runtime::Either<(), AssetId>>
-  for NativeOrAssetIdConverter
+impl sp_runtime::traits::Convert<AssetKind, Either<(), AssetKind>> for NativeOrAssetIdConverter {
+  fn convert(asset_kind: AssetKind) -> Either<(), AssetKind> {
+    match asset_kind {
+      AssetKind::Native => Either::Left(()),
+      asset_kind => Either::Right(asset_kind),
+    }
+  }
+}
+
+/// Converter to distinguish between local and foreign assets
+///
+/// `NativeOrAssetIdConverter` never passes the native token on; it maps to `Here`, where no
+/// foreign asset is registered.
+pub struct LocalOrForeignAssetIdConverter;
+
+impl sp_runtime::traits::Convert<AssetKind, Either<AssetId, Location>>
+  for LocalOrForeignAssetIdConverter
 {
-  fn convert(asset_kind: AssetKind) -> sp_runtime::Either<(), AssetId> {
+  fn convert(asset_kind: AssetKind) -> Either<AssetId, Location> {
     match asset_kind {
-      AssetKind::Native => sp_runtime::Either::Left(()),
-      AssetKind::Local(asset_id) => sp_runtime::Either::Right(asset_id),
+      AssetKind::Local(asset_id) => Either::Left(asset_id),
+      AssetKind::Foreign(location) => Either::Right(location),
+      AssetKind::Native => Either::Right(Location::here()),
     }
   }
 }
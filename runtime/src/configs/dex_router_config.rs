@@ -3,10 +3,16 @@
 //! Configures the trait-based DEX router with built-in fees.
 
 use polkadot_sdk::*;
+
+use frame_system::EnsureRoot;
 use sp_runtime::Permill;
 
-use crate::configs::assets_config::AssetKind;
-use crate::{AccountId, Balance, Runtime, RuntimeEvent};
+use crate::configs::{
+  assets_config::{AssetKind, NativeAndAssets},
+  treasury_config::{TreasuryAccount, TreasuryFeeShare},
+  xcm_config::{EnsureTrustedSibling, XcmCrossChainTransfer},
+};
+use crate::{AccountId, Balance, BlockNumber, Runtime, RuntimeCall, RuntimeEvent, MINUTES};
 
 frame_support::parameter_types! {
   /// Router fee percentage (0.2% = 20 basis points) for buyback mechanism
@@ -14,6 +20,21 @@ frame_support::parameter_types! {
 
   /// Account that receives router fees for buyback and burning
   pub const RouterFeeCollector: AccountId = AccountId::new([0u8; 32]);
+
+  /// Flash swap fee on top of the router fee (0.1%)
+  pub const FlashSwapFee: Permill = Permill::from_parts(1000);
+
+  /// Maximum price movement of a trade, and within the price window before a pair trips (20%)
+  pub const MaxPriceMovement: Permill = Permill::from_percent(20);
+
+  /// Window over which the reference price of a pair is kept
+  pub const PriceWindow: BlockNumber = 10 * MINUTES;
+
+  /// Maximum volume per pair and block, as a share of the input reserves (10%)
+  pub const MaxVolumePerBlock: Permill = Permill::from_percent(10);
+
+  /// Blocks after which a tripped pair is automatically re-enabled
+  pub const CircuitBreakerCooldown: BlockNumber = 30 * MINUTES;
 }
 
 impl pallet_dex_router::Config for Runtime {
@@ -22,7 +43,134 @@ impl pallet_dex_router::Config for Runtime {
   type AssetKind = AssetKind;
   type RouterFee = RouterFee;
   type RouterFeeCollector = RouterFeeCollector;
-  type WeightInfo = ();
+  type TreasuryAccount = TreasuryAccount;
+  type TreasuryFeeShare = TreasuryFeeShare;
+  type FlashSwapFee = FlashSwapFee;
+  type RuntimeCall = RuntimeCall;
+  type AdminOrigin = EnsureRoot<AccountId>;
+  type MaxPriceMovement = MaxPriceMovement;
+  type PriceWindow = PriceWindow;
+  type MaxVolumePerBlock = MaxVolumePerBlock;
+  type CircuitBreakerCooldown = CircuitBreakerCooldown;
+  type WeightInfo = pallet_dex_router::weights::SubstrateWeight<Runtime>;
   type AssetConversion = Runtime;
   type Balances = Runtime;
+  type Assets = NativeAndAssets;
+  type CrossChainTransfer = XcmCrossChainTransfer;
+  type RemoteOrigin = EnsureTrustedSibling;
+  #[cfg(feature = "runtime-benchmarks")]
+  type BenchmarkHelper = DexRouterBenchmarkHelper;
+}
+
+/// Benchmark helper creating a native pool for a local asset
+#[cfg(feature = "runtime-benchmarks")]
+pub struct DexRouterBenchmarkHelper;
+
+#[cfg(feature = "runtime-benchmarks")]
+impl pallet_dex_router::benchmarking::BenchmarkHelper<AccountId, AssetKind, Balance>
+  for DexRouterBenchmarkHelper
+{
+  fn setup_pair(caller: &AccountId) -> (AssetKind, AssetKind, Balance) {
+    const ASSET_ID: u32 = 1;
+    let liquidity = Self::create_pool(caller, ASSET_ID);
+
+    // Small enough to stay within the circuit breaker's volume and price limits
+    (
+      AssetKind::Native,
+      AssetKind::Local(ASSET_ID),
+      liquidity / 1_000,
+    )
+  }
+
+  fn setup_path(caller: &AccountId, len: u32) -> (alloc::vec::Vec<AssetKind>, Balance) {
+    // Pools always hold the native token, so the path alternates between local assets and it
+    const FIRST_ASSET_ID: u32 = 10;
+    let path: alloc::vec::Vec<_> = (0..len)
+      .map(|i| match i % 2 {
+        0 => AssetKind::Local(FIRST_ASSET_ID + i / 2),
+        _ => AssetKind::Native,
+      })
+      .collect();
+    let mut liquidity = 0;
+    for asset in &path {
+      if let AssetKind::Local(asset_id) = asset {
+        liquidity = Self::create_pool(caller, *asset_id);
+      }
+    }
+
+    (path, liquidity / 1_000)
+  }
+
+  fn setup_unpooled_pair(caller: &AccountId) -> (AssetKind, AssetKind, Balance) {
+    const ASSET_ID: u32 = 2;
+    let liquidity = Self::fund(caller, ASSET_ID);
+
+    (AssetKind::Native, AssetKind::Local(ASSET_ID), liquidity)
+  }
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+impl DexRouterBenchmarkHelper {
+  /// Create a native pool for local asset `asset_id` funded by `caller` and return the amount of
+  /// each side added; `caller` keeps as much again of both
+  pub(crate) fn create_pool(caller: &AccountId, asset_id: u32) -> Balance {
+    use crate::{AssetConversion, Balances, DexRouter, RuntimeOrigin, EXISTENTIAL_DEPOSIT};
+    use frame_support::{
+      assert_ok,
+      traits::fungible::{Inspect as _, Mutate as _},
+    };
+
+    let liquidity = Self::fund(caller, asset_id);
+    // The fee accounts are endowed at genesis to receive native router fees
+    for account in [RouterFeeCollector::get(), TreasuryAccount::get()] {
+      if Balances::balance(&account) < EXISTENTIAL_DEPOSIT {
+        assert_ok!(Balances::mint_into(&account, EXISTENTIAL_DEPOSIT));
+      }
+    }
+
+    let native = alloc::boxed::Box::new(AssetKind::Native);
+    let asset = alloc::boxed::Box::new(AssetKind::Local(asset_id));
+    assert_ok!(DexRouter::do_create_pool(caller, &native, &asset));
+    assert_ok!(AssetConversion::add_liquidity(
+      RuntimeOrigin::signed(caller.clone()),
+      native,
+      asset,
+      liquidity,
+      liquidity,
+      1,
+      1,
+      caller.clone(),
+    ));
+
+    liquidity
+  }
+
+  /// Create local asset `asset_id` and fund `caller` with twice the returned amount of it and
+  /// of the native token
+  fn fund(caller: &AccountId, asset_id: u32) -> Balance {
+    use crate::{Assets, Balances, RuntimeOrigin, EXISTENTIAL_DEPOSIT};
+    use frame_support::{
+      assert_ok,
+      traits::{fungible::Mutate as _, fungibles},
+    };
+    use sp_runtime::MultiAddress;
+
+    let liquidity: Balance = 1_000_000 * EXISTENTIAL_DEPOSIT;
+
+    assert_ok!(Assets::force_create(
+      RuntimeOrigin::root(),
+      asset_id,
+      MultiAddress::Id(caller.clone()),
+      true,
+      1,
+    ));
+    assert_ok!(Balances::mint_into(caller, 2 * liquidity));
+    assert_ok!(<Assets as fungibles::Mutate<_>>::mint_into(
+      asset_id,
+      caller,
+      2 * liquidity
+    ));
+
+    liquidity
+  }
 }
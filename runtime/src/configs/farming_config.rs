@@ -0,0 +1,74 @@
+//! Farming pallet configuration for the parachain runtime.
+//!
+//! Rewards LP token stakers of asset conversion pools, funded by anyone or from router fees.
+
+use polkadot_sdk::*;
+
+use frame_support::PalletId;
+use frame_system::EnsureRoot;
+
+use crate::configs::{
+  assets_config::{AssetKind, NativeAndAssets},
+  RouterFeeCollector,
+};
+use crate::{AccountId, Balance, PoolAssets, Runtime, RuntimeEvent};
+use frame_support::traits::Contains;
+
+frame_support::parameter_types! {
+  /// Derives the accounts holding the stakes and rewards of each program
+  pub const FarmingPalletId: PalletId = PalletId(*b"py/farms");
+}
+
+/// LP tokens of the asset conversion pools.
+///
+/// Iterates the pools, which only the admin-gated `create_program` does.
+pub struct PoolLpTokens;
+impl Contains<u32> for PoolLpTokens {
+  fn contains(lp_token: &u32) -> bool {
+    pallet_asset_conversion::Pools::<Runtime>::iter_values().any(|pool| pool.lp_token == *lp_token)
+  }
+}
+
+impl pallet_farming::Config for Runtime {
+  type RuntimeEvent = RuntimeEvent;
+  type Balance = Balance;
+  type PoolAssetId = u32;
+  type AssetId = AssetKind;
+  type PoolAssets = PoolAssets;
+  type LpTokens = PoolLpTokens;
+  type RewardAssets = NativeAndAssets;
+  type AdminOrigin = EnsureRoot<AccountId>;
+  type PalletId = FarmingPalletId;
+  type FeeSource = RouterFeeCollector;
+  type WeightInfo = pallet_farming::weights::SubstrateWeight<Runtime>;
+  #[cfg(feature = "runtime-benchmarks")]
+  type BenchmarkHelper = FarmingBenchmarkHelper;
+}
+
+/// Benchmark helper creating a native pool and rewarding in its local asset
+#[cfg(feature = "runtime-benchmarks")]
+pub struct FarmingBenchmarkHelper;
+
+#[cfg(feature = "runtime-benchmarks")]
+impl pallet_farming::benchmarking::BenchmarkHelper<AccountId, u32, AssetKind, Balance>
+  for FarmingBenchmarkHelper
+{
+  fn setup_assets(caller: &AccountId) -> (u32, AssetKind, Balance) {
+    use crate::configs::dex_router_config::DexRouterBenchmarkHelper;
+    use pallet_asset_conversion::PoolLocator;
+
+    const ASSET_ID: u32 = 1;
+    let liquidity = DexRouterBenchmarkHelper::create_pool(caller, ASSET_ID);
+    let pool_id = <Runtime as pallet_asset_conversion::Config>::PoolLocator::pool_id(
+      &AssetKind::Native,
+      &AssetKind::Local(ASSET_ID),
+    )
+    .expect("native pools are valid");
+    let lp_token = pallet_asset_conversion::Pools::<Runtime>::get(pool_id)
+      .expect("the pool was just created")
+      .lp_token;
+
+    // The caller holds almost `liquidity` LP tokens and `liquidity` of the local asset
+    (lp_token, AssetKind::Local(ASSET_ID), liquidity / 2)
+  }
+}
@@ -1,7 +1,9 @@
 mod assets_config;
 mod dex_router_config;
+mod farming_config;
+mod treasury_config;
 
-mod xcm_config;
+pub mod xcm_config;
 
 use polkadot_sdk::{staging_parachain_info as parachain_info, staging_xcm as xcm, *};
 #[cfg(not(feature = "runtime-benchmarks"))]
@@ -15,7 +17,8 @@ use frame_support::{
   dispatch::DispatchClass,
   parameter_types,
   traits::{
-    ConstBool, ConstU32, ConstU64, ConstU8, EitherOfDiverse, TransformOrigin, VariantCountOf,
+    ConstBool, ConstU32, ConstU64, ConstU8, Contains, EitherOfDiverse, TransformOrigin,
+    VariantCountOf,
   },
   weights::{ConstantMultiplier, Weight},
   PalletId,
@@ -43,7 +46,9 @@ use super::{
   WeightToFee, XcmpQueue, AVERAGE_ON_INITIALIZE_RATIO, EXISTENTIAL_DEPOSIT, HOURS,
   MAXIMUM_BLOCK_WEIGHT, MICRO_UNIT, NORMAL_DISPATCH_RATIO, SLOT_DURATION, VERSION,
 };
-pub use assets_config::{AssetId, AssetKind};
+pub use assets_config::{native_fee_in_asset, AssetId, AssetKind, PoolAssetsInstance};
+pub use dex_router_config::RouterFeeCollector;
+pub use treasury_config::{TreasuryAccount, TreasuryFeeShare};
 
 use xcm_config::{RelayLocation, XcmOriginToTransactDispatchOrigin};
 
@@ -77,6 +82,42 @@ parameter_types! {
   pub const SS58Prefix: u16 = 42;
 }
 
+/// Calls that users may dispatch directly.
+///
+/// `DexRouter` is the single public entry point for swaps: dispatching the `AssetConversion` swap
+/// calls directly would bypass the router fee, circuit breakers and pause state. Pools are created
+/// through `DexRouter` too, which makes sure the router fee can be collected in both assets.
+/// Liquidity management remains available, except while a flash swap waits to be repaid. Assets
+/// can only be transferred to the relay chain and the
+/// [`TrustedSiblings`](xcm_config::TrustedSiblings). Root is not subject to this filter.
+pub struct RuntimeCallFilter;
+impl Contains<RuntimeCall> for RuntimeCallFilter {
+  fn contains(call: &RuntimeCall) -> bool {
+    match call {
+      RuntimeCall::AssetConversion(
+        pallet_asset_conversion::Call::swap_exact_tokens_for_tokens { .. }
+        | pallet_asset_conversion::Call::swap_tokens_for_exact_tokens { .. }
+        | pallet_asset_conversion::Call::create_pool { .. },
+      ) => false,
+      // A pool that has lent a flash swap must not change until it is repaid
+      RuntimeCall::AssetConversion(
+        pallet_asset_conversion::Call::add_liquidity { .. }
+        | pallet_asset_conversion::Call::remove_liquidity { .. },
+      ) => !crate::DexRouter::flash_swap_in_progress(),
+      RuntimeCall::PolkadotXcm(
+        pallet_xcm::Call::transfer_assets { dest, .. }
+        | pallet_xcm::Call::transfer_assets_using_type_and_then { dest, .. }
+        | pallet_xcm::Call::reserve_transfer_assets { dest, .. }
+        | pallet_xcm::Call::limited_reserve_transfer_assets { dest, .. }
+        | pallet_xcm::Call::teleport_assets { dest, .. }
+        | pallet_xcm::Call::limited_teleport_assets { dest, .. },
+      ) => xcm::latest::Location::try_from(dest.as_ref().clone())
+        .is_ok_and(|dest| xcm_config::TransferDestinations::contains(&dest)),
+      _ => true,
+    }
+  }
+}
+
 /// The default types are being injected by [`derive_impl`](`frame_support::derive_impl`) from
 /// [`ParaChainDefaultConfig`](`struct@frame_system::config_preludes::ParaChainDefaultConfig`),
 /// but overridden as needed.
@@ -90,6 +131,8 @@ impl frame_system::Config for Runtime {
   type Hash = Hash;
   /// The block type.
   type Block = Block;
+  /// Only the DEX router may be used for swaps.
+  type BaseCallFilter = RuntimeCallFilter;
   /// Maximum number of block number to block hash mappings to keep (oldest pruned first).
   type BlockHashCount = BlockHashCount;
   /// Runtime version.
@@ -0,0 +1,178 @@
+//! Treasury pallet configuration for the parachain runtime.
+//!
+//! The treasury receives a share of the router fees and pays approved spends in the native token
+//! or in any asset it can buy through the router.
+
+use polkadot_sdk::*;
+
+use frame_support::{
+  traits::{
+    fungibles,
+    tokens::{ConversionFromAssetBalance, Fortitude, Pay, PaymentStatus, Preservation},
+  },
+  PalletId,
+};
+use frame_system::{EnsureRoot, EnsureRootWithSuccess};
+use sp_runtime::{
+  traits::{AccountIdConversion, IdentityLookup},
+  DispatchError, Permill,
+};
+
+use crate::configs::assets_config::{AssetKind, NativeAndAssets};
+use crate::{
+  AccountId, Balance, Balances, BlockNumber, DexRouter, Runtime, RuntimeEvent, System, DAYS, UNIT,
+};
+
+frame_support::parameter_types! {
+  /// Derives the treasury account
+  pub const TreasuryPalletId: PalletId = PalletId(*b"py/trsry");
+
+  /// Account holding the treasury funds
+  pub TreasuryAccount: AccountId = TreasuryPalletId::get().into_account_truncating();
+
+  /// Share of the router fees sent to the treasury (20%) until `DexRouter::set_fee_settings`
+  /// changes it
+  pub const TreasuryFeeShare: Permill = Permill::from_percent(20);
+
+  /// Period between spends of approved proposals
+  pub const SpendPeriod: BlockNumber = 6 * DAYS;
+
+  /// Share of unspent funds burned each spend period
+  pub const Burn: Permill = Permill::zero();
+
+  /// Period during which an approved spend can be paid out
+  pub const PayoutPeriod: BlockNumber = 30 * DAYS;
+
+  /// Largest spend, in native value, the spend origin may approve
+  pub const MaxTreasurySpend: Balance = 1_000_000 * UNIT;
+}
+
+impl pallet_treasury::Config for Runtime {
+  type PalletId = TreasuryPalletId;
+  type Currency = Balances;
+  type RejectOrigin = EnsureRoot<AccountId>;
+  type RuntimeEvent = RuntimeEvent;
+  type SpendPeriod = SpendPeriod;
+  type Burn = Burn;
+  type BurnDestination = ();
+  type SpendFunds = ();
+  type WeightInfo = pallet_treasury::weights::SubstrateWeight<Runtime>;
+  type MaxApprovals = frame_support::traits::ConstU32<100>;
+  type SpendOrigin = EnsureRootWithSuccess<AccountId, MaxTreasurySpend>;
+  type AssetKind = AssetKind;
+  type Beneficiary = AccountId;
+  type BeneficiaryLookup = IdentityLookup<AccountId>;
+  type Paymaster = PayThroughRouter;
+  type BalanceConverter = NativeValueThroughRouter;
+  type PayoutPeriod = PayoutPeriod;
+  type BlockNumberProvider = System;
+  #[cfg(feature = "runtime-benchmarks")]
+  type BenchmarkHelper = TreasuryBenchmarkHelper;
+}
+
+/// Pays treasury spends from the treasury account, buying assets it holds too little of with the
+/// native token through the router
+pub struct PayThroughRouter;
+
+impl Pay for PayThroughRouter {
+  type Balance = Balance;
+  type Beneficiary = AccountId;
+  type AssetKind = AssetKind;
+  type Id = ();
+  type Error = DispatchError;
+
+  fn pay(
+    who: &Self::Beneficiary,
+    asset_kind: Self::AssetKind,
+    amount: Self::Balance,
+  ) -> Result<Self::Id, Self::Error> {
+    use fungibles::{Inspect, Mutate};
+
+    let treasury = TreasuryAccount::get();
+    if asset_kind != AssetKind::Native {
+      let held = NativeAndAssets::reducible_balance(
+        asset_kind.clone(),
+        &treasury,
+        Preservation::Expendable,
+        Fortitude::Polite,
+      );
+      if held < amount {
+        let shortfall = amount - held;
+        let native_in = DexRouter::quote_tokens_for_exact_tokens(
+          AssetKind::Native,
+          asset_kind.clone(),
+          shortfall,
+        )
+        .ok_or(pallet_dex_router::Error::<Runtime>::NoLiquidityAvailable)?;
+        DexRouter::do_swap_exact_tokens_for_tokens(
+          &treasury,
+          AssetKind::Native,
+          asset_kind.clone(),
+          native_in,
+          shortfall,
+        )?;
+      }
+    }
+
+    NativeAndAssets::transfer(asset_kind, &treasury, who, amount, Preservation::Expendable)?;
+    Ok(())
+  }
+
+  fn check_payment(_id: Self::Id) -> PaymentStatus {
+    // Payments complete within `pay`
+    PaymentStatus::Success
+  }
+
+  #[cfg(feature = "runtime-benchmarks")]
+  fn ensure_successful(
+    _who: &Self::Beneficiary,
+    asset_kind: Self::AssetKind,
+    amount: Self::Balance,
+  ) {
+    use fungibles::Mutate;
+
+    frame_support::assert_ok!(NativeAndAssets::mint_into(
+      asset_kind,
+      &TreasuryAccount::get(),
+      amount
+    ));
+  }
+
+  #[cfg(feature = "runtime-benchmarks")]
+  fn ensure_concluded(_id: Self::Id) {}
+}
+
+/// Values treasury spends at the native cost of buying them through the router
+pub struct NativeValueThroughRouter;
+
+impl ConversionFromAssetBalance<Balance, AssetKind, Balance> for NativeValueThroughRouter {
+  type Error = DispatchError;
+
+  fn from_asset_balance(balance: Balance, asset_id: AssetKind) -> Result<Balance, Self::Error> {
+    if asset_id == AssetKind::Native {
+      return Ok(balance);
+    }
+    DexRouter::quote_tokens_for_exact_tokens(AssetKind::Native, asset_id, balance)
+      .ok_or_else(|| pallet_dex_router::Error::<Runtime>::NoLiquidityAvailable.into())
+  }
+
+  #[cfg(feature = "runtime-benchmarks")]
+  fn ensure_successful(_asset_id: AssetKind) {
+    // Benchmarks only spend the native token, which converts without a pool
+  }
+}
+
+/// Benchmark helper spending the native token
+#[cfg(feature = "runtime-benchmarks")]
+pub struct TreasuryBenchmarkHelper;
+
+#[cfg(feature = "runtime-benchmarks")]
+impl pallet_treasury::ArgumentsFactory<AssetKind, AccountId> for TreasuryBenchmarkHelper {
+  fn create_asset_kind(_seed: u32) -> AssetKind {
+    AssetKind::Native
+  }
+
+  fn create_beneficiary(seed: [u8; 32]) -> AccountId {
+    AccountId::new(seed)
+  }
+}
@@ -1,6 +1,15 @@
+use alloc::{boxed::Box, vec, vec::Vec};
+
+use super::{
+  assets_config::{
+    AssetId as LocalAssetId, AssetKind, ForeignAssetsInstance, NativeAndAssets, NativeAssetId,
+  },
+  RuntimeCallFilter,
+};
 use crate::{
-  AccountId, AllPalletsWithSystem, Balances, ParachainSystem, PolkadotXcm, Runtime, RuntimeCall,
-  RuntimeEvent, RuntimeOrigin, WeightToFee, XcmpQueue,
+  AccountId, AllPalletsWithSystem, Balance, Balances, CollatorSelection, DexRouter,
+  ParachainSystem, PolkadotXcm, Runtime, RuntimeCall, RuntimeEvent, RuntimeOrigin, WeightToFee,
+  XcmpQueue,
 };
 
 use polkadot_sdk::{
@@ -8,32 +17,51 @@ use polkadot_sdk::{
   staging_xcm_executor as xcm_executor, *,
 };
 
+use cumulus_primitives_core::{ChannelStatus, GetChannelInfo, ParaId};
+use cumulus_primitives_utility::SwapFirstAssetTrader;
 use frame_support::{
-  parameter_types,
-  traits::{ConstU32, Contains, Everything, Nothing},
+  ensure, parameter_types,
+  storage::{with_transaction, TransactionOutcome},
+  traits::{
+    fungibles,
+    tokens::{imbalance::ResolveAssetTo, Fortitude, Precision, Preservation},
+    ConstU32, Contains, ContainsPair, EnsureOrigin, Everything, Nothing, PalletInfoAccess,
+  },
   weights::Weight,
+  PalletId,
 };
 use frame_system::EnsureRoot;
-use pallet_xcm::XcmPassthrough;
+use pallet_xcm::{EnsureXcm, WeightInfo as _, XcmPassthrough};
 use polkadot_parachain_primitives::primitives::Sibling;
 use polkadot_runtime_common::impls::ToAuthor;
 use polkadot_sdk::{
   polkadot_sdk_frame::traits::Disabled,
   staging_xcm_builder::{DenyRecursively, DenyThenTry},
 };
-use xcm::latest::prelude::*;
+use sp_runtime::{
+  traits::{AccountIdConversion, MaybeEquivalence, TryConvertInto},
+  DispatchError, DispatchResult,
+};
+use xcm::{latest::prelude::*, VersionedAssets, VersionedLocation};
 use xcm_builder::{
   AccountId32Aliases, AllowExplicitUnpaidExecutionFrom, AllowTopLevelPaidExecutionFrom,
-  DenyReserveTransferToRelayChain, EnsureXcmOrigin, FixedWeightBounds, FrameTransactionalProcessor,
-  FungibleAdapter, IsConcrete, NativeAsset, ParentIsPreset, RelayChainAsNative,
+  AsPrefixedGeneralIndex, DenyReserveTransferToRelayChain, EnsureXcmOrigin, FixedWeightBounds,
+  FrameTransactionalProcessor, FungibleAdapter, FungiblesAdapter, IsConcrete,
+  MatchedConvertedConcreteId, NativeAsset, NoChecking, ParentIsPreset, RelayChainAsNative,
   SiblingParachainAsNative, SiblingParachainConvertsVia, SignedAccountId32AsNative,
-  SignedToAccountId32, SovereignSignedViaLocation, TakeWeightCredit, TrailingSetTopicAsId,
-  UsingComponents, WithComputedOrigin, WithUniqueTopic,
+  SignedToAccountId32, SovereignSignedViaLocation, StartsWith, TakeWeightCredit,
+  TrailingSetTopicAsId, UsingComponents, WithComputedOrigin, WithLatestLocationConverter,
+  WithUniqueTopic,
+};
+use xcm_executor::{
+  traits::{AssetExchange, ConvertLocation, JustTry},
+  AssetsInHolding, XcmExecutor,
 };
-use xcm_executor::XcmExecutor;
 
 parameter_types! {
   pub const RelayLocation: Location = Location::parent();
+  /// Location of the native token, relative to this chain
+  pub const HereLocation: Location = Location::here();
   pub const RelayNetwork: Option<NetworkId> = None;
   pub RelayChainOrigin: RuntimeOrigin = cumulus_pallet_xcm::Origin::Relay.into();
   // For the real deployment, it is recommended to set `RelayNetwork` according to the relay chain
@@ -53,12 +81,12 @@ pub type LocationToAccountId = (
   AccountId32Aliases<RelayNetwork, AccountId>,
 );
 
-/// Means for transacting assets on this chain.
-pub type LocalAssetTransactor = FungibleAdapter<
+/// Means for transacting the native token.
+pub type NativeAssetTransactor = FungibleAdapter<
   // Use this currency:
   Balances,
   // Use this currency when it is a fungible asset matching the given location or name:
-  IsConcrete<RelayLocation>,
+  IsConcrete<HereLocation>,
   // Do a simple punn to convert an AccountId32 Location into a native chain account ID:
   LocationToAccountId,
   // Our chain's account ID type (we can't get away without mentioning it explicitly):
@@ -67,6 +95,133 @@ pub type LocalAssetTransactor = FungibleAdapter<
   (),
 >;
 
+parameter_types! {
+  pub CheckingAccount: AccountId = PolkadotXcm::check_account();
+  /// Minimum balance of the relay token in `ForeignAssets`
+  pub const RelayTokenMinBalance: Balance = 10_000_000;
+}
+
+/// Locations of assets issued by other consensus systems and held in `ForeignAssets`.
+///
+/// This includes the relay token, which is never minted as native balance.
+pub struct ForeignAssetLocations;
+impl Contains<Location> for ForeignAssetLocations {
+  fn contains(location: &Location) -> bool {
+    location.parents > 0
+  }
+}
+
+/// Matches foreign assets by their location, which is also their `ForeignAssets` id.
+pub type ForeignAssetsConvertedConcreteId = MatchedConvertedConcreteId<
+  Location,
+  Balance,
+  ForeignAssetLocations,
+  WithLatestLocationConverter<Location>,
+  JustTry,
+>;
+
+/// Means for transacting foreign assets (sibling tokens, Asset Hub assets, ...).
+pub type ForeignFungiblesTransactor = FungiblesAdapter<
+  // Use this fungibles implementation:
+  pallet_assets::Pallet<Runtime, ForeignAssetsInstance>,
+  // Use this matcher to identify foreign assets by their location:
+  ForeignAssetsConvertedConcreteId,
+  // Convert an XCM Location into a local account id:
+  LocationToAccountId,
+  // Our chain's account ID type (we can't get away without mentioning it explicitly):
+  AccountId,
+  // We don't track any teleports of foreign assets.
+  NoChecking,
+  // The account for checking, unused without teleports.
+  CheckingAccount,
+>;
+
+/// Matches local assets by their `[PalletInstance(<Assets>), GeneralIndex(id)]` location.
+pub type LocalAssetsConvertedConcreteId = MatchedConvertedConcreteId<
+  LocalAssetId,
+  Balance,
+  StartsWith<LocalAssetsPalletLocation>,
+  AsPrefixedGeneralIndex<LocalAssetsPalletLocation, LocalAssetId, TryConvertInto>,
+  JustTry,
+>;
+
+/// Means for transacting local assets, for which this chain is the reserve.
+pub type LocalFungiblesTransactor = FungiblesAdapter<
+  // Use this fungibles implementation:
+  crate::Assets,
+  // Use this matcher to identify local assets by their location:
+  LocalAssetsConvertedConcreteId,
+  // Convert an XCM Location into a local account id:
+  LocationToAccountId,
+  // Our chain's account ID type (we can't get away without mentioning it explicitly):
+  AccountId,
+  // We don't track any teleports of local assets.
+  NoChecking,
+  // The account for checking, unused without teleports.
+  CheckingAccount,
+>;
+
+/// Means for transacting assets on this chain.
+pub type AssetTransactors = (
+  NativeAssetTransactor,
+  LocalFungiblesTransactor,
+  ForeignFungiblesTransactor,
+);
+
+parameter_types! {
+  /// Sibling parachains assets may be transferred to, Asset Hub to begin with
+  pub const TrustedSiblingParaIds: &'static [u32] = &[1000];
+}
+
+/// Sibling parachains trusted to receive assets from this chain.
+///
+/// Router transfers are limited to these destinations by [`XcmCrossChainTransfer`].
+pub struct TrustedSiblings;
+impl Contains<Location> for TrustedSiblings {
+  fn contains(location: &Location) -> bool {
+    matches!(
+      location.unpack(),
+      (1, [Parachain(id)]) if TrustedSiblingParaIds::get().contains(id)
+    )
+  }
+}
+
+/// Destinations of `pallet_xcm` transfers: the relay chain and the [`TrustedSiblings`].
+///
+/// Enforced by [`RuntimeCallFilter`](super::RuntimeCallFilter).
+pub struct TransferDestinations;
+impl Contains<Location> for TransferDestinations {
+  fn contains(location: &Location) -> bool {
+    *location == Location::parent() || TrustedSiblings::contains(location)
+  }
+}
+
+/// Assets reserve-transferred by a [`TrustedSiblings`] chain from under its own location.
+///
+/// Accepts Asset Hub assets such as USDT, `(1, [Parachain(1000), PalletInstance(50),
+/// GeneralIndex(1984)])`, which [`NativeAsset`] rejects as their id is not the sender's location.
+pub struct TrustedSiblingReserves;
+impl ContainsPair<Asset, Location> for TrustedSiblingReserves {
+  fn contains(asset: &Asset, origin: &Location) -> bool {
+    TrustedSiblings::contains(origin) && asset.id.0.starts_with(origin)
+  }
+}
+
+/// Reserve transfers allowed through `pallet_xcm`.
+///
+/// Local accounts may send any asset the router knows about: the native token and local assets
+/// are reserve-transferred from this chain, foreign assets through their own reserve. The
+/// destination is not part of this filter, it must be one of the [`TransferDestinations`].
+pub struct LocalReserveTransfers;
+impl Contains<(Location, Vec<Asset>)> for LocalReserveTransfers {
+  fn contains((origin, assets): &(Location, Vec<Asset>)) -> bool {
+    matches!(origin.unpack(), (0, [AccountId32 { .. }]))
+      && assets
+        .iter()
+        .all(|asset| AssetKindLocationConverter::convert(&asset.id.0).is_some())
+  }
+}
+
 /// This is the type we use to convert an (incoming) XCM origin into a local `Origin` instance,
 /// ready for dispatching a transaction with Xcm's `Transact`. There is an `OriginKind` which can
 /// biases the kind of local `Origin` it will become.
@@ -130,20 +285,306 @@ pub type Barrier = TrailingSetTopicAsId<
   >,
 >;
 
+parameter_types! {
+  /// Index of the local assets pallet, used in `[PalletInstance, GeneralIndex]` asset locations
+  pub AssetsPalletIndex: u8 = <crate::Assets as PalletInfoAccess>::index() as u8;
+  /// Location of the local assets pallet, the prefix of all local asset locations
+  pub LocalAssetsPalletLocation: Location = PalletInstance(AssetsPalletIndex::get()).into();
+  /// Account holding assets while an `ExchangeAsset` instruction is routed through the DEX router
+  pub DexExchangeAccount: AccountId = PalletId(*b"py/dxxcm").into_account_truncating();
+}
+
+/// Maps XCM asset locations (relative to this chain) to router `AssetKind`s and back.
+///
+/// - `Here` is the native token
+/// - `[PalletInstance(<Assets>), GeneralIndex(id)]` is the local asset `id`
+/// - Any location in `ForeignAssetLocations` is the foreign asset at that location
+pub struct AssetKindLocationConverter;
+impl MaybeEquivalence<Location, AssetKind> for AssetKindLocationConverter {
+  fn convert(location: &Location) -> Option<AssetKind> {
+    match location.unpack() {
+      (0, []) => Some(AssetKind::Native),
+      (0, [PalletInstance(instance), GeneralIndex(id)])
+        if *instance == AssetsPalletIndex::get() =>
+      {
+        Some(AssetKind::Local((*id).try_into().ok()?))
+      }
+      _ if ForeignAssetLocations::contains(location) => Some(AssetKind::Foreign(location.clone())),
+      _ => None,
+    }
+  }
+
+  fn convert_back(asset_kind: &AssetKind) -> Option<Location> {
+    match asset_kind {
+      AssetKind::Native => Some(Location::here()),
+      AssetKind::Local(id) => Some(Location::new(
+        0,
+        [
+          PalletInstance(AssetsPalletIndex::get()),
+          GeneralIndex((*id).into()),
+        ],
+      )),
+      AssetKind::Foreign(location) => Some(location.clone()),
+    }
+  }
+}
+
+/// Single fungible asset as a router `AssetKind` and amount
+fn fungible_asset_kind(asset: &Asset) -> Option<(AssetKind, Balance)> {
+  match asset.fun {
+    Fungible(amount) => Some((AssetKindLocationConverter::convert(&asset.id.0)?, amount)),
+    NonFungible(_) => None,
+  }
+}
+
+/// `ExchangeAsset` support backed by the DEX router.
+///
+/// Only exact-in exchanges (`maximal = true`) of a single fungible asset are supported. The
+/// given asset is minted into `DexExchangeAccount`, swapped through the router (so pause state,
+/// circuit breakers and router fees apply) and the output is burned back into holding.
+///
+/// `DexExchangeAccount` is endowed with the existential deposit at genesis, which keeps it alive
+/// to hold non-sufficient assets during the exchange. Exchanges fail until it exists.
+pub struct RouterAssetExchanger;
+impl AssetExchange for RouterAssetExchanger {
+  fn exchange_asset(
+    _origin: Option<&Location>,
+    give: AssetsInHolding,
+    want: &Assets,
+    maximal: bool,
+  ) -> Result<AssetsInHolding, AssetsInHolding> {
+    if !maximal || !give.non_fungible.is_empty() || want.len() != 1 {
+      return Err(give);
+    }
+    let give_assets: Vec<Asset> = give.fungible_assets_iter().collect();
+    let [give_asset] = give_assets.as_slice() else {
+      return Err(give);
+    };
+    let Some(want_asset) = want.get(0) else {
+      return Err(give);
+    };
+    let (Some((asset_in, amount_in)), Some((asset_out, amount_out_min))) = (
+      fungible_asset_kind(give_asset),
+      fungible_asset_kind(want_asset),
+    ) else {
+      return Err(give);
+    };
+
+    let result = with_transaction(|| {
+      let outcome = (|| -> Result<Balance, DispatchError> {
+        let account = DexExchangeAccount::get();
+        <NativeAndAssets as fungibles::Mutate<_>>::mint_into(
+          asset_in.clone(),
+          &account,
+          amount_in,
+        )?;
+        let amount_out = DexRouter::do_swap_exact_tokens_for_tokens(
+          &account,
+          asset_in,
+          asset_out.clone(),
+          amount_in,
+          amount_out_min,
+        )?;
+        <NativeAndAssets as fungibles::Mutate<_>>::burn_from(
+          asset_out,
+          &account,
+          amount_out,
+          Preservation::Expendable,
+          Precision::Exact,
+          Fortitude::Polite,
+        )?;
+        Ok(amount_out)
+      })();
+
+      match outcome {
+        Ok(amount_out) => TransactionOutcome::Commit(Ok(amount_out)),
+        Err(error) => TransactionOutcome::Rollback(Err(error)),
+      }
+    });
+
+    match result {
+      Ok(amount_out) => Ok(Asset::from((want_asset.id.clone(), amount_out)).into()),
+      Err(error) => {
+        log::debug!(
+          target: "xcm::exchange_asset",
+          "router exchange of {:?} for {:?} failed: {:?}",
+          give_asset,
+          want_asset,
+          error,
+        );
+        Err(give)
+      }
+    }
+  }
+
+  fn quote_exchange_price(give: &Assets, want: &Assets, maximal: bool) -> Option<Assets> {
+    // The router only supports exact-in swaps.
+    if !maximal || give.len() != 1 || want.len() != 1 {
+      return None;
+    }
+    let (asset_in, amount_in) = fungible_asset_kind(give.get(0)?)?;
+    let want_asset = want.get(0)?;
+    let (asset_out, _) = fungible_asset_kind(want_asset)?;
+    let amount_out = DexRouter::quote_exact_tokens_for_tokens(asset_in, asset_out, amount_in)?;
+    Some(Asset::from((want_asset.id.clone(), amount_out)).into())
+  }
+}
+
+/// Delivers router swap output to other chains through `pallet_xcm::transfer_assets`.
+///
+/// `pallet_xcm` picks the transfer type (local reserve, destination reserve or teleport) from
+/// the asset and destination. Execution on the destination is paid from the transferred asset.
+/// Only [`TrustedSiblings`] can be sent to.
+pub struct XcmCrossChainTransfer;
+impl pallet_dex_router::CrossChainTransfer<AccountId, AssetKind, Balance>
+  for XcmCrossChainTransfer
+{
+  type Location = VersionedLocation;
+
+  fn transfer(
+    who: &AccountId,
+    asset: AssetKind,
+    amount: Balance,
+    dest: VersionedLocation,
+    beneficiary: VersionedLocation,
+  ) -> DispatchResult {
+    let trusted =
+      Location::try_from(dest.clone()).is_ok_and(|dest| TrustedSiblings::contains(&dest));
+    ensure!(
+      trusted,
+      DispatchError::Other("destination is not a trusted sibling")
+    );
+    let location = AssetKindLocationConverter::convert_back(&asset)
+      .ok_or(DispatchError::Other("asset has no XCM location"))?;
+    let assets: Assets = Asset::from((location, amount)).into();
+    PolkadotXcm::transfer_assets(
+      RuntimeOrigin::signed(who.clone()),
+      Box::new(dest),
+      Box::new(beneficiary),
+      Box::new(VersionedAssets::from(assets)),
+      0,
+      Unlimited,
+    )
+  }
+
+  fn transfer_weight() -> Weight {
+    <Runtime as pallet_xcm::Config>::WeightInfo::transfer_assets()
+  }
+
+  fn delivery_fee(
+    asset: &AssetKind,
+    amount: Balance,
+    dest: &VersionedLocation,
+    beneficiary: &VersionedLocation,
+  ) -> Option<Balance> {
+    let dest = Location::try_from(dest.clone()).ok()?;
+    if !TrustedSiblings::contains(&dest) {
+      return None;
+    }
+    // Sends to siblings are only checked against the channel once delivered
+    if let (1, [Parachain(id)]) = dest.unpack() {
+      if matches!(
+        ParachainSystem::get_channel_status(ParaId::from(*id)),
+        ChannelStatus::Closed
+      ) {
+        return None;
+      }
+    }
+    let beneficiary = Location::try_from(beneficiary.clone()).ok()?;
+    let asset = Asset::from((AssetKindLocationConverter::convert_back(asset)?, amount))
+      .reanchored(&dest, &UniversalLocation::get())
+      .ok()?;
+    // Same shape and size as the program `transfer_assets` sends
+    let message = Xcm(vec![
+      ReserveAssetDeposited(asset.clone().into()),
+      ClearOrigin,
+      BuyExecution {
+        fees: asset,
+        weight_limit: Unlimited,
+      },
+      DepositAsset {
+        assets: Wild(AllCounted(1)),
+        beneficiary,
+      },
+    ]);
+    let (_, price) = validate_send::<XcmRouter>(dest, message).ok()?;
+    price
+      .inner()
+      .iter()
+      .try_fold(0, |total: Balance, fee| match (&fee.id, &fee.fun) {
+        (AssetId(location), Fungible(amount)) if *location == Location::here() => {
+          Some(total.saturating_add(*amount))
+        }
+        _ => None,
+      })
+  }
+}
+
+/// [`TrustedSiblings`] calling the router through XCM `Transact` with `OriginKind::Xcm`.
+///
+/// Remote swaps are paid from the sibling's sovereign account, and the output is sent back to
+/// the sibling. Other siblings are rejected before anything is swapped, since the output could
+/// not be delivered to them by [`XcmCrossChainTransfer`].
+pub struct EnsureTrustedSibling;
+impl EnsureOrigin<RuntimeOrigin> for EnsureTrustedSibling {
+  type Success = (AccountId, VersionedLocation);
+
+  fn try_origin(origin: RuntimeOrigin) -> Result<Self::Success, RuntimeOrigin> {
+    let location = EnsureXcm::<TrustedSiblings>::try_origin(origin)?;
+    match LocationToAccountId::convert_location(&location) {
+      Some(sovereign) => Ok((sovereign, location.into())),
+      None => Err(pallet_xcm::Origin::Xcm(location).into()),
+    }
+  }
+
+  #[cfg(feature = "runtime-benchmarks")]
+  fn try_successful_origin() -> Result<RuntimeOrigin, ()> {
+    let para_id = TrustedSiblingParaIds::get().first().ok_or(())?;
+    Ok(pallet_xcm::Origin::Xcm(Location::new(1, [Parachain(*para_id)])).into())
+  }
+}
+
+parameter_types! {
+  /// Collator reward pot, receiving XCM execution fees paid in pooled assets
+  pub StakingPot: AccountId = CollatorSelection::account_id();
+}
+
+/// Matches assets that can be routed through the DEX pools, by their location.
+pub type PooledAssetsConvertedConcreteId =
+  MatchedConvertedConcreteId<AssetKind, Balance, Everything, AssetKindLocationConverter, JustTry>;
+
+/// Buys execution weight with any asset that has a pool against the native token.
+///
+/// Just enough of the payment is swapped to native through the DEX router to cover the weight,
+/// so pauses, circuit breakers and the router fee apply; the rest stays in holding, and refunded
+/// weight is swapped back into the paying asset. Collected fees go to the collator pot.
+pub type PoolAssetTrader = SwapFirstAssetTrader<
+  NativeAssetId,
+  DexRouter,
+  WeightToFee,
+  NativeAndAssets,
+  PooledAssetsConvertedConcreteId,
+  ResolveAssetTo<StakingPot, NativeAndAssets>,
+  AccountId,
+>;
+
 pub struct XcmConfig;
 impl xcm_executor::Config for XcmConfig {
   type RuntimeCall = RuntimeCall;
   type XcmSender = XcmRouter;
   type XcmEventEmitter = PolkadotXcm;
   // How to withdraw and deposit an asset.
-  type AssetTransactor = LocalAssetTransactor;
+  type AssetTransactor = AssetTransactors;
   type OriginConverter = XcmOriginToTransactDispatchOrigin;
-  type IsReserve = NativeAsset;
+  type IsReserve = (NativeAsset, TrustedSiblingReserves);
   type IsTeleporter = (); // Teleporting is disabled.
   type UniversalLocation = UniversalLocation;
   type Barrier = Barrier;
   type Weigher = FixedWeightBounds<UnitWeightCost, RuntimeCall, MaxInstructions>;
-  type Trader = UsingComponents<WeightToFee, RelayLocation, AccountId, Balances, ToAuthor<Runtime>>;
+  type Trader = (
+    UsingComponents<WeightToFee, HereLocation, AccountId, Balances, ToAuthor<Runtime>>,
+    PoolAssetTrader,
+  );
   type ResponseHandler = PolkadotXcm;
   type AssetTrap = PolkadotXcm;
   type AssetClaims = PolkadotXcm;
@@ -151,12 +592,13 @@ impl xcm_executor::Config for XcmConfig {
   type PalletInstancesInfo = AllPalletsWithSystem;
   type MaxAssetsIntoHolding = MaxAssetsIntoHolding;
   type AssetLocker = ();
-  type AssetExchanger = ();
+  type AssetExchanger = RouterAssetExchanger;
   type FeeManager = ();
   type MessageExporter = ();
   type UniversalAliases = Nothing;
   type CallDispatcher = RuntimeCall;
-  type SafeCallFilter = Everything;
+  // `Transact` must not bypass the DEX router either.
+  type SafeCallFilter = RuntimeCallFilter;
   type Aliasers = Nothing;
   type TransactionalProcessor = FrameTransactionalProcessor;
   type HrmpNewChannelOpenRequestHandler = ();
@@ -188,7 +630,7 @@ impl pallet_xcm::Config for Runtime {
   // Needs to be `Everything` for local testing.
   type XcmExecutor = XcmExecutor<XcmConfig>;
   type XcmTeleportFilter = Everything;
-  type XcmReserveTransferFilter = Nothing;
+  type XcmReserveTransferFilter = LocalReserveTransfers;
   type Weigher = FixedWeightBounds<UnitWeightCost, RuntimeCall, MaxInstructions>;
   type UniversalLocation = UniversalLocation;
   type RuntimeOrigin = RuntimeOrigin;
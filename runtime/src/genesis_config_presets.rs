@@ -1,6 +1,11 @@
 use crate::{
-  AccountId, BalancesConfig, CollatorSelectionConfig, ParachainInfoConfig, PolkadotXcmConfig,
-  RuntimeGenesisConfig, SessionConfig, SessionKeys, SudoConfig, EXISTENTIAL_DEPOSIT,
+  configs::{
+    xcm_config::{CheckingAccount, DexExchangeAccount, RelayLocation, RelayTokenMinBalance},
+    AssetKind, RouterFeeCollector, TreasuryAccount,
+  },
+  AccountId, AssetsConfig, Balance, BalancesConfig, CollatorSelectionConfig, DexRouterConfig,
+  ForeignAssetsConfig, ParachainInfoConfig, PolkadotXcmConfig, RuntimeGenesisConfig, SessionConfig,
+  SessionKeys, SudoConfig, EXISTENTIAL_DEPOSIT, UNIT,
 };
 
 use alloc::{vec, vec::Vec};
@@ -20,6 +25,24 @@ const SAFE_XCM_VERSION: u32 = xcm::prelude::XCM_VERSION;
 #[docify::export_content]
 pub const PARACHAIN_ID: u32 = 1000;
 
+/// Development preset with local assets and seeded DEX pools.
+pub const DEX_DEV_PRESET: &str = "dex_dev";
+
+/// Native balance of every endowed account.
+const ENDOWMENT: Balance = 1 << 60;
+
+/// Local assets of the `dex_dev` preset as `(id, name, symbol, units per native unit)`.
+///
+/// Each is pooled against the native token at the given price.
+const DEX_DEV_ASSETS: [(u32, &str, &str, Balance); 2] =
+  [(1, "Dev Dollar", "DUSD", 1), (2, "Dev Token", "DTKN", 10)];
+
+/// Balance of every `dex_dev` asset held by each endowed account.
+const DEX_DEV_ASSET_ENDOWMENT: Balance = 1_000_000 * UNIT;
+
+/// Native side of every `dex_dev` pool.
+const DEX_DEV_POOL_NATIVE: Balance = 10_000 * UNIT;
+
 /// Generate the session keys from individual elements.
 ///
 /// The input must be a tuple of individual keys (a single arg for now since we have just one key).
@@ -27,18 +50,30 @@ pub fn template_session_keys(keys: AuraId) -> SessionKeys {
   SessionKeys { aura: keys }
 }
 
+/// Genesis config patch of a test network.
+///
+/// `dex_assets` are local assets, as in [`DEX_DEV_ASSETS`], created by `root` with metadata, held
+/// by every endowed account and pooled against the native token by `root`.
 fn testnet_genesis(
   invulnerables: Vec<(AccountId, AuraId)>,
   endowed_accounts: Vec<AccountId>,
   root: AccountId,
   id: ParaId,
+  dex_assets: &[(u32, &str, &str, Balance)],
 ) -> Value {
   build_struct_json_patch!(RuntimeGenesisConfig {
     balances: BalancesConfig {
+      // The router fee accounts must exist to receive native fees below the existential deposit,
+      // and the XCM exchange account to hold non-sufficient assets
       balances: endowed_accounts
         .iter()
         .cloned()
-        .map(|k| (k, 1u128 << 60))
+        .map(|k| (k, ENDOWMENT))
+        .chain([
+          (RouterFeeCollector::get(), EXISTENTIAL_DEPOSIT),
+          (TreasuryAccount::get(), EXISTENTIAL_DEPOSIT),
+          (DexExchangeAccount::get(), EXISTENTIAL_DEPOSIT),
+        ])
         .collect::<Vec<_>>(),
     },
     parachain_info: ParachainInfoConfig { parachain_id: id },
@@ -62,6 +97,52 @@ fn testnet_genesis(
         })
         .collect::<Vec<_>>(),
     },
+    foreign_assets: ForeignAssetsConfig {
+      // The relay token is owned by a keyless account, so it can only enter through XCM
+      assets: vec![(
+        RelayLocation::get(),
+        CheckingAccount::get(),
+        true,
+        RelayTokenMinBalance::get()
+      )],
+    },
+    assets: AssetsConfig {
+      assets: dex_assets
+        .iter()
+        .map(|(id, ..)| (*id, root.clone(), true, EXISTENTIAL_DEPOSIT))
+        .collect::<Vec<_>>(),
+      metadata: dex_assets
+        .iter()
+        .map(|(id, name, symbol, _)| (
+          *id,
+          name.as_bytes().to_vec(),
+          symbol.as_bytes().to_vec(),
+          12
+        ))
+        .collect::<Vec<_>>(),
+      accounts: endowed_accounts
+        .iter()
+        .flat_map(|who| {
+          dex_assets
+            .iter()
+            .map(move |(id, ..)| (*id, who.clone(), DEX_DEV_ASSET_ENDOWMENT))
+        })
+        .collect::<Vec<_>>(),
+    },
+    dex_router: DexRouterConfig {
+      pools: dex_assets
+        .iter()
+        .map(|(id, _, _, price)| {
+          (
+            AssetKind::Native,
+            AssetKind::Local(*id),
+            DEX_DEV_POOL_NATIVE,
+            DEX_DEV_POOL_NATIVE * price,
+            root.clone(),
+          )
+        })
+        .collect::<Vec<_>>(),
+    },
     polkadot_xcm: PolkadotXcmConfig {
       safe_xcm_version: Some(SAFE_XCM_VERSION)
     },
@@ -87,10 +168,21 @@ fn local_testnet_genesis() -> Value {
       .collect(),
     Sr25519Keyring::Alice.to_account_id(),
     PARACHAIN_ID.into(),
+    &[],
   )
 }
 
 fn development_config_genesis() -> Value {
+  dev_genesis(&[])
+}
+
+/// The development genesis with a tradable market: local assets with metadata held by every
+/// endowed account, and a pool of each against the native token seeded by Alice.
+fn dex_dev_genesis() -> Value {
+  dev_genesis(&DEX_DEV_ASSETS)
+}
+
+fn dev_genesis(dex_assets: &[(u32, &str, &str, Balance)]) -> Value {
   testnet_genesis(
     // initial collators.
     vec![
@@ -108,6 +200,7 @@ fn development_config_genesis() -> Value {
       .collect(),
     Sr25519Keyring::Alice.to_account_id(),
     PARACHAIN_ID.into(),
+    dex_assets,
   )
 }
 
@@ -116,6 +209,7 @@ pub fn get_preset(id: &PresetId) -> Option<vec::Vec<u8>> {
   let patch = match id.as_ref() {
     sp_genesis_builder::LOCAL_TESTNET_RUNTIME_PRESET => local_testnet_genesis(),
     sp_genesis_builder::DEV_RUNTIME_PRESET => development_config_genesis(),
+    DEX_DEV_PRESET => dex_dev_genesis(),
     _ => return None,
   };
   Some(
@@ -130,5 +224,6 @@ pub fn preset_names() -> Vec<PresetId> {
   vec![
     PresetId::from(sp_genesis_builder::DEV_RUNTIME_PRESET),
     PresetId::from(sp_genesis_builder::LOCAL_TESTNET_RUNTIME_PRESET),
+    PresetId::from(DEX_DEV_PRESET),
   ]
 }
@@ -11,6 +11,7 @@ pub mod apis;
 mod benchmarks;
 pub mod configs;
 mod genesis_config_presets;
+pub mod pool_assets_migration;
 #[cfg(test)]
 mod tests;
 mod weights;
@@ -85,7 +86,7 @@ pub type TxExtension = (
   frame_system::CheckEra<Runtime>,
   frame_system::CheckNonce<Runtime>,
   frame_system::CheckWeight<Runtime>,
-  pallet_transaction_payment::ChargeTransactionPayment<Runtime>,
+  pallet_asset_conversion_tx_payment::ChargeAssetTxPayment<Runtime>,
   frame_metadata_hash_extension::CheckMetadataHash<Runtime>,
 );
 
@@ -97,7 +98,10 @@ pub type UncheckedExtrinsic =
 ///
 /// This can be a tuple of types, each implementing `OnRuntimeUpgrade`.
 #[allow(unused_parens)]
-type Migrations = ();
+type Migrations = (
+  pallet_dex_router::migrations::v1::MigrateV0ToV1<Runtime>,
+  pool_assets_migration::MoveLpTokensToPoolAssets,
+);
 
 /// Handles converting a weight scalar to a fee value, based on the scale and granularity of the
 /// node's balance type.
@@ -159,10 +163,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
   spec_name: alloc::borrow::Cow::Borrowed("parachain-template-runtime"),
   impl_name: alloc::borrow::Cow::Borrowed("parachain-template-runtime"),
   authoring_version: 1,
-  spec_version: 1,
+  spec_version: 2,
   impl_version: 0,
   apis: apis::RUNTIME_API_VERSIONS,
-  transaction_version: 1,
+  transaction_version: 2,
   system_version: 1,
 };
 
@@ -259,9 +263,16 @@ construct_runtime!(
     Assets: pallet_assets = 12,
     AssetConversion: pallet_asset_conversion = 13,
     DexRouter: pallet_dex_router = 14,
+    ForeignAssets: pallet_assets::<Instance2> = 17,
+    PoolAssets: pallet_assets::<Instance3> = 18,
+    Farming: pallet_farming = 19,
 
     // Governance
     Sudo: pallet_sudo = 15,
+    Treasury: pallet_treasury = 25,
+
+    // Transaction fees payable in pooled assets
+    AssetTxPayment: pallet_asset_conversion_tx_payment = 16,
 
     // Collator support. The order of these 4 are important and shall not change.
     Authorship: pallet_authorship = 20,
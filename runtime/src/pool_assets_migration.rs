@@ -0,0 +1,103 @@
+//! Migration moving the LP tokens of asset conversion pools from `Assets` to `PoolAssets`.
+//!
+//! LP tokens used to live in the `Assets` instance next to local assets. Their details,
+//! metadata, accounts and approvals are moved under the same ids; deposits held for them stay
+//! with their owners, as both instances reserve them in the native token.
+//!
+//! The migration is gated on the state rather than on a storage version: `PoolAssets` is a new
+//! instance, so `Executive` initializes its storage version before any migration runs. An LP token
+//! is moved while its pool's id is an asset of `Assets` owned by the pool account, and not yet an
+//! asset of `PoolAssets`. Local assets that reuse the id of an LP token after the move are owned by
+//! someone else and left alone, which makes the migration idempotent.
+
+use crate::{configs::PoolAssetsInstance, Runtime};
+use alloc::vec::Vec;
+use frame_support::{traits::OnRuntimeUpgrade, weights::Weight};
+use pallet_asset_conversion::PoolLocator;
+use polkadot_sdk::*;
+#[cfg(feature = "try-runtime")]
+use {
+  codec::{Decode, Encode},
+  sp_runtime::TryRuntimeError,
+};
+
+type LocalAsset = pallet_assets::Asset<Runtime>;
+type LocalAccount = pallet_assets::Account<Runtime>;
+type LocalApprovals = pallet_assets::Approvals<Runtime>;
+type LocalMetadata = pallet_assets::Metadata<Runtime>;
+type PoolAsset = pallet_assets::Asset<Runtime, PoolAssetsInstance>;
+type PoolAccount = pallet_assets::Account<Runtime, PoolAssetsInstance>;
+type PoolApprovals = pallet_assets::Approvals<Runtime, PoolAssetsInstance>;
+type PoolMetadata = pallet_assets::Metadata<Runtime, PoolAssetsInstance>;
+
+type Pools = pallet_asset_conversion::Pools<Runtime>;
+type PoolIdLocator = <Runtime as pallet_asset_conversion::Config>::PoolLocator;
+
+/// The LP tokens that are still in `Assets`: the ids of pools whose asset in `Assets` is owned by
+/// the pool account and that have no asset in `PoolAssets` yet.
+fn lp_tokens_in_assets() -> impl Iterator<Item = u32> {
+  Pools::iter().filter_map(|(pool_id, pool)| {
+    let pool_account = PoolIdLocator::address(&pool_id).ok()?;
+    let in_assets = LocalAsset::get(pool.lp_token).is_some_and(|d| d.owner == pool_account);
+    (in_assets && !PoolAsset::contains_key(pool.lp_token)).then_some(pool.lp_token)
+  })
+}
+
+/// Moves the LP tokens of asset conversion pools from `Assets` to `PoolAssets`, on chains that
+/// had pools before `PoolAssets` was added. Does nothing once they are moved.
+pub struct MoveLpTokensToPoolAssets;
+
+impl OnRuntimeUpgrade for MoveLpTokensToPoolAssets {
+  fn on_runtime_upgrade() -> Weight {
+    // Every pool, its asset in `Assets` and in `PoolAssets`
+    let mut reads = Pools::iter_keys().count() as u64 * 3;
+    let mut writes = 0u64;
+
+    for lp_token in lp_tokens_in_assets().collect::<Vec<_>>() {
+      if let Some(details) = LocalAsset::take(lp_token) {
+        PoolAsset::insert(lp_token, details);
+        writes += 2;
+      }
+      if LocalMetadata::contains_key(lp_token) {
+        PoolMetadata::insert(lp_token, LocalMetadata::take(lp_token));
+        writes += 2;
+      }
+      for (who, account) in LocalAccount::drain_prefix(lp_token) {
+        PoolAccount::insert(lp_token, who, account);
+        writes += 2;
+      }
+      for ((owner, delegate), approval) in LocalApprovals::drain_prefix((lp_token,)) {
+        PoolApprovals::insert((lp_token, owner, delegate), approval);
+        writes += 2;
+      }
+      reads += 3;
+    }
+
+    <Runtime as frame_system::Config>::DbWeight::get().reads_writes(reads, writes)
+  }
+
+  #[cfg(feature = "try-runtime")]
+  fn pre_upgrade() -> Result<Vec<u8>, TryRuntimeError> {
+    let supplies: Vec<_> = lp_tokens_in_assets()
+      .filter_map(|lp_token| LocalAsset::get(lp_token).map(|d| (lp_token, d.supply)))
+      .collect();
+    Ok(supplies.encode())
+  }
+
+  #[cfg(feature = "try-runtime")]
+  fn post_upgrade(state: Vec<u8>) -> Result<(), TryRuntimeError> {
+    let supplies = Vec::<(u32, crate::Balance)>::decode(&mut &state[..])
+      .map_err(|_| TryRuntimeError::Other("cannot decode the LP token supplies"))?;
+    for (lp_token, supply) in supplies {
+      frame_support::ensure!(
+        !LocalAsset::contains_key(lp_token) && LocalAccount::iter_prefix(lp_token).next().is_none(),
+        TryRuntimeError::Other("LP tokens must be removed from `Assets`")
+      );
+      frame_support::ensure!(
+        PoolAsset::get(lp_token).map(|d| d.supply) == Some(supply),
+        TryRuntimeError::Other("LP tokens must keep their supply in `PoolAssets`")
+      );
+    }
+    Ok(())
+  }
+}
@@ -0,0 +1,355 @@
+//! Tests for paying transaction fees in pooled assets.
+//!
+//! These drive `ChargeAssetTxPayment` through validation and post-dispatch the way the executive
+//! does, for an account that holds no native tokens at all.
+
+use super::dex_router_integration_tests::{add_liquidity, alice, create_pool, dave, new_test_ext};
+use crate::{
+  apis::runtime_decl_for_asset_fee_payment_api::AssetFeePaymentApi,
+  configs::{native_fee_in_asset, AssetKind, RouterFeeCollector, TreasuryAccount},
+  AssetConversion, Assets, Balance, Balances, Block, DexRouter, Runtime, RuntimeCall, RuntimeEvent,
+  RuntimeOrigin, Signature, System, TransactionPayment, TxExtension, UncheckedExtrinsic,
+  EXISTENTIAL_DEPOSIT,
+};
+use codec::Encode;
+use polkadot_sdk::{
+  frame_metadata_hash_extension,
+  frame_support::{
+    assert_ok,
+    dispatch::{DispatchInfo, GetDispatchInfo, PostDispatchInfo},
+    weights::Weight,
+  },
+  frame_system, pallet_asset_conversion_tx_payment,
+  sp_core::sr25519,
+  sp_runtime::{
+    generic::Era,
+    traits::{DispatchTransaction, TransactionExtension},
+    transaction_validity::{InvalidTransaction, TransactionValidityError},
+    MultiAddress,
+  },
+};
+
+type ChargeAssetTxPayment = pallet_asset_conversion_tx_payment::ChargeAssetTxPayment<Runtime>;
+
+const FEE_ASSET: u32 = 10;
+const CALL_WEIGHT: Weight = Weight::from_parts(500_000_000, 0);
+const LEN: usize = 100;
+
+fn remark() -> RuntimeCall {
+  RuntimeCall::System(frame_system::Call::remark { remark: vec![] })
+}
+
+fn info() -> DispatchInfo {
+  DispatchInfo {
+    call_weight: CALL_WEIGHT,
+    ..Default::default()
+  }
+}
+
+/// Create a sufficient asset with a native pool and give dave some of it
+fn setup_fee_asset(liquidity: Balance) {
+  assert_ok!(Assets::force_create(
+    RuntimeOrigin::root(),
+    FEE_ASSET,
+    MultiAddress::Id(alice()),
+    true,
+    EXISTENTIAL_DEPOSIT,
+  ));
+  assert_ok!(Assets::mint(
+    RuntimeOrigin::signed(alice()),
+    FEE_ASSET,
+    MultiAddress::Id(alice()),
+    2 * liquidity,
+  ));
+  assert_ok!(Assets::mint(
+    RuntimeOrigin::signed(alice()),
+    FEE_ASSET,
+    MultiAddress::Id(dave()),
+    1_000 * EXISTENTIAL_DEPOSIT,
+  ));
+  assert_ok!(create_pool(AssetKind::Native, AssetKind::Local(FEE_ASSET)));
+  assert_ok!(add_liquidity(
+    RuntimeOrigin::signed(alice()),
+    AssetKind::Native,
+    AssetKind::Local(FEE_ASSET),
+    (liquidity, liquidity),
+    (1, 1),
+    &alice(),
+  ));
+}
+
+/// Test that an account without native tokens can pay fees in a pooled asset
+#[test]
+fn test_fee_paid_in_pooled_asset() {
+  new_test_ext().execute_with(|| {
+    setup_fee_asset(100_000 * EXISTENTIAL_DEPOSIT);
+    assert_eq!(Balances::free_balance(dave()), 0);
+    let balance_before = Assets::balance(FEE_ASSET, dave());
+
+    let native_fee = TransactionPayment::compute_fee(LEN as u32, &info(), 0);
+    let expected_fee = native_fee_in_asset(AssetKind::Local(FEE_ASSET), native_fee).unwrap();
+    assert!(expected_fee > 0);
+
+    let (pre, _) = ChargeAssetTxPayment::from(0, Some(AssetKind::Local(FEE_ASSET)))
+      .validate_and_prepare(RuntimeOrigin::signed(dave()), &remark(), &info(), LEN, 0)
+      .unwrap();
+    assert_eq!(
+      Assets::balance(FEE_ASSET, dave()),
+      balance_before - expected_fee
+    );
+
+    let mut post_info = PostDispatchInfo {
+      actual_weight: Some(CALL_WEIGHT),
+      pays_fee: Default::default(),
+    };
+    assert_ok!(ChargeAssetTxPayment::post_dispatch(
+      pre,
+      &info(),
+      &mut post_info,
+      LEN,
+      &Ok(()),
+    ));
+
+    assert_eq!(
+      Assets::balance(FEE_ASSET, dave()),
+      balance_before - expected_fee
+    );
+    assert_eq!(Balances::free_balance(dave()), 0);
+    assert!(System::events().iter().any(|record| matches!(
+      record.event,
+      RuntimeEvent::AssetTxPayment(pallet_asset_conversion_tx_payment::Event::AssetTxFeePaid {
+        asset_id: AssetKind::Local(FEE_ASSET),
+        ..
+      })
+    )));
+
+    // The swap is reported without a trader, and its router fee reaches the fee accounts
+    let router_fee = System::events()
+      .iter()
+      .find_map(|record| match record.event {
+        RuntimeEvent::DexRouter(pallet_dex_router::Event::SwapExecuted {
+          who: None,
+          asset_in: AssetKind::Local(FEE_ASSET),
+          asset_out: AssetKind::Native,
+          router_fee,
+          ..
+        }) => Some(router_fee),
+        _ => None,
+      })
+      .expect("the fee payment swapped through the router");
+    assert_eq!(
+      Assets::balance(FEE_ASSET, RouterFeeCollector::get())
+        + Assets::balance(FEE_ASSET, TreasuryAccount::get()),
+      router_fee
+    );
+  });
+}
+
+/// Test that fees cannot be paid in a pooled asset whose router fee the fee accounts cannot
+/// receive, like any other swap
+#[test]
+fn test_fee_payment_requires_collectable_router_fee() {
+  new_test_ext().execute_with(|| {
+    // A non-sufficient asset pooled around the router, so the fee accounts hold none of it
+    assert_ok!(Assets::force_create(
+      RuntimeOrigin::root(),
+      FEE_ASSET,
+      MultiAddress::Id(alice()),
+      false,
+      EXISTENTIAL_DEPOSIT,
+    ));
+    assert_ok!(Assets::mint(
+      RuntimeOrigin::signed(alice()),
+      FEE_ASSET,
+      MultiAddress::Id(alice()),
+      200_000 * EXISTENTIAL_DEPOSIT,
+    ));
+    assert_ok!(AssetConversion::create_pool(
+      RuntimeOrigin::signed(alice()),
+      Box::new(AssetKind::Native),
+      Box::new(AssetKind::Local(FEE_ASSET)),
+    ));
+    assert_ok!(add_liquidity(
+      RuntimeOrigin::signed(alice()),
+      AssetKind::Native,
+      AssetKind::Local(FEE_ASSET),
+      (100_000 * EXISTENTIAL_DEPOSIT, 100_000 * EXISTENTIAL_DEPOSIT),
+      (1, 1),
+      &alice(),
+    ));
+    let balance_before = Assets::balance(FEE_ASSET, alice());
+
+    let result = ChargeAssetTxPayment::from(0, Some(AssetKind::Local(FEE_ASSET)))
+      .validate_and_prepare(RuntimeOrigin::signed(alice()), &remark(), &info(), LEN, 0);
+    assert_eq!(
+      result.err(),
+      Some(TransactionValidityError::Invalid(
+        InvalidTransaction::Payment
+      ))
+    );
+    assert_eq!(Assets::balance(FEE_ASSET, alice()), balance_before);
+  });
+}
+
+/// Test that unused weight is refunded in the paying asset
+#[test]
+fn test_unused_weight_refunded_in_paying_asset() {
+  new_test_ext().execute_with(|| {
+    setup_fee_asset(100_000 * EXISTENTIAL_DEPOSIT);
+    let balance_before = Assets::balance(FEE_ASSET, dave());
+
+    let (pre, _) = ChargeAssetTxPayment::from(0, Some(AssetKind::Local(FEE_ASSET)))
+      .validate_and_prepare(RuntimeOrigin::signed(dave()), &remark(), &info(), LEN, 0)
+      .unwrap();
+    let balance_after_withdraw = Assets::balance(FEE_ASSET, dave());
+
+    // The call used only a fraction of its declared weight
+    let mut post_info = PostDispatchInfo {
+      actual_weight: Some(CALL_WEIGHT / 10),
+      pays_fee: Default::default(),
+    };
+    assert_ok!(ChargeAssetTxPayment::post_dispatch(
+      pre,
+      &info(),
+      &mut post_info,
+      LEN,
+      &Ok(()),
+    ));
+
+    let balance_after_refund = Assets::balance(FEE_ASSET, dave());
+    assert!(balance_after_refund > balance_after_withdraw);
+    assert!(balance_after_refund < balance_before);
+    assert_eq!(Balances::free_balance(dave()), 0);
+  });
+}
+
+/// Test that fees cannot be paid in an asset without a native pool
+#[test]
+fn test_fee_payment_requires_pool() {
+  new_test_ext().execute_with(|| {
+    assert_ok!(Assets::force_create(
+      RuntimeOrigin::root(),
+      FEE_ASSET,
+      MultiAddress::Id(alice()),
+      true,
+      EXISTENTIAL_DEPOSIT,
+    ));
+    assert_ok!(Assets::mint(
+      RuntimeOrigin::signed(alice()),
+      FEE_ASSET,
+      MultiAddress::Id(dave()),
+      1_000 * EXISTENTIAL_DEPOSIT,
+    ));
+    assert_eq!(
+      native_fee_in_asset(AssetKind::Local(FEE_ASSET), 1_000),
+      None
+    );
+
+    let result = ChargeAssetTxPayment::from(0, Some(AssetKind::Local(FEE_ASSET)))
+      .validate_and_prepare(RuntimeOrigin::signed(dave()), &remark(), &info(), LEN, 0);
+    assert_eq!(
+      result.err(),
+      Some(TransactionValidityError::Invalid(
+        InvalidTransaction::Payment
+      ))
+    );
+  });
+}
+
+/// Test that fees cannot be paid in a pooled asset while the router is paused
+#[test]
+fn test_fee_payment_respects_router_pause() {
+  new_test_ext().execute_with(|| {
+    setup_fee_asset(100_000 * EXISTENTIAL_DEPOSIT);
+    assert_ok!(DexRouter::set_router_paused(RuntimeOrigin::root(), true));
+    let balance_before = Assets::balance(FEE_ASSET, dave());
+    assert_eq!(
+      native_fee_in_asset(AssetKind::Local(FEE_ASSET), 1_000),
+      None
+    );
+
+    let result = ChargeAssetTxPayment::from(0, Some(AssetKind::Local(FEE_ASSET)))
+      .validate_and_prepare(RuntimeOrigin::signed(dave()), &remark(), &info(), LEN, 0);
+    assert_eq!(
+      result.err(),
+      Some(TransactionValidityError::Invalid(
+        InvalidTransaction::Payment
+      ))
+    );
+    assert_eq!(Assets::balance(FEE_ASSET, dave()), balance_before);
+  });
+}
+
+/// Test that the fee estimate of the runtime API is what the extension charges for an extrinsic
+#[test]
+fn test_fee_estimate_matches_charged_fee() {
+  new_test_ext().execute_with(|| {
+    setup_fee_asset(100_000 * EXISTENTIAL_DEPOSIT);
+    let asset = AssetKind::Local(FEE_ASSET);
+    let tx_ext: TxExtension = (
+      frame_system::CheckNonZeroSender::new(),
+      frame_system::CheckSpecVersion::new(),
+      frame_system::CheckTxVersion::new(),
+      frame_system::CheckGenesis::new(),
+      frame_system::CheckEra::from(Era::Immortal),
+      frame_system::CheckNonce::from(0),
+      frame_system::CheckWeight::new(),
+      ChargeAssetTxPayment::from(0, Some(asset.clone())),
+      frame_metadata_hash_extension::CheckMetadataHash::new(false),
+    );
+    // Fees are estimated without checking the signature
+    let uxt = UncheckedExtrinsic::new_signed(
+      remark(),
+      MultiAddress::Id(dave()),
+      Signature::Sr25519(sr25519::Signature::from_raw([0; 64])),
+      tx_ext,
+    );
+    let len = uxt.encoded_size();
+    let info = uxt.get_dispatch_info();
+
+    let estimate = <Runtime as AssetFeePaymentApi<Block, Balance, AssetKind>>::query_fee_in_asset(
+      uxt,
+      len as u32,
+      asset.clone(),
+    )
+    .unwrap();
+    assert!(estimate > 0);
+
+    let balance_before = Assets::balance(FEE_ASSET, dave());
+    let result = ChargeAssetTxPayment::from(0, Some(asset)).validate_and_prepare(
+      RuntimeOrigin::signed(dave()),
+      &remark(),
+      &info,
+      len,
+      0,
+    );
+    assert!(result.is_ok());
+    assert_eq!(
+      Assets::balance(FEE_ASSET, dave()),
+      balance_before - estimate
+    );
+  });
+}
+
+/// Test that native fee payment still works through the same extension
+#[test]
+fn test_fee_paid_in_native() {
+  new_test_ext().execute_with(|| {
+    let balance_before = Balances::free_balance(alice());
+    let native_fee = TransactionPayment::compute_fee(LEN as u32, &info(), 0);
+    assert_eq!(
+      native_fee_in_asset(AssetKind::Native, native_fee),
+      Some(native_fee)
+    );
+
+    let result = ChargeAssetTxPayment::from(0, None).validate_and_prepare(
+      RuntimeOrigin::signed(alice()),
+      &remark(),
+      &info(),
+      LEN,
+      0,
+    );
+    assert!(result.is_ok());
+    assert_eq!(Balances::free_balance(alice()), balance_before - native_fee);
+  });
+}
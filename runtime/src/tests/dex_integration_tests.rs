@@ -11,6 +11,7 @@ use crate::{
   AccountId, AssetConversion, Assets, Balance, Balances, Runtime, RuntimeEvent, RuntimeOrigin,
   System, EXISTENTIAL_DEPOSIT,
 };
+use polkadot_sdk::pallet_asset_conversion;
 use polkadot_sdk::{
   frame_support::{
     assert_noop, assert_ok,
@@ -21,9 +22,8 @@ use polkadot_sdk::{
     },
   },
   sp_io::TestExternalities,
-  sp_runtime::BuildStorage,
+  sp_runtime::{ArithmeticError, BuildStorage, TokenError},
 };
-use polkadot_sdk::{pallet_asset_conversion, pallet_assets};
 
 /// Initialize test externalities with a clean state
 fn new_test_ext() -> TestExternalities {
@@ -176,7 +176,7 @@ fn test_pool_creation_success() {
     // Create pool (native token must be first asset)
     assert_ok!(create_pool(
       RuntimeOrigin::signed(admin.clone()),
-      native_asset,
+      native_asset.clone(),
       AssetKind::Local(asset_id)
     ));
 
@@ -204,7 +204,7 @@ fn test_pool_creation_duplicate_fails() {
     // Create pool successfully (native token must be first asset)
     assert_ok!(create_pool(
       RuntimeOrigin::signed(admin.clone()),
-      native_asset,
+      native_asset.clone(),
       AssetKind::Local(asset_id)
     ));
 
@@ -212,7 +212,7 @@ fn test_pool_creation_duplicate_fails() {
     assert_noop!(
       create_pool(
         RuntimeOrigin::signed(admin.clone()),
-        native_asset,
+        native_asset.clone(),
         AssetKind::Local(asset_id)
       ),
       pallet_asset_conversion::Error::<Runtime>::PoolExists
@@ -236,20 +236,21 @@ fn test_liquidity_provision_success() {
       asset_id,
       &admin,
       &liquidity_provider,
-      liquidity_amount
+      liquidity_amount * 2
     ));
 
     // Create pool - this will charge pool setup fee from admin
     assert_ok!(create_pool(
       RuntimeOrigin::signed(admin.clone()),
-      native_asset,
+      native_asset.clone(),
       AssetKind::Local(asset_id)
     ));
 
     // Add liquidity - amounts must be sufficient for minimum liquidity requirements
+    let initial_native_balance = Balances::free_balance(&liquidity_provider);
     assert_ok!(add_liquidity(
       RuntimeOrigin::signed(liquidity_provider.clone()),
-      native_asset,
+      native_asset.clone(),
       AssetKind::Local(asset_id),
       (liquidity_amount, liquidity_amount),
       (EXISTENTIAL_DEPOSIT, EXISTENTIAL_DEPOSIT), // Proper minimum amounts
@@ -257,8 +258,11 @@ fn test_liquidity_provision_success() {
     ));
 
     // Verify liquidity was added by checking balances decreased
-    assert!(Assets::balance(asset_id, &liquidity_provider) < liquidity_amount);
-    assert!(Balances::free_balance(&liquidity_provider) < 10_000_000_000 * EXISTENTIAL_DEPOSIT);
+    assert_eq!(
+      Assets::balance(asset_id, &liquidity_provider),
+      liquidity_amount
+    );
+    assert!(Balances::free_balance(&liquidity_provider) < initial_native_balance);
 
     // Verify liquidity provision event was emitted
     let events = System::events();
@@ -294,7 +298,7 @@ fn test_liquidity_provision_insufficient_balance_fails() {
     // Create pool
     assert_ok!(create_pool(
       RuntimeOrigin::signed(admin.clone()),
-      native_asset,
+      native_asset.clone(),
       AssetKind::Local(asset1_id)
     ));
 
@@ -302,13 +306,13 @@ fn test_liquidity_provision_insufficient_balance_fails() {
     assert_noop!(
       add_liquidity(
         RuntimeOrigin::signed(liquidity_provider.clone()),
-        native_asset,
+        native_asset.clone(),
         AssetKind::Local(asset1_id),
         (liquidity_amount, liquidity_amount),
         (1, 1),
         &liquidity_provider
       ),
-      pallet_assets::Error::<Runtime>::BalanceLow
+      ArithmeticError::Underflow
     );
   });
 }
@@ -330,19 +334,19 @@ fn test_token_swap_success() {
       asset1_id,
       &admin,
       &liquidity_provider,
-      liquidity_amount
+      liquidity_amount * 2
     ));
     assert_ok!(mint_tokens(asset1_id, &admin, &trader, swap_amount));
 
     // Create pool and add liquidity
     assert_ok!(create_pool(
       RuntimeOrigin::signed(admin.clone()),
-      native_asset,
+      native_asset.clone(),
       AssetKind::Local(asset1_id)
     ));
     assert_ok!(add_liquidity(
       RuntimeOrigin::signed(liquidity_provider.clone()),
-      native_asset,
+      native_asset.clone(),
       AssetKind::Local(asset1_id),
       (liquidity_amount, liquidity_amount),
       (1, 1),
@@ -353,9 +357,9 @@ fn test_token_swap_success() {
     let initial_native_balance = Balances::free_balance(&trader);
     assert_ok!(swap_exact_tokens_for_tokens(
       RuntimeOrigin::signed(trader.clone()),
-      vec![AssetKind::Local(asset1_id), native_asset],
+      vec![AssetKind::Local(asset1_id), native_asset.clone()],
       swap_amount,
-      0,
+      1,
       &trader,
       false
     ));
@@ -367,7 +371,7 @@ fn test_token_swap_success() {
 
     // Verify reserves changed
     let reserves =
-      AssetConversion::get_reserves(native_asset, AssetKind::Local(asset1_id)).unwrap();
+      AssetConversion::get_reserves(native_asset.clone(), AssetKind::Local(asset1_id)).unwrap();
     assert!(reserves.0 < liquidity_amount); // Less native in pool
     assert_eq!(reserves.1, liquidity_amount + swap_amount); // More asset1 in pool
   });
@@ -390,36 +394,37 @@ fn test_token_swap_insufficient_liquidity_fails() {
       asset1_id,
       &admin,
       &liquidity_provider,
-      liquidity_amount
+      liquidity_amount * 2
     ));
     assert_ok!(mint_tokens(asset1_id, &admin, &trader, swap_amount));
 
     // Create pool and add liquidity
     assert_ok!(create_pool(
       RuntimeOrigin::signed(admin.clone()),
-      native_asset,
+      native_asset.clone(),
       AssetKind::Local(asset1_id)
     ));
     assert_ok!(add_liquidity(
       RuntimeOrigin::signed(liquidity_provider.clone()),
-      native_asset,
+      native_asset.clone(),
       AssetKind::Local(asset1_id),
       (liquidity_amount, liquidity_amount),
       (1, 1),
       &liquidity_provider
     ));
 
-    // Try to swap more than available liquidity - should fail
+    // Try to swap more than available liquidity - the pool cannot be drained
+    // below its existential deposit
     assert_noop!(
       swap_exact_tokens_for_tokens(
         RuntimeOrigin::signed(trader.clone()),
-        vec![AssetKind::Local(asset1_id), native_asset],
+        vec![AssetKind::Local(asset1_id), native_asset.clone()],
         swap_amount,
-        0,
+        1,
         &trader,
         false
       ),
-      pallet_asset_conversion::Error::<Runtime>::Overflow
+      TokenError::NotExpendable
     );
   });
 }
@@ -439,9 +444,9 @@ fn test_token_swap_invalid_path_fails() {
     assert_noop!(
       swap_exact_tokens_for_tokens(
         RuntimeOrigin::signed(trader.clone()),
-        vec![AssetKind::Local(asset1_id), native_asset],
+        vec![AssetKind::Local(asset1_id), native_asset.clone()],
         1_000 * EXISTENTIAL_DEPOSIT,
-        0,
+        1,
         &trader,
         false
       ),
@@ -465,20 +470,20 @@ fn test_liquidity_removal_success() {
       asset1_id,
       &admin,
       &liquidity_provider,
-      liquidity_amount
+      liquidity_amount * 2
     ));
 
     // Create pool
     assert_ok!(create_pool(
       RuntimeOrigin::signed(admin.clone()),
-      native_asset,
+      native_asset.clone(),
       AssetKind::Local(asset1_id)
     ));
 
     // Add liquidity
     assert_ok!(add_liquidity(
       RuntimeOrigin::signed(liquidity_provider.clone()),
-      native_asset,
+      native_asset.clone(),
       AssetKind::Local(asset1_id),
       (liquidity_amount, liquidity_amount),
       (1, 1),
@@ -493,7 +498,7 @@ fn test_liquidity_removal_success() {
     // depends on the pallet implementation details)
     let remove_result = AssetConversion::remove_liquidity(
       RuntimeOrigin::signed(liquidity_provider.clone()),
-      Box::new(native_asset),
+      Box::new(native_asset.clone()),
       Box::new(AssetKind::Local(asset1_id)),
       1_000 * EXISTENTIAL_DEPOSIT, // Small amount to remove
       0,
@@ -532,13 +537,13 @@ fn test_multi_hop_swap_success() {
       asset1_id,
       &admin,
       &liquidity_provider,
-      liquidity_amount
+      liquidity_amount * 2
     ));
     assert_ok!(mint_tokens(
       asset2_id,
       &admin,
       &liquidity_provider,
-      liquidity_amount
+      liquidity_amount * 2
     ));
 
     // Mint tokens for trader
@@ -547,19 +552,19 @@ fn test_multi_hop_swap_success() {
     // Create pools: native-asset1 and native-asset2
     assert_ok!(create_pool(
       RuntimeOrigin::signed(admin.clone()),
-      native_asset,
+      native_asset.clone(),
       AssetKind::Local(asset1_id)
     ));
     assert_ok!(create_pool(
       RuntimeOrigin::signed(admin.clone()),
-      native_asset,
+      native_asset.clone(),
       AssetKind::Local(asset2_id)
     ));
 
     // Add liquidity to both pools
     assert_ok!(add_liquidity(
       RuntimeOrigin::signed(liquidity_provider.clone()),
-      native_asset,
+      native_asset.clone(),
       AssetKind::Local(asset1_id),
       (liquidity_amount, liquidity_amount),
       (1, 1),
@@ -567,7 +572,7 @@ fn test_multi_hop_swap_success() {
     ));
     assert_ok!(add_liquidity(
       RuntimeOrigin::signed(liquidity_provider.clone()),
-      native_asset,
+      native_asset.clone(),
       AssetKind::Local(asset2_id),
       (liquidity_amount, liquidity_amount),
       (1, 1),
@@ -580,11 +585,11 @@ fn test_multi_hop_swap_success() {
       RuntimeOrigin::signed(trader.clone()),
       vec![
         AssetKind::Local(asset1_id),
-        native_asset,
+        native_asset.clone(),
         AssetKind::Local(asset2_id)
       ],
       swap_amount,
-      0,
+      1,
       &trader,
       false
     ));
@@ -620,21 +625,21 @@ fn test_native_token_integration() {
       asset_id,
       &admin,
       &liquidity_provider,
-      liquidity_amount
+      liquidity_amount * 2
     ));
     assert_ok!(mint_tokens(asset_id, &admin, &trader, swap_amount));
 
     // Create pool between native and custom asset
     assert_ok!(create_pool(
       RuntimeOrigin::signed(admin.clone()),
-      native_asset,
+      native_asset.clone(),
       AssetKind::Local(asset_id)
     ));
 
     // Add liquidity (native token balance should already exist from genesis)
     assert_ok!(add_liquidity(
       RuntimeOrigin::signed(liquidity_provider.clone()),
-      native_asset,
+      native_asset.clone(),
       AssetKind::Local(asset_id),
       (liquidity_amount, liquidity_amount),
       (1, 1),
@@ -642,7 +647,10 @@ fn test_native_token_integration() {
     ));
 
     // Verify liquidity was added
-    assert!(Assets::balance(asset_id, &liquidity_provider) < liquidity_amount);
+    assert_eq!(
+      Assets::balance(asset_id, &liquidity_provider),
+      liquidity_amount
+    );
 
     // Perform swap from custom asset to native token
     let initial_native_balance = Balances::free_balance(&trader);
@@ -650,9 +658,9 @@ fn test_native_token_integration() {
 
     assert_ok!(swap_exact_tokens_for_tokens(
       RuntimeOrigin::signed(trader.clone()),
-      vec![AssetKind::Local(asset_id), native_asset],
+      vec![AssetKind::Local(asset_id), native_asset.clone()],
       swap_amount,
-      0,
+      1,
       &trader,
       false
     ));
@@ -683,19 +691,19 @@ fn test_swap_with_minimum_output() {
       asset1_id,
       &admin,
       &liquidity_provider,
-      liquidity_amount
+      liquidity_amount * 2
     ));
     assert_ok!(mint_tokens(asset1_id, &admin, &trader, swap_amount));
 
     // Create pool and add liquidity
     assert_ok!(create_pool(
       RuntimeOrigin::signed(admin.clone()),
-      native_asset,
+      native_asset.clone(),
       AssetKind::Local(asset1_id)
     ));
     assert_ok!(add_liquidity(
       RuntimeOrigin::signed(liquidity_provider.clone()),
-      native_asset,
+      native_asset.clone(),
       AssetKind::Local(asset1_id),
       (liquidity_amount, liquidity_amount),
       (1, 1),
@@ -706,7 +714,7 @@ fn test_swap_with_minimum_output() {
     assert_noop!(
       swap_exact_tokens_for_tokens(
         RuntimeOrigin::signed(trader.clone()),
-        vec![AssetKind::Local(asset1_id), native_asset],
+        vec![AssetKind::Local(asset1_id), native_asset.clone()],
         swap_amount,
         swap_amount * 2, // Unreasonably high minimum output
         &trader,
@@ -727,7 +735,8 @@ fn test_native_local_asset_pair() {
     let native_asset = AssetKind::Native;
     let local_asset = AssetKind::Local(asset_id);
     let liquidity_amount = 100 * EXISTENTIAL_DEPOSIT; // Match reference counter test
-    let swap_amount = EXISTENTIAL_DEPOSIT / 10; // Minimal swap amount to avoid AMM ZeroAmount
+                                                      // Large enough that the output stays above the asset's min_balance
+    let swap_amount = 10 * EXISTENTIAL_DEPOSIT;
 
     // Setup: Create local asset
     assert_ok!(create_test_asset(asset_id, &admin, EXISTENTIAL_DEPOSIT));
@@ -742,8 +751,8 @@ fn test_native_local_asset_pair() {
     // Create Native-Local pool
     assert_ok!(create_pool(
       RuntimeOrigin::signed(admin.clone()),
-      native_asset,
-      local_asset
+      native_asset.clone(),
+      local_asset.clone()
     ));
 
     // Check account info before adding liquidity
@@ -769,8 +778,8 @@ fn test_native_local_asset_pair() {
     let safe_liquidity_amount = (liquidity_amount * 3) / 4;
     let add_liquidity_result = add_liquidity(
       RuntimeOrigin::signed(liquidity_provider.clone()),
-      native_asset,
-      local_asset,
+      native_asset.clone(),
+      local_asset.clone(),
       (safe_liquidity_amount, safe_liquidity_amount),
       (1, 1),
       &liquidity_provider,
@@ -794,9 +803,9 @@ fn test_native_local_asset_pair() {
 
     let swap_result = swap_exact_tokens_for_tokens(
       RuntimeOrigin::signed(trader.clone()),
-      vec![native_asset, local_asset],
+      vec![native_asset.clone(), local_asset.clone()],
       swap_amount,
-      0,
+      1,
       &trader,
       false,
     );
@@ -813,9 +822,9 @@ fn test_native_local_asset_pair() {
 
     assert_ok!(swap_exact_tokens_for_tokens(
       RuntimeOrigin::signed(trader.clone()),
-      vec![local_asset, native_asset],
+      vec![local_asset.clone(), native_asset.clone()],
       current_asset_balance / 2,
-      0,
+      1,
       &trader,
       false
     ));
@@ -837,7 +846,8 @@ fn test_local_local_asset_pair() {
     let local_asset1 = AssetKind::Local(asset_id_1);
     let local_asset2 = AssetKind::Local(asset_id_2);
     let liquidity_amount = 100 * EXISTENTIAL_DEPOSIT; // Conservative amount
-    let swap_amount = EXISTENTIAL_DEPOSIT; // Minimal swap amount to avoid ZeroAmount
+                                                      // Large enough that the output stays above the asset's min_balance
+    let swap_amount = 10 * EXISTENTIAL_DEPOSIT;
 
     // Setup: Create local assets
     assert_ok!(create_test_asset(asset_id_1, &admin, EXISTENTIAL_DEPOSIT));
@@ -864,12 +874,12 @@ fn test_local_local_asset_pair() {
     assert_ok!(create_pool(
       RuntimeOrigin::signed(admin.clone()),
       AssetKind::Native,
-      local_asset1
+      local_asset1.clone()
     ));
     assert_ok!(create_pool(
       RuntimeOrigin::signed(admin.clone()),
       AssetKind::Native,
-      local_asset2
+      local_asset2.clone()
     ));
 
     // Add liquidity to Native-Local pools (use 75% to avoid NotExpendable)
@@ -877,7 +887,7 @@ fn test_local_local_asset_pair() {
     assert_ok!(add_liquidity(
       RuntimeOrigin::signed(liquidity_provider.clone()),
       AssetKind::Native,
-      local_asset1,
+      local_asset1.clone(),
       (safe_liquidity, safe_liquidity),
       (1, 1),
       &liquidity_provider
@@ -885,7 +895,7 @@ fn test_local_local_asset_pair() {
     assert_ok!(add_liquidity(
       RuntimeOrigin::signed(liquidity_provider.clone()),
       AssetKind::Native,
-      local_asset2,
+      local_asset2.clone(),
       (safe_liquidity, safe_liquidity),
       (1, 1),
       &liquidity_provider
@@ -897,9 +907,13 @@ fn test_local_local_asset_pair() {
 
     assert_ok!(swap_exact_tokens_for_tokens(
       RuntimeOrigin::signed(trader.clone()),
-      vec![local_asset1, AssetKind::Native, local_asset2],
+      vec![
+        local_asset1.clone(),
+        AssetKind::Native,
+        local_asset2.clone()
+      ],
       swap_amount,
-      0,
+      1,
       &trader,
       false
     ));
@@ -914,9 +928,13 @@ fn test_local_local_asset_pair() {
 
     assert_ok!(swap_exact_tokens_for_tokens(
       RuntimeOrigin::signed(trader.clone()),
-      vec![local_asset2, AssetKind::Native, local_asset1],
+      vec![
+        local_asset2.clone(),
+        AssetKind::Native,
+        local_asset1.clone()
+      ],
       current_asset2_balance / 2,
-      0,
+      1,
       &trader,
       false
     ));
@@ -954,7 +972,7 @@ fn test_multiple_local_asset_combinations() {
         asset_id,
         &admin,
         &liquidity_provider,
-        liquidity_amount
+        liquidity_amount * 2
       ));
     }
 
@@ -964,41 +982,41 @@ fn test_multiple_local_asset_combinations() {
     // Create multiple pools: Native-Local1, Native-Local2, Native-Local3
     assert_ok!(create_pool(
       RuntimeOrigin::signed(admin.clone()),
-      native_asset,
-      local_asset1
+      native_asset.clone(),
+      local_asset1.clone()
     ));
     assert_ok!(create_pool(
       RuntimeOrigin::signed(admin.clone()),
-      native_asset,
-      local_asset2
+      native_asset.clone(),
+      local_asset2.clone()
     ));
     assert_ok!(create_pool(
       RuntimeOrigin::signed(admin.clone()),
-      native_asset,
-      local_asset3
+      native_asset.clone(),
+      local_asset3.clone()
     ));
 
     // Add liquidity to all pools
     assert_ok!(add_liquidity(
       RuntimeOrigin::signed(liquidity_provider.clone()),
-      native_asset,
-      local_asset1,
+      native_asset.clone(),
+      local_asset1.clone(),
       (liquidity_amount, liquidity_amount),
       (1, 1),
       &liquidity_provider
     ));
     assert_ok!(add_liquidity(
       RuntimeOrigin::signed(liquidity_provider.clone()),
-      native_asset,
-      local_asset2,
+      native_asset.clone(),
+      local_asset2.clone(),
       (liquidity_amount, liquidity_amount),
       (1, 1),
       &liquidity_provider
     ));
     assert_ok!(add_liquidity(
       RuntimeOrigin::signed(liquidity_provider.clone()),
-      native_asset,
-      local_asset3,
+      native_asset.clone(),
+      local_asset3.clone(),
       (liquidity_amount, liquidity_amount),
       (1, 1),
       &liquidity_provider
@@ -1010,9 +1028,13 @@ fn test_multiple_local_asset_combinations() {
 
     assert_ok!(swap_exact_tokens_for_tokens(
       RuntimeOrigin::signed(trader.clone()),
-      vec![local_asset1, native_asset, local_asset3],
+      vec![
+        local_asset1.clone(),
+        native_asset.clone(),
+        local_asset3.clone()
+      ],
       swap_amount,
-      0,
+      1,
       &trader,
       false
     ));
@@ -1056,8 +1078,8 @@ fn test_debug_liquidity_provision() {
     // Create Native-Local pool
     assert_ok!(create_pool(
       RuntimeOrigin::signed(admin.clone()),
-      native_asset,
-      local_asset
+      native_asset.clone(),
+      local_asset.clone()
     ));
 
     println!("Pool created successfully");
@@ -1065,8 +1087,8 @@ fn test_debug_liquidity_provision() {
     // Try to add liquidity with smaller amounts
     let result = add_liquidity(
       RuntimeOrigin::signed(liquidity_provider.clone()),
-      native_asset,
-      local_asset,
+      native_asset.clone(),
+      local_asset.clone(),
       (liquidity_amount, liquidity_amount),
       (1, 1),
       &liquidity_provider,
@@ -1116,8 +1138,8 @@ fn test_balance_requirements() {
     // Create pool - admin pays setup fee
     assert_ok!(create_pool(
       RuntimeOrigin::signed(admin.clone()),
-      native_asset,
-      local_asset
+      native_asset.clone(),
+      local_asset.clone()
     ));
 
     // Check that we have enough balance for the operation
@@ -1130,8 +1152,8 @@ fn test_balance_requirements() {
 
     let result = add_liquidity(
       RuntimeOrigin::signed(liquidity_provider.clone()),
-      native_asset,
-      local_asset,
+      native_asset.clone(),
+      local_asset.clone(),
       (liquidity_amount, liquidity_amount),
       min_amounts,
       &liquidity_provider,
@@ -1188,8 +1210,8 @@ fn test_account_reference_counters() {
     // Create Native-Local pool
     assert_ok!(create_pool(
       RuntimeOrigin::signed(admin.clone()),
-      native_asset,
-      local_asset
+      native_asset.clone(),
+      local_asset.clone()
     ));
 
     // Check account info after pool creation
@@ -1221,8 +1243,8 @@ fn test_account_reference_counters() {
     let safe_amount = liquidity_amount / 2; // Use half the amount
     let result = add_liquidity(
       RuntimeOrigin::signed(liquidity_provider.clone()),
-      native_asset,
-      local_asset,
+      native_asset.clone(),
+      local_asset.clone(),
       (safe_amount, safe_amount),
       (1, 1),
       &liquidity_provider,
@@ -5,36 +5,45 @@
 //! single entry point architecture, and buyback mechanism.
 
 use crate::{
-  configs::{AssetId, AssetKind},
-  AccountId, AssetConversion, Assets, Balance, Balances, DexRouter, Runtime, RuntimeEvent,
-  RuntimeOrigin, System, EXISTENTIAL_DEPOSIT,
+  configs::{
+    xcm_config::DexExchangeAccount, AssetId, AssetKind, RouterFeeCollector, RuntimeCallFilter,
+    TreasuryAccount, TreasuryFeeShare,
+  },
+  AccountId, AssetConversion, Assets, Balance, Balances, DexRouter, ForeignAssets, Runtime,
+  RuntimeCall, RuntimeEvent, RuntimeOrigin, System, EXISTENTIAL_DEPOSIT,
 };
 use polkadot_sdk::{
-  frame_support::{assert_noop, assert_ok, dispatch::DispatchResult},
+  frame_support::{
+    assert_noop, assert_ok,
+    dispatch::{DispatchResult, DispatchResultWithPostInfo, GetDispatchInfo},
+    traits::Contains,
+  },
+  frame_system, pallet_asset_conversion,
   sp_io::TestExternalities,
-  sp_runtime::{BoundedVec, BuildStorage, MultiAddress, Permill},
+  sp_runtime::{
+    traits::Dispatchable, BoundedVec, BuildStorage, DispatchError, MultiAddress, Permill,
+  },
+  staging_xcm::latest::{
+    Junction::{AccountId32, Parachain},
+    Location,
+  },
 };
 
 /// Initialize test externalities with a clean state
-fn new_test_ext() -> TestExternalities {
+pub(crate) fn new_test_ext() -> TestExternalities {
   let mut t = polkadot_sdk::frame_system::GenesisConfig::<Runtime>::default()
     .build_storage()
     .unwrap();
 
   polkadot_sdk::pallet_balances::GenesisConfig::<Runtime> {
     balances: vec![
-      (
-        AccountId::from([1u8; 32]),
-        1_000_000_000_000 * EXISTENTIAL_DEPOSIT,
-      ),
-      (
-        AccountId::from([2u8; 32]),
-        1_000_000_000_000 * EXISTENTIAL_DEPOSIT,
-      ),
-      (
-        AccountId::from([3u8; 32]),
-        1_000_000_000_000 * EXISTENTIAL_DEPOSIT,
-      ),
+      (alice(), 1_000_000_000_000 * EXISTENTIAL_DEPOSIT),
+      (bob(), 1_000_000_000_000 * EXISTENTIAL_DEPOSIT),
+      (charlie(), 1_000_000_000_000 * EXISTENTIAL_DEPOSIT),
+      // Endowed like at genesis, so they can receive native router fees and hold exchanged assets
+      (RouterFeeCollector::get(), EXISTENTIAL_DEPOSIT),
+      (TreasuryAccount::get(), EXISTENTIAL_DEPOSIT),
+      (DexExchangeAccount::get(), EXISTENTIAL_DEPOSIT),
     ],
     dev_accounts: None,
   }
@@ -47,16 +56,47 @@ fn new_test_ext() -> TestExternalities {
 }
 
 // Test accounts
-fn alice() -> AccountId {
+pub(crate) fn alice() -> AccountId {
   AccountId::from([1u8; 32])
 }
 
-fn bob() -> AccountId {
+pub(crate) fn bob() -> AccountId {
   AccountId::from([2u8; 32])
 }
 
+pub(crate) fn charlie() -> AccountId {
+  AccountId::from([3u8; 32])
+}
+
+/// Account without a native balance at genesis
+pub(crate) fn dave() -> AccountId {
+  AccountId::from([4u8; 32])
+}
+
+/// Local asset most tests pool against the native token
+pub(crate) const LOCAL_ASSET: AssetId = 10;
+
+pub(crate) fn local() -> AssetKind {
+  AssetKind::Local(LOCAL_ASSET)
+}
+
+/// Location of `who` on this chain
+pub(crate) fn account_location(who: AccountId) -> Location {
+  Location::new(
+    0,
+    [AccountId32 {
+      network: None,
+      id: who.into(),
+    }],
+  )
+}
+
 // Helper functions for DEX Router testing
-fn create_test_asset(asset_id: AssetId, admin: &AccountId, min_balance: Balance) -> DispatchResult {
+pub(crate) fn create_test_asset(
+  asset_id: AssetId,
+  admin: &AccountId,
+  min_balance: Balance,
+) -> DispatchResult {
   Assets::create(
     RuntimeOrigin::signed(admin.clone()),
     asset_id,
@@ -65,7 +105,7 @@ fn create_test_asset(asset_id: AssetId, admin: &AccountId, min_balance: Balance)
   )
 }
 
-fn mint_tokens(
+pub(crate) fn mint_tokens(
   asset_id: AssetId,
   admin: &AccountId,
   beneficiary: &AccountId,
@@ -79,15 +119,12 @@ fn mint_tokens(
   )
 }
 
-fn create_pool(asset1: AssetKind, asset2: AssetKind) -> DispatchResult {
-  AssetConversion::create_pool(
-    RuntimeOrigin::signed(alice()),
-    Box::new(asset1),
-    Box::new(asset2),
-  )
+/// Create a pool through the router, which touches the fee accounts for both assets
+pub(crate) fn create_pool(asset1: AssetKind, asset2: AssetKind) -> DispatchResult {
+  DexRouter::do_create_pool(&alice(), &asset1, &asset2)
 }
 
-fn add_liquidity(
+pub(crate) fn add_liquidity(
   origin: RuntimeOrigin,
   asset1: AssetKind,
   asset2: AssetKind,
@@ -107,6 +144,72 @@ fn add_liquidity(
   )
 }
 
+/// Create a local asset and a Native/Local pool seeded with equal liquidity by alice
+pub(crate) fn setup_native_pool(asset_id: AssetId, liquidity_amount: Balance) {
+  assert_ok!(create_test_asset(asset_id, &alice(), EXISTENTIAL_DEPOSIT));
+  assert_ok!(mint_tokens(
+    asset_id,
+    &alice(),
+    &alice(),
+    liquidity_amount * 2
+  ));
+  assert_ok!(create_pool(AssetKind::Native, AssetKind::Local(asset_id)));
+  assert_ok!(add_liquidity(
+    RuntimeOrigin::signed(alice()),
+    AssetKind::Native,
+    AssetKind::Local(asset_id),
+    (liquidity_amount, liquidity_amount),
+    (1, 1),
+    &alice(),
+  ));
+}
+
+/// Register a foreign asset and a Native/Foreign pool seeded with equal liquidity by alice
+pub(crate) fn setup_foreign_pool(location: Location, liquidity_amount: Balance) {
+  assert_ok!(ForeignAssets::force_create(
+    RuntimeOrigin::root(),
+    location.clone(),
+    MultiAddress::Id(alice()),
+    false,
+    EXISTENTIAL_DEPOSIT,
+  ));
+  assert_ok!(ForeignAssets::mint(
+    RuntimeOrigin::signed(alice()),
+    location.clone(),
+    MultiAddress::Id(alice()),
+    liquidity_amount * 2,
+  ));
+  assert_ok!(create_pool(
+    AssetKind::Native,
+    AssetKind::Foreign(location.clone())
+  ));
+  assert_ok!(add_liquidity(
+    RuntimeOrigin::signed(alice()),
+    AssetKind::Native,
+    AssetKind::Foreign(location),
+    (liquidity_amount, liquidity_amount),
+    (1, 1),
+    &alice(),
+  ));
+}
+
+/// Swap through the router with a permissive minimum output
+pub(crate) fn router_swap(
+  who: &AccountId,
+  asset_in: AssetKind,
+  asset_out: AssetKind,
+  amount: Balance,
+) -> DispatchResultWithPostInfo {
+  DexRouter::swap_exact_tokens_for_tokens(
+    RuntimeOrigin::signed(who.clone()),
+    BoundedVec::try_from(vec![asset_in, asset_out]).unwrap(),
+    amount,
+    1,
+    who.clone(),
+    false,
+  )
+}
+
 /// Test DEX Router basic integration with AssetConversion
 #[test]
 fn test_dex_router_basic_integration() {
@@ -128,11 +231,11 @@ fn test_dex_router_basic_integration() {
     ));
 
     // Create pool and add liquidity
-    assert_ok!(create_pool(native_asset, local_asset));
+    assert_ok!(create_pool(native_asset.clone(), local_asset.clone()));
     assert_ok!(add_liquidity(
       RuntimeOrigin::signed(alice()),
-      native_asset,
-      local_asset,
+      native_asset.clone(),
+      local_asset.clone(),
       (liquidity_amount, liquidity_amount),
       (1, 1),
       &alice(),
@@ -142,7 +245,7 @@ fn test_dex_router_basic_integration() {
     let initial_native_balance = Balances::free_balance(bob());
     let initial_asset_balance = Assets::balance(asset_id, bob());
 
-    let path = BoundedVec::try_from(vec![native_asset, local_asset]).unwrap();
+    let path = BoundedVec::try_from(vec![native_asset.clone(), local_asset.clone()]).unwrap();
 
     assert_ok!(DexRouter::swap_exact_tokens_for_tokens(
       RuntimeOrigin::signed(bob()),
@@ -171,8 +274,9 @@ fn test_dual_fee_mechanism() {
     let asset_id = 1u32;
     let native_asset = AssetKind::Native;
     let local_asset = AssetKind::Local(asset_id);
-    let liquidity_amount = 100_000 * EXISTENTIAL_DEPOSIT;
     let swap_amount = 1_000_000 * EXISTENTIAL_DEPOSIT; // 1M tokens for clear fee calculation
+                                                       // Deep enough for the swap to stay within the circuit breaker limits
+    let liquidity_amount = 100 * swap_amount;
 
     assert_ok!(create_test_asset(asset_id, &alice(), EXISTENTIAL_DEPOSIT));
     assert_ok!(mint_tokens(
@@ -182,11 +286,11 @@ fn test_dual_fee_mechanism() {
       liquidity_amount * 2
     ));
 
-    assert_ok!(create_pool(native_asset, local_asset));
+    assert_ok!(create_pool(native_asset.clone(), local_asset.clone()));
     assert_ok!(add_liquidity(
       RuntimeOrigin::signed(alice()),
-      native_asset,
-      local_asset,
+      native_asset.clone(),
+      local_asset.clone(),
       (liquidity_amount, liquidity_amount),
       (1, 1),
       &alice(),
@@ -208,7 +312,7 @@ fn test_dual_fee_mechanism() {
     assert_eq!(expected_total_fee, 5_000 * EXISTENTIAL_DEPOSIT);
 
     // Execute swap
-    let path = BoundedVec::try_from(vec![native_asset, local_asset]).unwrap();
+    let path = BoundedVec::try_from(vec![native_asset.clone(), local_asset.clone()]).unwrap();
 
     assert_ok!(DexRouter::swap_exact_tokens_for_tokens(
       RuntimeOrigin::signed(bob()),
@@ -227,7 +331,7 @@ fn test_dual_fee_mechanism() {
   });
 }
 
-/// Test multi-hop swaps through DEX Router (currently limited to direct swaps)
+/// Test multi-hop swaps through DEX Router, one pool hop per pair of adjacent assets
 #[test]
 fn test_multi_hop_swap_integration() {
   new_test_ext().execute_with(|| {
@@ -238,7 +342,7 @@ fn test_multi_hop_swap_integration() {
     let local_asset1 = AssetKind::Local(asset1_id);
     let local_asset2 = AssetKind::Local(asset2_id);
     let liquidity_amount = 100_000 * EXISTENTIAL_DEPOSIT;
-    let swap_amount = 10_000 * EXISTENTIAL_DEPOSIT;
+    let swap_amount = 1_000 * EXISTENTIAL_DEPOSIT;
 
     // Create assets
     assert_ok!(create_test_asset(asset1_id, &alice(), EXISTENTIAL_DEPOSIT));
@@ -255,25 +359,51 @@ fn test_multi_hop_swap_integration() {
       &alice(),
       liquidity_amount * 2
     ));
+    assert_ok!(mint_tokens(asset1_id, &alice(), &bob(), swap_amount * 2));
+
+    // Create pools (pools always hold the native token)
+    for local_asset in [local_asset1.clone(), local_asset2.clone()] {
+      assert_ok!(create_pool(native_asset.clone(), local_asset.clone()));
+      assert_ok!(add_liquidity(
+        RuntimeOrigin::signed(alice()),
+        native_asset.clone(),
+        local_asset,
+        (liquidity_amount, liquidity_amount),
+        (1, 1),
+        &alice(),
+      ));
+    }
 
-    // Create pools (only native pairs to avoid conflicts)
-    assert_ok!(create_pool(native_asset, local_asset1));
-    assert_ok!(add_liquidity(
-      RuntimeOrigin::signed(alice()),
-      native_asset,
-      local_asset1,
-      (liquidity_amount, liquidity_amount),
-      (1, 1),
-      &alice(),
+    // Test: Asset1 -> Native -> Asset2 swaps through both pools
+    let multi_hop_path = BoundedVec::try_from(vec![
+      local_asset1.clone(),
+      native_asset.clone(),
+      local_asset2.clone(),
+    ])
+    .unwrap();
+    assert_ok!(DexRouter::swap_exact_tokens_for_tokens(
+      RuntimeOrigin::signed(bob()),
+      multi_hop_path,
+      swap_amount,
+      1,
+      bob(),
+      false,
     ));
 
-    // Test: Multi-hop path should fail with InvalidPath (path length > 2)
-    let multi_hop_path =
-      BoundedVec::try_from(vec![native_asset, local_asset1, local_asset2]).unwrap();
+    assert!(Assets::balance(asset1_id, bob()) < swap_amount * 2);
+    assert!(Assets::balance(asset2_id, bob()) > 0);
+
+    // Test: A path swapping an asset for itself is rejected
+    let repeating_path = BoundedVec::try_from(vec![
+      local_asset1.clone(),
+      local_asset1.clone(),
+      native_asset.clone(),
+    ])
+    .unwrap();
     assert_noop!(
       DexRouter::swap_exact_tokens_for_tokens(
         RuntimeOrigin::signed(bob()),
-        multi_hop_path,
+        repeating_path,
         swap_amount,
         1,
         bob(),
@@ -281,35 +411,16 @@ fn test_multi_hop_swap_integration() {
       ),
       pallet_dex_router::Error::<Runtime>::InvalidPath
     );
-
-    // Test: Direct swap works correctly
-    let direct_path = BoundedVec::try_from(vec![native_asset, local_asset1]).unwrap();
-    assert_ok!(DexRouter::swap_exact_tokens_for_tokens(
-      RuntimeOrigin::signed(bob()),
-      direct_path,
-      swap_amount,
-      1,
-      bob(),
-      false,
-    ));
-
-    // Verify direct swap occurred
-    let asset1_balance = Assets::balance(asset1_id, bob());
-    assert!(asset1_balance > 0);
   });
 }
 
-/// Test that AssetConversion is not directly accessible in runtime
+/// Test that AssetConversion swaps are not directly accessible in runtime
 #[test]
 fn test_asset_conversion_access_control() {
   new_test_ext().execute_with(|| {
-    // This test verifies that AssetConversion pallet is configured
-    // but should not be directly accessible to users in a production runtime
-
-    // In a production runtime, AssetConversion should not be in construct_runtime!
-    // or should have restricted access controls
-
-    // For now, we test that DexRouter is the intended entry point
+    // AssetConversion stays in construct_runtime! for pool and liquidity management,
+    // but its swap calls are rejected by the runtime's BaseCallFilter so that users
+    // cannot bypass the router fee. DexRouter is the only public swap entry point.
     let asset_id = 1u32;
     let native_asset = AssetKind::Native;
     let local_asset = AssetKind::Local(asset_id);
@@ -322,18 +433,18 @@ fn test_asset_conversion_access_control() {
       &alice(),
       2_000_000 * EXISTENTIAL_DEPOSIT
     ));
-    assert_ok!(create_pool(native_asset, local_asset));
+    assert_ok!(create_pool(native_asset.clone(), local_asset.clone()));
     assert_ok!(add_liquidity(
       RuntimeOrigin::signed(alice()),
-      native_asset,
-      local_asset,
+      native_asset.clone(),
+      local_asset.clone(),
       (100_000 * EXISTENTIAL_DEPOSIT, 100_000 * EXISTENTIAL_DEPOSIT),
       (1, 1),
       &alice(),
     ));
 
     // DexRouter should work (intended entry point)
-    let path = BoundedVec::try_from(vec![native_asset, local_asset]).unwrap();
+    let path = BoundedVec::try_from(vec![native_asset.clone(), local_asset.clone()]).unwrap();
 
     // This should work - DexRouter is the public interface
     assert_ok!(DexRouter::swap_exact_tokens_for_tokens(
@@ -344,6 +455,134 @@ fn test_asset_conversion_access_control() {
       bob(),
       false,
     ));
+
+    // Direct AssetConversion swaps are filtered for signed origins
+    let exact_in = RuntimeCall::AssetConversion(
+      pallet_asset_conversion::Call::swap_exact_tokens_for_tokens {
+        path: vec![
+          Box::new(native_asset.clone()),
+          Box::new(local_asset.clone()),
+        ],
+        amount_in: 1000 * EXISTENTIAL_DEPOSIT,
+        amount_out_min: 1,
+        send_to: bob(),
+        keep_alive: false,
+      },
+    );
+    let exact_out = RuntimeCall::AssetConversion(
+      pallet_asset_conversion::Call::swap_tokens_for_exact_tokens {
+        path: vec![
+          Box::new(native_asset.clone()),
+          Box::new(local_asset.clone()),
+        ],
+        amount_out: 100 * EXISTENTIAL_DEPOSIT,
+        amount_in_max: 1000 * EXISTENTIAL_DEPOSIT,
+        send_to: bob(),
+        keep_alive: false,
+      },
+    );
+    for call in [exact_in, exact_out] {
+      assert_noop!(
+        call.dispatch(RuntimeOrigin::signed(bob())),
+        frame_system::Error::<Runtime>::CallFiltered
+      );
+    }
+  });
+}
+
+/// Test that liquidity management stays available while swaps are filtered, and that pools are
+/// created through the router
+#[test]
+fn test_call_filter_allows_liquidity_management() {
+  new_test_ext().execute_with(|| {
+    let asset_id = 1u32;
+    let native_asset = AssetKind::Native;
+    let local_asset = AssetKind::Local(asset_id);
+
+    assert_ok!(create_test_asset(asset_id, &alice(), EXISTENTIAL_DEPOSIT));
+    assert_ok!(mint_tokens(
+      asset_id,
+      &alice(),
+      &alice(),
+      200_000 * EXISTENTIAL_DEPOSIT
+    ));
+
+    assert_noop!(
+      RuntimeCall::AssetConversion(pallet_asset_conversion::Call::create_pool {
+        asset1: Box::new(native_asset.clone()),
+        asset2: Box::new(local_asset.clone()),
+      })
+      .dispatch(RuntimeOrigin::signed(alice())),
+      frame_system::Error::<Runtime>::CallFiltered
+    );
+    assert_ok!(create_pool(native_asset.clone(), local_asset.clone()));
+    assert_ok!(
+      RuntimeCall::AssetConversion(pallet_asset_conversion::Call::add_liquidity {
+        asset1: Box::new(native_asset.clone()),
+        asset2: Box::new(local_asset.clone()),
+        amount1_desired: 100_000 * EXISTENTIAL_DEPOSIT,
+        amount2_desired: 100_000 * EXISTENTIAL_DEPOSIT,
+        amount1_min: 1,
+        amount2_min: 1,
+        mint_to: alice(),
+      })
+      .dispatch(RuntimeOrigin::signed(alice()))
+    );
+    assert_ok!(
+      RuntimeCall::AssetConversion(pallet_asset_conversion::Call::remove_liquidity {
+        asset1: Box::new(native_asset.clone()),
+        asset2: Box::new(local_asset.clone()),
+        lp_token_burn: 10_000 * EXISTENTIAL_DEPOSIT,
+        amount1_min_receive: 1,
+        amount2_min_receive: 1,
+        withdraw_to: alice(),
+      })
+      .dispatch(RuntimeOrigin::signed(alice()))
+    );
+
+    // Router swaps dispatched as calls are allowed
+    assert_ok!(
+      RuntimeCall::DexRouter(pallet_dex_router::Call::swap_exact_tokens_for_tokens {
+        path: BoundedVec::try_from(vec![native_asset.clone(), local_asset.clone()]).unwrap(),
+        amount_in: 1000 * EXISTENTIAL_DEPOSIT,
+        amount_out_min: 1,
+        send_to: bob(),
+        keep_alive: false,
+      })
+      .dispatch(RuntimeOrigin::signed(bob()))
+    );
+  });
+}
+
+/// Test that XCM `Transact` cannot reach AssetConversion swaps either
+#[test]
+fn test_xcm_safe_call_filter_blocks_direct_swaps() {
+  let swap = RuntimeCall::AssetConversion(
+    pallet_asset_conversion::Call::swap_exact_tokens_for_tokens {
+      path: vec![Box::new(AssetKind::Native), Box::new(AssetKind::Local(1))],
+      amount_in: 1000 * EXISTENTIAL_DEPOSIT,
+      amount_out_min: 1,
+      send_to: bob(),
+      keep_alive: false,
+    },
+  );
+  let add_liquidity = RuntimeCall::AssetConversion(pallet_asset_conversion::Call::add_liquidity {
+    asset1: Box::new(AssetKind::Native),
+    asset2: Box::new(AssetKind::Local(1)),
+    amount1_desired: 1000 * EXISTENTIAL_DEPOSIT,
+    amount2_desired: 1000 * EXISTENTIAL_DEPOSIT,
+    amount1_min: 1,
+    amount2_min: 1,
+    mint_to: bob(),
+  });
+
+  type SafeCallFilter =
+    <crate::configs::xcm_config::XcmConfig as polkadot_sdk::staging_xcm_executor::Config>::SafeCallFilter;
+  // The call filters read the flash swap lock from storage
+  new_test_ext().execute_with(|| {
+    assert!(!SafeCallFilter::contains(&swap));
+    assert!(SafeCallFilter::contains(&add_liquidity));
+    assert!(!RuntimeCallFilter::contains(&swap));
   });
 }
 
@@ -414,11 +653,11 @@ fn test_dex_router_events() {
       &alice(),
       liquidity_amount * 2
     ));
-    assert_ok!(create_pool(native_asset, local_asset));
+    assert_ok!(create_pool(native_asset.clone(), local_asset.clone()));
     assert_ok!(add_liquidity(
       RuntimeOrigin::signed(alice()),
-      native_asset,
-      local_asset,
+      native_asset.clone(),
+      local_asset.clone(),
       (liquidity_amount, liquidity_amount),
       (1, 1),
       &alice(),
@@ -428,7 +667,7 @@ fn test_dex_router_events() {
     System::reset_events();
 
     // Execute swap
-    let path = BoundedVec::try_from(vec![native_asset, local_asset]).unwrap();
+    let path = BoundedVec::try_from(vec![native_asset.clone(), local_asset.clone()]).unwrap();
     assert_ok!(DexRouter::swap_exact_tokens_for_tokens(
       RuntimeOrigin::signed(bob()),
       path.clone(),
@@ -455,8 +694,9 @@ fn test_buyback_mechanism() {
     let asset_id = 1u32;
     let native_asset = AssetKind::Native;
     let local_asset = AssetKind::Local(asset_id);
-    let liquidity_amount = 100_000 * EXISTENTIAL_DEPOSIT;
     let swap_amount = 1_000_000 * EXISTENTIAL_DEPOSIT;
+    // Deep enough for the swap to stay within the circuit breaker limits
+    let liquidity_amount = 100 * swap_amount;
 
     assert_ok!(create_test_asset(asset_id, &alice(), EXISTENTIAL_DEPOSIT));
     assert_ok!(mint_tokens(
@@ -465,11 +705,11 @@ fn test_buyback_mechanism() {
       &alice(),
       liquidity_amount * 2
     ));
-    assert_ok!(create_pool(native_asset, local_asset));
+    assert_ok!(create_pool(native_asset.clone(), local_asset.clone()));
     assert_ok!(add_liquidity(
       RuntimeOrigin::signed(alice()),
-      native_asset,
-      local_asset,
+      native_asset.clone(),
+      local_asset.clone(),
       (liquidity_amount, liquidity_amount),
       (1, 1),
       &alice(),
@@ -479,7 +719,7 @@ fn test_buyback_mechanism() {
     let _initial_treasury_balance = Balances::free_balance(alice()); // Treasury account
 
     // Execute swap
-    let path = BoundedVec::try_from(vec![native_asset, local_asset]).unwrap();
+    let path = BoundedVec::try_from(vec![native_asset.clone(), local_asset.clone()]).unwrap();
     assert_ok!(DexRouter::swap_exact_tokens_for_tokens(
       RuntimeOrigin::signed(bob()),
       path,
@@ -523,18 +763,19 @@ fn test_path_validation_with_real_assets() {
     ));
 
     // Create only Native -> Asset1 pool
-    assert_ok!(create_pool(native_asset, local_asset1));
+    assert_ok!(create_pool(native_asset.clone(), local_asset1.clone()));
     assert_ok!(add_liquidity(
       RuntimeOrigin::signed(alice()),
-      native_asset,
-      local_asset1,
+      native_asset.clone(),
+      local_asset1.clone(),
       (100_000 * EXISTENTIAL_DEPOSIT, 100_000 * EXISTENTIAL_DEPOSIT),
       (1, 1),
       &alice(),
     ));
 
     // Valid path: Native -> Asset1 (should work)
-    let valid_path = BoundedVec::try_from(vec![native_asset, local_asset1]).unwrap();
+    let valid_path =
+      BoundedVec::try_from(vec![native_asset.clone(), local_asset1.clone()]).unwrap();
     assert_ok!(DexRouter::swap_exact_tokens_for_tokens(
       RuntimeOrigin::signed(bob()),
       valid_path,
@@ -545,7 +786,8 @@ fn test_path_validation_with_real_assets() {
     ));
 
     // Invalid path: Asset1 -> Asset2 (no pool)
-    let invalid_path = BoundedVec::try_from(vec![local_asset1, local_asset2]).unwrap();
+    let invalid_path =
+      BoundedVec::try_from(vec![local_asset1.clone(), local_asset2.clone()]).unwrap();
     assert_noop!(
       DexRouter::swap_exact_tokens_for_tokens(
         RuntimeOrigin::signed(bob()),
@@ -578,18 +820,18 @@ fn test_minimum_amount_out_protection() {
       &alice(),
       liquidity_amount * 2
     ));
-    assert_ok!(create_pool(native_asset, local_asset));
+    assert_ok!(create_pool(native_asset.clone(), local_asset.clone()));
     assert_ok!(add_liquidity(
       RuntimeOrigin::signed(alice()),
-      native_asset,
-      local_asset,
+      native_asset.clone(),
+      local_asset.clone(),
       (liquidity_amount, liquidity_amount),
       (1, 1),
       &alice(),
     ));
 
     // Test with reasonable minimum amount out (should work)
-    let path = BoundedVec::try_from(vec![native_asset, local_asset]).unwrap();
+    let path = BoundedVec::try_from(vec![native_asset.clone(), local_asset.clone()]).unwrap();
     assert_ok!(DexRouter::swap_exact_tokens_for_tokens(
       RuntimeOrigin::signed(bob()),
       path.clone(),
@@ -609,7 +851,7 @@ fn test_minimum_amount_out_protection() {
         bob(),
         false,
       ),
-      pallet_dex_router::Error::<Runtime>::NoLiquidityAvailable
+      pallet_dex_router::Error::<Runtime>::SlippageExceeded
     );
   });
 }
@@ -622,8 +864,9 @@ fn test_fee_calculation_accuracy() {
     let asset_id = 1u32;
     let native_asset = AssetKind::Native;
     let local_asset = AssetKind::Local(asset_id);
-    let liquidity_amount = 100_000 * EXISTENTIAL_DEPOSIT;
     let swap_amount = 100_000 * EXISTENTIAL_DEPOSIT; // 100K for precise calculation
+                                                     // Deep enough for the swap to stay within the circuit breaker limits
+    let liquidity_amount = 100 * swap_amount;
 
     assert_ok!(create_test_asset(asset_id, &alice(), EXISTENTIAL_DEPOSIT));
     assert_ok!(mint_tokens(
@@ -632,11 +875,11 @@ fn test_fee_calculation_accuracy() {
       &alice(),
       liquidity_amount * 2
     ));
-    assert_ok!(create_pool(native_asset, local_asset));
+    assert_ok!(create_pool(native_asset.clone(), local_asset.clone()));
     assert_ok!(add_liquidity(
       RuntimeOrigin::signed(alice()),
-      native_asset,
-      local_asset,
+      native_asset.clone(),
+      local_asset.clone(),
       (liquidity_amount, liquidity_amount),
       (1, 1),
       &alice(),
@@ -646,7 +889,7 @@ fn test_fee_calculation_accuracy() {
     System::reset_events();
 
     // Execute swap
-    let path = BoundedVec::try_from(vec![native_asset, local_asset]).unwrap();
+    let path = BoundedVec::try_from(vec![native_asset.clone(), local_asset.clone()]).unwrap();
     assert_ok!(DexRouter::swap_exact_tokens_for_tokens(
       RuntimeOrigin::signed(bob()),
       path,
@@ -687,10 +930,10 @@ fn test_router_with_empty_pools() {
     let swap_amount = 1_000 * EXISTENTIAL_DEPOSIT;
 
     assert_ok!(create_test_asset(asset_id, &alice(), EXISTENTIAL_DEPOSIT));
-    assert_ok!(create_pool(native_asset, local_asset));
+    assert_ok!(create_pool(native_asset.clone(), local_asset.clone()));
 
     // Try to swap with empty pool (should fail)
-    let path = BoundedVec::try_from(vec![native_asset, local_asset]).unwrap();
+    let path = BoundedVec::try_from(vec![native_asset.clone(), local_asset.clone()]).unwrap();
     assert_noop!(
       DexRouter::swap_exact_tokens_for_tokens(
         RuntimeOrigin::signed(bob()),
@@ -704,3 +947,743 @@ fn test_router_with_empty_pools() {
     );
   });
 }
+
+/// Test that a trade moving the price too far is rejected before it executes
+#[test]
+fn test_circuit_breaker_rejects_price_movement() {
+  new_test_ext().execute_with(|| {
+    let native_asset = AssetKind::Native;
+    let local_asset = AssetKind::Local(1);
+    setup_native_pool(1, 100_000 * EXISTENTIAL_DEPOSIT);
+
+    // Small trade stays within the limits
+    assert_ok!(router_swap(
+      &bob(),
+      native_asset.clone(),
+      local_asset.clone(),
+      1_000 * EXISTENTIAL_DEPOSIT
+    ));
+    let observation = pallet_dex_router::PairObservations::<Runtime>::get((
+      native_asset.clone(),
+      local_asset.clone(),
+    ));
+    assert!(observation.is_some());
+
+    // A trade moving the price by ~30% fails, in either direction
+    assert_noop!(
+      router_swap(
+        &bob(),
+        native_asset.clone(),
+        local_asset.clone(),
+        20_000 * EXISTENTIAL_DEPOSIT
+      ),
+      pallet_dex_router::Error::<Runtime>::CircuitBreakerLimitExceeded
+    );
+    assert_noop!(
+      router_swap(
+        &alice(),
+        local_asset.clone(),
+        native_asset.clone(),
+        20_000 * EXISTENTIAL_DEPOSIT
+      ),
+      pallet_dex_router::Error::<Runtime>::CircuitBreakerLimitExceeded
+    );
+    // Rejected trades are not recorded, so trades within the limits still go through
+    assert_eq!(
+      pallet_dex_router::PairObservations::<Runtime>::get((
+        native_asset.clone(),
+        local_asset.clone()
+      )),
+      observation
+    );
+    assert_ok!(router_swap(
+      &bob(),
+      local_asset.clone(),
+      native_asset.clone(),
+      500 * EXISTENTIAL_DEPOSIT
+    ));
+  });
+}
+
+/// Test that trades pushing the cumulative volume of a block over the cap trip the pair
+#[test]
+fn test_circuit_breaker_trips_on_block_volume() {
+  new_test_ext().execute_with(|| {
+    let native_asset = AssetKind::Native;
+    let local_asset = AssetKind::Local(1);
+    setup_native_pool(1, 100_000 * EXISTENTIAL_DEPOSIT);
+
+    // A single trade above 10% of the reserves fails
+    assert_noop!(
+      router_swap(
+        &bob(),
+        native_asset.clone(),
+        local_asset.clone(),
+        11_000 * EXISTENTIAL_DEPOSIT
+      ),
+      pallet_dex_router::Error::<Runtime>::CircuitBreakerLimitExceeded
+    );
+
+    // Alternate directions so the price barely moves while volume accumulates (~3% + ~2.5% each)
+    for (asset_in, asset_out, amount) in [
+      (&native_asset, &local_asset, 3_000),
+      (&local_asset, &native_asset, 2_500),
+      (&native_asset, &local_asset, 3_000),
+    ] {
+      assert_ok!(router_swap(
+        &bob(),
+        asset_in.clone(),
+        asset_out.clone(),
+        amount * EXISTENTIAL_DEPOSIT
+      ));
+    }
+    assert!(!DexRouter::is_tripped(&(
+      native_asset.clone(),
+      local_asset.clone()
+    )));
+
+    // The trade crossing 10% of the reserves within the block executes and trips the pair
+    assert_ok!(router_swap(
+      &bob(),
+      local_asset.clone(),
+      native_asset.clone(),
+      2_500 * EXISTENTIAL_DEPOSIT
+    ));
+    System::assert_has_event(RuntimeEvent::DexRouter(
+      pallet_dex_router::Event::CircuitBreakerTripped {
+        asset_a: native_asset.clone(),
+        asset_b: local_asset.clone(),
+        reason: pallet_dex_router::TripReason::VolumeCap,
+      },
+    ));
+
+    // The pair stays tripped in later blocks, in both directions
+    System::set_block_number(2);
+    assert_noop!(
+      router_swap(
+        &bob(),
+        native_asset.clone(),
+        local_asset.clone(),
+        100 * EXISTENTIAL_DEPOSIT
+      ),
+      pallet_dex_router::Error::<Runtime>::CircuitBreakerTripped
+    );
+    assert_noop!(
+      router_swap(
+        &bob(),
+        local_asset.clone(),
+        native_asset.clone(),
+        100 * EXISTENTIAL_DEPOSIT
+      ),
+      pallet_dex_router::Error::<Runtime>::CircuitBreakerTripped
+    );
+  });
+}
+
+/// Test that trades moving the price too far within the window together trip the pair
+#[test]
+fn test_circuit_breaker_trips_on_price_movement() {
+  new_test_ext().execute_with(|| {
+    let native_asset = AssetKind::Native;
+    let local_asset = AssetKind::Local(1);
+    setup_native_pool(1, 100_000 * EXISTENTIAL_DEPOSIT);
+
+    // Each trade moves the price by ~14%, within the limits on its own
+    assert_ok!(router_swap(
+      &bob(),
+      native_asset.clone(),
+      local_asset.clone(),
+      8_000 * EXISTENTIAL_DEPOSIT
+    ));
+    assert!(!DexRouter::is_tripped(&(
+      native_asset.clone(),
+      local_asset.clone()
+    )));
+
+    // The second, in a later block of the same window, takes the price ~25% from its reference
+    System::set_block_number(2);
+    assert_ok!(router_swap(
+      &bob(),
+      native_asset.clone(),
+      local_asset.clone(),
+      8_000 * EXISTENTIAL_DEPOSIT
+    ));
+    System::assert_has_event(RuntimeEvent::DexRouter(
+      pallet_dex_router::Event::CircuitBreakerTripped {
+        asset_a: native_asset.clone(),
+        asset_b: local_asset.clone(),
+        reason: pallet_dex_router::TripReason::PriceMovement,
+      },
+    ));
+
+    // Even trades moving the price back are rejected
+    assert_noop!(
+      router_swap(
+        &bob(),
+        local_asset.clone(),
+        native_asset.clone(),
+        100 * EXISTENTIAL_DEPOSIT
+      ),
+      pallet_dex_router::Error::<Runtime>::CircuitBreakerTripped
+    );
+  });
+}
+
+/// Trip the circuit breaker of a Native/Local pair with trades exceeding the block volume cap
+fn trip_circuit_breaker(asset_id: AssetId) {
+  let (native, local) = (AssetKind::Native, AssetKind::Local(asset_id));
+  for (asset_in, asset_out, amount) in [
+    (&native, &local, 4_000),
+    (&local, &native, 3_500),
+    (&native, &local, 4_000),
+  ] {
+    assert_ok!(router_swap(
+      &bob(),
+      asset_in.clone(),
+      asset_out.clone(),
+      amount * EXISTENTIAL_DEPOSIT
+    ));
+  }
+  assert!(DexRouter::is_tripped(&(
+    AssetKind::Native,
+    AssetKind::Local(asset_id)
+  )));
+}
+
+/// Test that only the admin origin can reset a tripped pair
+#[test]
+fn test_circuit_breaker_admin_reset() {
+  new_test_ext().execute_with(|| {
+    let native_asset = AssetKind::Native;
+    let local_asset = AssetKind::Local(1);
+    setup_native_pool(1, 100_000 * EXISTENTIAL_DEPOSIT);
+
+    // Nothing to reset yet
+    assert_noop!(
+      DexRouter::reset_circuit_breaker(
+        RuntimeOrigin::root(),
+        native_asset.clone(),
+        local_asset.clone()
+      ),
+      pallet_dex_router::Error::<Runtime>::CircuitBreakerNotTripped
+    );
+
+    trip_circuit_breaker(1);
+
+    // Regular accounts cannot reset the breaker
+    assert_noop!(
+      DexRouter::reset_circuit_breaker(
+        RuntimeOrigin::signed(bob()),
+        native_asset.clone(),
+        local_asset.clone()
+      ),
+      DispatchError::BadOrigin
+    );
+
+    // Root resets it regardless of asset order and trading resumes within the same block
+    assert_ok!(DexRouter::reset_circuit_breaker(
+      RuntimeOrigin::root(),
+      local_asset.clone(),
+      native_asset.clone()
+    ));
+    System::assert_last_event(RuntimeEvent::DexRouter(
+      pallet_dex_router::Event::CircuitBreakerReset {
+        asset_a: native_asset.clone(),
+        asset_b: local_asset.clone(),
+      },
+    ));
+    assert!(pallet_dex_router::PairObservations::<Runtime>::get((
+      native_asset.clone(),
+      local_asset.clone()
+    ))
+    .is_none());
+    assert_ok!(router_swap(
+      &bob(),
+      native_asset.clone(),
+      local_asset.clone(),
+      1_000 * EXISTENTIAL_DEPOSIT
+    ));
+  });
+}
+
+/// Test that a tripped pair re-opens automatically once the cooldown has passed
+#[test]
+fn test_circuit_breaker_cooldown() {
+  new_test_ext().execute_with(|| {
+    let native_asset = AssetKind::Native;
+    let local_asset = AssetKind::Local(1);
+    let cooldown: u32 = <Runtime as pallet_dex_router::Config>::CircuitBreakerCooldown::get();
+    setup_native_pool(1, 100_000 * EXISTENTIAL_DEPOSIT);
+
+    trip_circuit_breaker(1);
+
+    // Still tripped one block before the cooldown ends
+    System::set_block_number(cooldown);
+    assert_noop!(
+      router_swap(
+        &bob(),
+        native_asset.clone(),
+        local_asset.clone(),
+        1_000 * EXISTENTIAL_DEPOSIT
+      ),
+      pallet_dex_router::Error::<Runtime>::CircuitBreakerTripped
+    );
+
+    // Trading resumes once the cooldown has passed, and the next trade clears the trip
+    System::set_block_number(1 + cooldown);
+    assert!(!DexRouter::is_tripped(&(
+      native_asset.clone(),
+      local_asset.clone()
+    )));
+    assert_ok!(router_swap(
+      &bob(),
+      native_asset.clone(),
+      local_asset.clone(),
+      1_000 * EXISTENTIAL_DEPOSIT
+    ));
+    System::assert_has_event(RuntimeEvent::DexRouter(
+      pallet_dex_router::Event::CircuitBreakerReset {
+        asset_a: native_asset.clone(),
+        asset_b: local_asset.clone(),
+      },
+    ));
+    assert!(pallet_dex_router::TrippedPairs::<Runtime>::get((
+      native_asset.clone(),
+      local_asset.clone()
+    ))
+    .is_none());
+  });
+}
+
+/// Test that pausing the whole router blocks every swap until resumed
+#[test]
+fn test_router_pause() {
+  new_test_ext().execute_with(|| {
+    let native_asset = AssetKind::Native;
+    let local_asset = AssetKind::Local(1);
+    setup_native_pool(1, 100_000 * EXISTENTIAL_DEPOSIT);
+
+    // Only the admin origin can pause
+    assert_noop!(
+      DexRouter::set_router_paused(RuntimeOrigin::signed(bob()), true),
+      DispatchError::BadOrigin
+    );
+
+    assert_ok!(DexRouter::set_router_paused(RuntimeOrigin::root(), true));
+    System::assert_last_event(RuntimeEvent::DexRouter(
+      pallet_dex_router::Event::RouterPauseChanged { paused: true },
+    ));
+    assert!(DexRouter::pause_status(native_asset.clone(), local_asset.clone()).router_paused);
+
+    assert_noop!(
+      router_swap(
+        &bob(),
+        native_asset.clone(),
+        local_asset.clone(),
+        1_000 * EXISTENTIAL_DEPOSIT
+      ),
+      pallet_dex_router::Error::<Runtime>::RouterPaused
+    );
+
+    assert_ok!(DexRouter::set_router_paused(RuntimeOrigin::root(), false));
+    assert_ok!(router_swap(
+      &bob(),
+      native_asset.clone(),
+      local_asset.clone(),
+      1_000 * EXISTENTIAL_DEPOSIT
+    ));
+  });
+}
+
+/// Test that pausing a pair blocks it in both directions while liquidity can still be withdrawn
+#[test]
+fn test_pair_pause_allows_liquidity_withdrawal() {
+  new_test_ext().execute_with(|| {
+    let native_asset = AssetKind::Native;
+    let local_asset1 = AssetKind::Local(10);
+    let local_asset2 = AssetKind::Local(11);
+    setup_native_pool(10, 100_000 * EXISTENTIAL_DEPOSIT);
+    setup_native_pool(11, 100_000 * EXISTENTIAL_DEPOSIT);
+
+    assert_ok!(DexRouter::set_pair_paused(
+      RuntimeOrigin::root(),
+      local_asset1.clone(),
+      native_asset.clone(),
+      true
+    ));
+    System::assert_last_event(RuntimeEvent::DexRouter(
+      pallet_dex_router::Event::PairPauseChanged {
+        asset_a: native_asset.clone(),
+        asset_b: local_asset1.clone(),
+        paused: true,
+      },
+    ));
+
+    // The paused pair is blocked in both directions
+    assert_noop!(
+      router_swap(
+        &bob(),
+        native_asset.clone(),
+        local_asset1.clone(),
+        1_000 * EXISTENTIAL_DEPOSIT
+      ),
+      pallet_dex_router::Error::<Runtime>::PairPaused
+    );
+    assert_noop!(
+      router_swap(
+        &bob(),
+        local_asset1.clone(),
+        native_asset.clone(),
+        1_000 * EXISTENTIAL_DEPOSIT
+      ),
+      pallet_dex_router::Error::<Runtime>::PairPaused
+    );
+
+    // Other pairs keep trading
+    assert!(!DexRouter::pause_status(native_asset.clone(), local_asset2.clone()).pair_paused);
+    assert_ok!(router_swap(
+      &bob(),
+      native_asset.clone(),
+      local_asset2.clone(),
+      1_000 * EXISTENTIAL_DEPOSIT
+    ));
+
+    // Liquidity providers can still exit the paused pair
+    let asset_balance_before = Assets::balance(10, alice());
+    assert_ok!(AssetConversion::remove_liquidity(
+      RuntimeOrigin::signed(alice()),
+      Box::new(native_asset.clone()),
+      Box::new(local_asset1.clone()),
+      10_000 * EXISTENTIAL_DEPOSIT,
+      1,
+      1,
+      alice(),
+    ));
+    assert!(Assets::balance(10, alice()) > asset_balance_before);
+
+    // Resuming the pair re-enables swaps
+    assert_ok!(DexRouter::set_pair_paused(
+      RuntimeOrigin::root(),
+      native_asset.clone(),
+      local_asset1.clone(),
+      false
+    ));
+    assert_ok!(router_swap(
+      &bob(),
+      native_asset.clone(),
+      local_asset1.clone(),
+      1_000 * EXISTENTIAL_DEPOSIT
+    ));
+  });
+}
+
+/// Test that pausing an AMM stops the router from routing through it
+#[test]
+fn test_amm_pause() {
+  new_test_ext().execute_with(|| {
+    let native_asset = AssetKind::Native;
+    let local_asset = AssetKind::Local(1);
+    setup_native_pool(1, 100_000 * EXISTENTIAL_DEPOSIT);
+
+    assert_ok!(DexRouter::set_amm_paused(
+      RuntimeOrigin::root(),
+      pallet_dex_router::AMMType::XYK,
+      true
+    ));
+    assert_eq!(
+      DexRouter::pause_status(native_asset.clone(), local_asset.clone()).paused_amms,
+      vec![pallet_dex_router::AMMType::XYK]
+    );
+
+    assert_noop!(
+      router_swap(
+        &bob(),
+        native_asset.clone(),
+        local_asset.clone(),
+        1_000 * EXISTENTIAL_DEPOSIT
+      ),
+      pallet_dex_router::Error::<Runtime>::AMMPaused
+    );
+
+    assert_ok!(DexRouter::set_amm_paused(
+      RuntimeOrigin::root(),
+      pallet_dex_router::AMMType::XYK,
+      false
+    ));
+    assert_ok!(router_swap(
+      &bob(),
+      native_asset.clone(),
+      local_asset.clone(),
+      1_000 * EXISTENTIAL_DEPOSIT
+    ));
+  });
+}
+
+/// Test that foreign assets can be pooled and routed like local ones
+#[test]
+fn test_router_swaps_foreign_assets() {
+  new_test_ext().execute_with(|| {
+    let sibling_token = Location::new(1, [Parachain(2000)]);
+    let native_asset = AssetKind::Native;
+    let foreign_asset = AssetKind::Foreign(sibling_token.clone());
+    setup_foreign_pool(sibling_token.clone(), 100_000 * EXISTENTIAL_DEPOSIT);
+
+    // Native -> Foreign
+    assert_ok!(router_swap(
+      &bob(),
+      native_asset.clone(),
+      foreign_asset.clone(),
+      5_000 * EXISTENTIAL_DEPOSIT
+    ));
+    let foreign_balance = ForeignAssets::balance(sibling_token.clone(), bob());
+    assert!(foreign_balance > 0);
+
+    // Foreign -> Native, with the router fee collected in the foreign asset
+    let swap_amount = foreign_balance / 2;
+    assert_ok!(router_swap(
+      &bob(),
+      foreign_asset.clone(),
+      native_asset,
+      swap_amount
+    ));
+    let fee = Permill::from_parts(2000).mul_floor(swap_amount);
+    let treasury_fee = TreasuryFeeShare::get().mul_floor(fee);
+    assert_eq!(
+      ForeignAssets::balance(sibling_token.clone(), TreasuryAccount::get()),
+      treasury_fee
+    );
+    assert_eq!(
+      ForeignAssets::balance(sibling_token.clone(), RouterFeeCollector::get()),
+      fee - treasury_fee
+    );
+    assert_eq!(
+      ForeignAssets::balance(sibling_token, bob()),
+      foreign_balance - swap_amount
+    );
+    assert!(System::events().iter().any(|record| matches!(
+      &record.event,
+      RuntimeEvent::DexRouter(pallet_dex_router::Event::SwapExecuted {
+        asset_in,
+        asset_out: AssetKind::Native,
+        ..
+      }) if *asset_in == foreign_asset
+    )));
+  });
+}
+
+/// Test that foreign assets cannot be registered by regular accounts
+#[test]
+fn test_foreign_asset_creation_requires_force_origin() {
+  new_test_ext().execute_with(|| {
+    let sibling_token = Location::new(1, [Parachain(2000)]);
+    assert_noop!(
+      ForeignAssets::create(
+        RuntimeOrigin::signed(alice()),
+        sibling_token,
+        MultiAddress::Id(alice()),
+        EXISTENTIAL_DEPOSIT,
+      ),
+      DispatchError::BadOrigin
+    );
+  });
+}
+
+/// Test that router swaps report the weight of the route they executed
+#[test]
+fn test_router_swap_reports_actual_weight() {
+  new_test_ext().execute_with(|| {
+    setup_native_pool(1, 100_000 * EXISTENTIAL_DEPOSIT);
+    let call = RuntimeCall::DexRouter(pallet_dex_router::Call::swap_exact_tokens_for_tokens {
+      path: BoundedVec::try_from(vec![AssetKind::Native, AssetKind::Local(1)]).unwrap(),
+      amount_in: 1_000 * EXISTENTIAL_DEPOSIT,
+      amount_out_min: 1,
+      send_to: bob(),
+      keep_alive: false,
+    });
+    let charged = call.get_dispatch_info().call_weight;
+
+    let post_info = call.dispatch(RuntimeOrigin::signed(bob())).unwrap();
+
+    let actual = post_info
+      .actual_weight
+      .expect("router swaps report their actual weight");
+    assert_eq!(
+      actual,
+      DexRouter::swap_weight(&pallet_dex_router::ExecutedRoute { hops: 1 })
+    );
+    assert!(actual.all_lte(charged));
+  });
+}
+
+/// Test that a route failing before its last hop is only charged for the hops it reached
+#[test]
+fn test_router_swap_refunds_hops_not_reached() {
+  new_test_ext().execute_with(|| {
+    setup_native_pool(1, 100_000 * EXISTENTIAL_DEPOSIT);
+    // Local(2) has no pool, so the first hop fails and the other two are never reached
+    let call = RuntimeCall::DexRouter(pallet_dex_router::Call::swap_exact_tokens_for_tokens {
+      path: BoundedVec::try_from(vec![
+        AssetKind::Native,
+        AssetKind::Local(2),
+        AssetKind::Native,
+        AssetKind::Local(1),
+      ])
+      .unwrap(),
+      amount_in: 1_000 * EXISTENTIAL_DEPOSIT,
+      amount_out_min: 1,
+      send_to: bob(),
+      keep_alive: false,
+    });
+    let charged = call.get_dispatch_info().call_weight;
+
+    let err = call.dispatch(RuntimeOrigin::signed(bob())).unwrap_err();
+
+    assert_eq!(
+      err.error,
+      pallet_dex_router::Error::<Runtime>::NoLiquidityAvailable.into()
+    );
+    let actual = err
+      .post_info
+      .actual_weight
+      .expect("failed routes report the weight of the hops reached");
+    assert_eq!(
+      actual,
+      DexRouter::swap_weight(&pallet_dex_router::ExecutedRoute { hops: 1 })
+    );
+    assert!(actual.all_lte(charged) && actual != charged);
+  });
+}
+
+/// Test that the router's storage invariants hold after trading and catch inconsistent state
+#[test]
+fn test_router_try_state() {
+  new_test_ext().execute_with(|| {
+    setup_native_pool(1, 100_000 * EXISTENTIAL_DEPOSIT);
+    assert_ok!(router_swap(
+      &bob(),
+      AssetKind::Native,
+      AssetKind::Local(1),
+      1_000 * EXISTENTIAL_DEPOSIT
+    ));
+    assert_ok!(DexRouter::do_try_state());
+
+    // An observation for a pair without a pool
+    let observation =
+      pallet_dex_router::PairObservations::<Runtime>::get((AssetKind::Native, AssetKind::Local(1)))
+        .expect("the swap recorded an observation");
+    let unpooled = (AssetKind::Native, AssetKind::Local(2));
+    pallet_dex_router::PairObservations::<Runtime>::insert(&unpooled, observation.clone());
+    assert!(DexRouter::do_try_state().is_err());
+    pallet_dex_router::PairObservations::<Runtime>::remove(&unpooled);
+
+    // A tripped pair without an observation
+    pallet_dex_router::TrippedPairs::<Runtime>::insert(&unpooled, System::block_number());
+    assert!(DexRouter::do_try_state().is_err());
+    pallet_dex_router::TrippedPairs::<Runtime>::remove(&unpooled);
+
+    // A router fee the fee accounts were not credited with in full
+    let ledger = pallet_dex_router::FeeLedger::<Runtime>::get(AssetKind::Native);
+    assert!(ledger.charged > 0);
+    assert_eq!(ledger.charged, ledger.received);
+    pallet_dex_router::FeeLedger::<Runtime>::mutate(AssetKind::Native, |entry| entry.received -= 1);
+    assert!(DexRouter::do_try_state().is_err());
+    pallet_dex_router::FeeLedger::<Runtime>::insert(AssetKind::Native, ledger);
+
+    // A treasury left below the existential deposit
+    let treasury = TreasuryAccount::get();
+    let account = frame_system::Account::<Runtime>::get(&treasury);
+    frame_system::Account::<Runtime>::mutate(&treasury, |account| {
+      account.data.free = EXISTENTIAL_DEPOSIT - 1
+    });
+    assert!(DexRouter::do_try_state().is_err());
+    frame_system::Account::<Runtime>::insert(&treasury, account);
+    assert_ok!(DexRouter::do_try_state());
+
+    // An observation from a future block
+    let mut future = observation;
+    future.window_start = System::block_number() + 1;
+    pallet_dex_router::PairObservations::<Runtime>::insert(
+      (AssetKind::Native, AssetKind::Local(1)),
+      future,
+    );
+    assert!(DexRouter::do_try_state().is_err());
+  });
+}
+
+/// Test that swaps report the output actually received in both directions
+#[test]
+fn test_swap_reports_received_amount() {
+  new_test_ext().execute_with(|| {
+    setup_native_pool(1, 100_000 * EXISTENTIAL_DEPOSIT);
+    let amount_in = 1_000 * EXISTENTIAL_DEPOSIT;
+    let swapped_amount_out = || {
+      System::events()
+        .iter()
+        .rev()
+        .find_map(|record| match &record.event {
+          RuntimeEvent::DexRouter(pallet_dex_router::Event::SwapExecuted {
+            amount_out, ..
+          }) => Some(*amount_out),
+          _ => None,
+        })
+        .expect("the swap emitted an event")
+    };
+
+    let local_before = Assets::balance(1, bob());
+    assert_ok!(router_swap(
+      &bob(),
+      AssetKind::Native,
+      AssetKind::Local(1),
+      amount_in
+    ));
+    assert_eq!(
+      swapped_amount_out(),
+      Assets::balance(1, bob()) - local_before
+    );
+
+    let native_before = Balances::free_balance(bob());
+    assert_ok!(router_swap(
+      &bob(),
+      AssetKind::Local(1),
+      AssetKind::Native,
+      amount_in / 2
+    ));
+    assert_eq!(
+      swapped_amount_out(),
+      Balances::free_balance(bob()) - native_before
+    );
+  });
+}
+
+/// Test that failed swaps report why the AMM could not execute them
+#[test]
+fn test_swap_failures_keep_their_cause() {
+  new_test_ext().execute_with(|| {
+    setup_native_pool(1, 100_000 * EXISTENTIAL_DEPOSIT);
+    let amount_in = 1_000 * EXISTENTIAL_DEPOSIT;
+
+    // An account holding less than it swaps
+    let trader = AccountId::from([9u8; 32]);
+    assert_ok!(Balances::transfer_keep_alive(
+      RuntimeOrigin::signed(alice()),
+      trader.clone().into(),
+      10 * EXISTENTIAL_DEPOSIT,
+    ));
+    assert_noop!(
+      router_swap(&trader, AssetKind::Native, AssetKind::Local(1), amount_in),
+      pallet_dex_router::Error::<Runtime>::InsufficientBalance
+    );
+
+    // An account whose asset is frozen
+    assert_ok!(mint_tokens(1, &alice(), &bob(), amount_in));
+    assert_ok!(Assets::freeze(
+      RuntimeOrigin::signed(alice()),
+      1,
+      MultiAddress::Id(bob()),
+    ));
+    assert_noop!(
+      router_swap(&bob(), AssetKind::Local(1), AssetKind::Native, amount_in),
+      pallet_dex_router::Error::<Runtime>::AssetUnavailable
+    );
+  });
+}
@@ -0,0 +1,331 @@
+//! Tests for liquidity mining programs on asset conversion LP tokens.
+//!
+//! The first pool created in a test gets LP token 0 in `PoolAssets` and the first program
+//! created gets program id 0.
+
+use super::dex_router_integration_tests::{
+  alice, bob, new_test_ext, router_swap, setup_native_pool, LOCAL_ASSET,
+};
+use crate::{
+  configs::{AssetKind, RouterFeeCollector},
+  Balance, Balances, DexRouter, Farming, PoolAssets, Runtime, RuntimeEvent, RuntimeOrigin, System,
+  EXISTENTIAL_DEPOSIT,
+};
+use pallet_farming::ProgramId;
+use polkadot_sdk::{
+  frame_support::{assert_noop, assert_ok},
+  sp_runtime::DispatchError,
+};
+
+const LP_TOKEN: u32 = 0;
+const PROGRAM: ProgramId = 0;
+const LIQUIDITY: Balance = 100_000 * EXISTENTIAL_DEPOSIT;
+const REWARD_PER_BLOCK: Balance = 10 * EXISTENTIAL_DEPOSIT;
+const END_BLOCK: u32 = 101;
+
+/// Create a pool and a native reward program for its LP token, funded by alice
+fn setup_program() {
+  setup_native_pool(LOCAL_ASSET, LIQUIDITY);
+  assert_ok!(Farming::create_program(
+    RuntimeOrigin::root(),
+    LP_TOKEN,
+    AssetKind::Native,
+    REWARD_PER_BLOCK,
+    END_BLOCK,
+  ));
+  assert_ok!(Farming::fund_program(
+    RuntimeOrigin::signed(alice()),
+    PROGRAM,
+    REWARD_PER_BLOCK * 1_000,
+  ));
+}
+
+/// Rewards are rounded down, so allow one unit less than `expected`
+fn assert_rewards(actual: Balance, expected: Balance) {
+  assert!(
+    actual <= expected && actual + 1 >= expected,
+    "{actual} != {expected}"
+  );
+}
+
+/// Test that rewards accrue per block and are paid out on harvest
+#[test]
+fn test_rewards_accrue_and_harvest() {
+  new_test_ext().execute_with(|| {
+    setup_program();
+    let staked = PoolAssets::balance(LP_TOKEN, alice()) / 2;
+    assert_ok!(Farming::stake(
+      RuntimeOrigin::signed(alice()),
+      PROGRAM,
+      staked
+    ));
+    assert_eq!(
+      PoolAssets::balance(LP_TOKEN, Farming::program_account(PROGRAM)),
+      staked
+    );
+
+    System::set_block_number(11);
+    let pending = Farming::pending_rewards(PROGRAM, &alice()).unwrap();
+    assert_rewards(pending, 10 * REWARD_PER_BLOCK);
+
+    let native_before = Balances::free_balance(alice());
+    assert_ok!(Farming::harvest(RuntimeOrigin::signed(alice()), PROGRAM));
+    assert_eq!(Balances::free_balance(alice()), native_before + pending);
+    assert_eq!(Farming::pending_rewards(PROGRAM, &alice()), Some(0));
+    System::assert_last_event(RuntimeEvent::Farming(
+      pallet_farming::Event::RewardsHarvested {
+        who: alice(),
+        program_id: PROGRAM,
+        amount: pending,
+      },
+    ));
+
+    assert_noop!(
+      Farming::harvest(RuntimeOrigin::signed(alice()), PROGRAM),
+      pallet_farming::Error::<Runtime>::NoRewards
+    );
+  });
+}
+
+/// Test that the emission is shared pro rata and late stakers only earn from when they joined
+#[test]
+fn test_rewards_shared_between_stakers() {
+  new_test_ext().execute_with(|| {
+    setup_program();
+    assert_ok!(DexRouter::zap_in(
+      RuntimeOrigin::signed(bob()),
+      AssetKind::Native,
+      AssetKind::Local(LOCAL_ASSET),
+      1_000 * EXISTENTIAL_DEPOSIT,
+      1,
+    ));
+    let bob_staked = PoolAssets::balance(LP_TOKEN, bob());
+    let alice_staked = 3 * bob_staked;
+    assert_ok!(Farming::stake(
+      RuntimeOrigin::signed(alice()),
+      PROGRAM,
+      alice_staked
+    ));
+
+    // Alice earns everything until bob joins
+    System::set_block_number(5);
+    assert_ok!(Farming::stake(
+      RuntimeOrigin::signed(bob()),
+      PROGRAM,
+      bob_staked
+    ));
+
+    System::set_block_number(9);
+    assert_rewards(
+      Farming::pending_rewards(PROGRAM, &alice()).unwrap(),
+      4 * REWARD_PER_BLOCK + 3 * REWARD_PER_BLOCK,
+    );
+    assert_rewards(
+      Farming::pending_rewards(PROGRAM, &bob()).unwrap(),
+      REWARD_PER_BLOCK,
+    );
+  });
+}
+
+/// Test that unstaking returns LP tokens and keeps earned rewards harvestable
+#[test]
+fn test_unstake_keeps_pending_rewards() {
+  new_test_ext().execute_with(|| {
+    setup_program();
+    let lp_before = PoolAssets::balance(LP_TOKEN, alice());
+    assert_ok!(Farming::stake(
+      RuntimeOrigin::signed(alice()),
+      PROGRAM,
+      lp_before
+    ));
+
+    System::set_block_number(3);
+    assert_noop!(
+      Farming::unstake(RuntimeOrigin::signed(alice()), PROGRAM, lp_before + 1),
+      pallet_farming::Error::<Runtime>::InsufficientStake
+    );
+    assert_ok!(Farming::unstake(
+      RuntimeOrigin::signed(alice()),
+      PROGRAM,
+      lp_before
+    ));
+    assert_eq!(PoolAssets::balance(LP_TOKEN, alice()), lp_before);
+
+    // Nothing accrues without stakers, but what was earned can still be harvested
+    System::set_block_number(10);
+    let pending = Farming::pending_rewards(PROGRAM, &alice()).unwrap();
+    assert_rewards(pending, 2 * REWARD_PER_BLOCK);
+    assert_ok!(Farming::harvest(RuntimeOrigin::signed(alice()), PROGRAM));
+    assert!(pallet_farming::Stakes::<Runtime>::get(PROGRAM, alice()).is_none());
+  });
+}
+
+/// Test that emission stops at the end block and can be extended by the admin
+#[test]
+fn test_emission_ends_and_can_be_extended() {
+  new_test_ext().execute_with(|| {
+    setup_program();
+    let staked = PoolAssets::balance(LP_TOKEN, alice());
+    assert_ok!(Farming::stake(
+      RuntimeOrigin::signed(alice()),
+      PROGRAM,
+      staked
+    ));
+
+    System::set_block_number(END_BLOCK + 50);
+    let emitted = (END_BLOCK as Balance - 1) * REWARD_PER_BLOCK;
+    assert_rewards(
+      Farming::pending_rewards(PROGRAM, &alice()).unwrap(),
+      emitted,
+    );
+
+    assert_ok!(Farming::set_program_emission(
+      RuntimeOrigin::root(),
+      PROGRAM,
+      2 * REWARD_PER_BLOCK,
+      END_BLOCK + 100,
+    ));
+    System::set_block_number(END_BLOCK + 60);
+    assert_rewards(
+      Farming::pending_rewards(PROGRAM, &alice()).unwrap(),
+      emitted + 20 * REWARD_PER_BLOCK,
+    );
+  });
+}
+
+/// Test that programs can only pay out what they have been funded with
+#[test]
+fn test_harvest_requires_funding() {
+  new_test_ext().execute_with(|| {
+    setup_native_pool(LOCAL_ASSET, LIQUIDITY);
+    assert_ok!(Farming::create_program(
+      RuntimeOrigin::root(),
+      LP_TOKEN,
+      AssetKind::Native,
+      REWARD_PER_BLOCK,
+      END_BLOCK,
+    ));
+    let staked = PoolAssets::balance(LP_TOKEN, alice());
+    assert_ok!(Farming::stake(
+      RuntimeOrigin::signed(alice()),
+      PROGRAM,
+      staked
+    ));
+    System::set_block_number(11);
+
+    assert_noop!(
+      Farming::harvest(RuntimeOrigin::signed(alice()), PROGRAM),
+      pallet_farming::Error::<Runtime>::InsufficientRewards
+    );
+  });
+}
+
+/// Test that the admin can fund programs from the router fees
+#[test]
+fn test_program_funded_from_router_fees() {
+  new_test_ext().execute_with(|| {
+    setup_native_pool(LOCAL_ASSET, LIQUIDITY);
+    assert_ok!(Farming::create_program(
+      RuntimeOrigin::root(),
+      LP_TOKEN,
+      AssetKind::Native,
+      REWARD_PER_BLOCK,
+      END_BLOCK,
+    ));
+    assert_ok!(router_swap(
+      &bob(),
+      AssetKind::Native,
+      AssetKind::Local(LOCAL_ASSET),
+      1_000 * EXISTENTIAL_DEPOSIT,
+    ));
+    let fee_collector = RouterFeeCollector::get();
+    let fees = Balances::free_balance(&fee_collector) - EXISTENTIAL_DEPOSIT;
+    assert!(fees > 0);
+
+    assert_noop!(
+      Farming::fund_program_from_fees(RuntimeOrigin::signed(alice()), PROGRAM, fees),
+      DispatchError::BadOrigin
+    );
+    assert_ok!(Farming::fund_program_from_fees(
+      RuntimeOrigin::root(),
+      PROGRAM,
+      fees
+    ));
+
+    assert_eq!(
+      Balances::free_balance(Farming::program_account(PROGRAM)),
+      fees
+    );
+    assert_eq!(Balances::free_balance(&fee_collector), EXISTENTIAL_DEPOSIT);
+    System::assert_last_event(RuntimeEvent::Farming(
+      pallet_farming::Event::ProgramFunded {
+        program_id: PROGRAM,
+        from: fee_collector,
+        amount: fees,
+      },
+    ));
+  });
+}
+
+/// Test that only the admin can create programs, and only ones that end in the future
+#[test]
+fn test_create_program_validation() {
+  new_test_ext().execute_with(|| {
+    assert_noop!(
+      Farming::create_program(
+        RuntimeOrigin::signed(alice()),
+        LP_TOKEN,
+        AssetKind::Native,
+        REWARD_PER_BLOCK,
+        END_BLOCK,
+      ),
+      DispatchError::BadOrigin
+    );
+    assert_noop!(
+      Farming::create_program(
+        RuntimeOrigin::root(),
+        LP_TOKEN,
+        AssetKind::Native,
+        REWARD_PER_BLOCK,
+        1,
+      ),
+      pallet_farming::Error::<Runtime>::InvalidEndBlock
+    );
+  });
+}
+
+/// Test that programs can only be created for the LP token of an existing pool
+#[test]
+fn test_create_program_requires_pool() {
+  new_test_ext().execute_with(|| {
+    assert_noop!(
+      Farming::create_program(
+        RuntimeOrigin::root(),
+        LP_TOKEN,
+        AssetKind::Native,
+        REWARD_PER_BLOCK,
+        END_BLOCK,
+      ),
+      pallet_farming::Error::<Runtime>::UnknownPoolAsset
+    );
+
+    setup_native_pool(LOCAL_ASSET, LIQUIDITY);
+    assert_noop!(
+      Farming::create_program(
+        RuntimeOrigin::root(),
+        LP_TOKEN + 1,
+        AssetKind::Native,
+        REWARD_PER_BLOCK,
+        END_BLOCK,
+      ),
+      pallet_farming::Error::<Runtime>::UnknownPoolAsset
+    );
+    assert_ok!(Farming::create_program(
+      RuntimeOrigin::root(),
+      LP_TOKEN,
+      AssetKind::Native,
+      REWARD_PER_BLOCK,
+      END_BLOCK,
+    ));
+  });
+}
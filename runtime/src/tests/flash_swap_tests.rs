@@ -0,0 +1,238 @@
+//! Tests for flash swaps through the DEX router.
+//!
+//! The borrower receives the output first, dispatches a call and repays the input afterwards.
+
+use super::dex_router_integration_tests::{
+  alice, bob, charlie, local, new_test_ext, setup_native_pool, LOCAL_ASSET,
+};
+use crate::{
+  configs::AssetKind, AccountId, AssetConversion, Assets, Balance, Balances, DexRouter, Runtime,
+  RuntimeCall, RuntimeEvent, RuntimeOrigin, System, EXISTENTIAL_DEPOSIT,
+};
+use polkadot_sdk::{
+  frame_support::{assert_noop, assert_ok},
+  frame_system, pallet_asset_conversion, pallet_assets,
+  sp_runtime::{BoundedVec, MultiAddress},
+};
+
+const OTHER_ASSET: u32 = 11;
+const LIQUIDITY: Balance = 100_000 * EXISTENTIAL_DEPOSIT;
+const AMOUNT: Balance = 1_000 * EXISTENTIAL_DEPOSIT;
+
+fn remark() -> Box<RuntimeCall> {
+  Box::new(RuntimeCall::System(frame_system::Call::remark {
+    remark: vec![],
+  }))
+}
+
+/// Input and flash fee due for borrowing `amount_out` of the local asset against native
+fn amount_due(amount_out: Balance) -> (Balance, Balance) {
+  let amount_in =
+    DexRouter::quote_tokens_for_exact_tokens(AssetKind::Native, local(), amount_out).unwrap();
+  let flash_fee = <Runtime as pallet_dex_router::Config>::FlashSwapFee::get().mul_ceil(amount_in);
+  (amount_in + flash_fee, flash_fee)
+}
+
+/// Test that the borrower can use the output before repaying the input with the flash fee
+#[test]
+fn test_flash_swap_repaid_after_call() {
+  new_test_ext().execute_with(|| {
+    setup_native_pool(LOCAL_ASSET, LIQUIDITY);
+    let (due, flash_fee) = amount_due(AMOUNT);
+    let native_before = Balances::free_balance(bob());
+
+    // The borrowed output is spent before anything has been paid
+    let call = RuntimeCall::Assets(pallet_assets::Call::transfer {
+      id: LOCAL_ASSET,
+      target: MultiAddress::Id(charlie()),
+      amount: AMOUNT,
+    });
+    assert_ok!(DexRouter::flash_swap(
+      RuntimeOrigin::signed(bob()),
+      AssetKind::Native,
+      local(),
+      AMOUNT,
+      due,
+      Box::new(call),
+    ));
+
+    assert_eq!(Assets::balance(LOCAL_ASSET, charlie()), AMOUNT);
+    assert_eq!(Assets::balance(LOCAL_ASSET, bob()), 0);
+    assert_eq!(Balances::free_balance(bob()), native_before - due);
+    let (_, reserve_out) = AssetConversion::get_reserves(AssetKind::Native, local()).unwrap();
+    assert_eq!(reserve_out, LIQUIDITY - AMOUNT);
+    assert!(!DexRouter::flash_swap_in_progress());
+    System::assert_last_event(RuntimeEvent::DexRouter(
+      pallet_dex_router::Event::FlashSwapExecuted {
+        who: bob(),
+        asset_in: AssetKind::Native,
+        asset_out: local(),
+        amount_in: due,
+        amount_out: AMOUNT,
+        flash_fee,
+      },
+    ));
+  });
+}
+
+/// Test that a borrower who cannot repay gets the whole flash swap reverted
+#[test]
+fn test_unrepaid_flash_swap_reverts() {
+  new_test_ext().execute_with(|| {
+    setup_native_pool(LOCAL_ASSET, LIQUIDITY);
+    let borrower = AccountId::from([9u8; 32]);
+    assert_ok!(Balances::transfer_keep_alive(
+      RuntimeOrigin::signed(alice()),
+      borrower.clone().into(),
+      10 * EXISTENTIAL_DEPOSIT,
+    ));
+
+    assert_noop!(
+      DexRouter::flash_swap(
+        RuntimeOrigin::signed(borrower),
+        AssetKind::Native,
+        local(),
+        AMOUNT,
+        Balance::MAX,
+        remark(),
+      ),
+      pallet_dex_router::Error::<Runtime>::FlashSwapNotRepaid
+    );
+  });
+}
+
+/// Test that failing calls and excessive amounts due revert the flash swap
+#[test]
+fn test_flash_swap_call_failure_and_slippage() {
+  new_test_ext().execute_with(|| {
+    setup_native_pool(LOCAL_ASSET, LIQUIDITY);
+    let (due, _) = amount_due(AMOUNT);
+
+    assert_noop!(
+      DexRouter::flash_swap(
+        RuntimeOrigin::signed(bob()),
+        AssetKind::Native,
+        local(),
+        AMOUNT,
+        due - 1,
+        remark(),
+      ),
+      pallet_dex_router::Error::<Runtime>::SlippageExceeded
+    );
+
+    let call = RuntimeCall::Assets(pallet_assets::Call::transfer {
+      id: LOCAL_ASSET,
+      target: MultiAddress::Id(charlie()),
+      amount: 2 * AMOUNT,
+    });
+    assert_noop!(
+      DexRouter::flash_swap(
+        RuntimeOrigin::signed(bob()),
+        AssetKind::Native,
+        local(),
+        AMOUNT,
+        due,
+        Box::new(call),
+      ),
+      pallet_assets::Error::<Runtime>::BalanceLow
+    );
+  });
+}
+
+/// Test that the lending pool cannot be traded or changed until the flash swap is repaid
+#[test]
+fn test_flash_swap_locks_its_pair() {
+  new_test_ext().execute_with(|| {
+    setup_native_pool(LOCAL_ASSET, LIQUIDITY);
+    let flash_swap = |call: RuntimeCall| {
+      DexRouter::flash_swap(
+        RuntimeOrigin::signed(bob()),
+        AssetKind::Native,
+        local(),
+        AMOUNT,
+        Balance::MAX,
+        Box::new(call),
+      )
+    };
+
+    let swap = RuntimeCall::DexRouter(pallet_dex_router::Call::swap_exact_tokens_for_tokens {
+      path: BoundedVec::try_from(vec![local(), AssetKind::Native]).unwrap(),
+      amount_in: AMOUNT,
+      amount_out_min: 1,
+      send_to: bob(),
+      keep_alive: false,
+    });
+    assert_noop!(
+      flash_swap(swap),
+      pallet_dex_router::Error::<Runtime>::FlashSwapInProgress
+    );
+
+    let add_liquidity = RuntimeCall::DexRouter(pallet_dex_router::Call::add_liquidity {
+      asset1: AssetKind::Native,
+      asset2: local(),
+      amount1_desired: AMOUNT,
+      amount2_desired: AMOUNT,
+      amount1_min: 1,
+      amount2_min: 1,
+    });
+    assert_noop!(
+      flash_swap(add_liquidity),
+      pallet_dex_router::Error::<Runtime>::FlashSwapInProgress
+    );
+
+    let direct_add_liquidity =
+      RuntimeCall::AssetConversion(pallet_asset_conversion::Call::add_liquidity {
+        asset1: Box::new(AssetKind::Native),
+        asset2: Box::new(local()),
+        amount1_desired: AMOUNT,
+        amount2_desired: AMOUNT,
+        amount1_min: 1,
+        amount2_min: 1,
+        mint_to: bob(),
+      });
+    assert_noop!(
+      flash_swap(direct_add_liquidity),
+      frame_system::Error::<Runtime>::CallFiltered
+    );
+
+    let nested = RuntimeCall::DexRouter(pallet_dex_router::Call::flash_swap {
+      asset_in: AssetKind::Native,
+      asset_out: local(),
+      amount_out: AMOUNT,
+      amount_in_max: Balance::MAX,
+      call: remark(),
+    });
+    assert_noop!(
+      flash_swap(nested),
+      pallet_dex_router::Error::<Runtime>::FlashSwapInProgress
+    );
+  });
+}
+
+/// Test that other pairs can be traded with the borrowed funds
+#[test]
+fn test_flash_swap_can_trade_other_pairs() {
+  new_test_ext().execute_with(|| {
+    setup_native_pool(LOCAL_ASSET, LIQUIDITY);
+    setup_native_pool(OTHER_ASSET, LIQUIDITY);
+
+    let swap = RuntimeCall::DexRouter(pallet_dex_router::Call::swap_exact_tokens_for_tokens {
+      path: BoundedVec::try_from(vec![AssetKind::Native, AssetKind::Local(OTHER_ASSET)]).unwrap(),
+      amount_in: AMOUNT,
+      amount_out_min: 1,
+      send_to: bob(),
+      keep_alive: false,
+    });
+    assert_ok!(DexRouter::flash_swap(
+      RuntimeOrigin::signed(bob()),
+      AssetKind::Native,
+      local(),
+      AMOUNT,
+      Balance::MAX,
+      Box::new(swap),
+    ));
+
+    assert_eq!(Assets::balance(LOCAL_ASSET, bob()), AMOUNT);
+    assert!(Assets::balance(OTHER_ASSET, bob()) > 0);
+  });
+}
@@ -0,0 +1,197 @@
+//! Tests for the genesis presets.
+
+use crate::{
+  configs::{AssetKind, RouterFeeCollector},
+  genesis_config_presets::{get_preset, preset_names, DEX_DEV_PRESET},
+  AccountId, AssetConversion, Assets, Balances, DexRouter, PoolAssets, Runtime,
+  RuntimeGenesisConfig, RuntimeOrigin, System, EXISTENTIAL_DEPOSIT, UNIT,
+};
+use polkadot_sdk::{
+  frame_support::{
+    assert_ok,
+    genesis_builder_helper::build_state,
+    storage::{storage_prefix, unhashed},
+    traits::fungibles::metadata::Inspect,
+  },
+  pallet_aura, pallet_collator_selection,
+  sp_genesis_builder::{self, PresetId},
+  sp_io::TestExternalities,
+  sp_keyring::Sr25519Keyring,
+  sp_runtime::{BoundedVec, MultiAddress},
+};
+use serde_json::Value;
+
+/// Merge `patch` into `base` like the node applies a preset: objects are merged key by key, any
+/// other value is replaced
+fn merge(base: &mut Value, patch: Value) {
+  match (base, patch) {
+    (Value::Object(base), Value::Object(patch)) => {
+      for (key, value) in patch {
+        merge(base.entry(key).or_insert(Value::Null), value);
+      }
+    }
+    (base, patch) => *base = patch,
+  }
+}
+
+/// Build the state of preset `id`, patched onto the default genesis config like the node does
+fn preset_ext(id: &PresetId) -> TestExternalities {
+  let patch = get_preset(id).expect("the preset exists");
+  let mut config = serde_json::to_value(RuntimeGenesisConfig::default()).unwrap();
+  merge(&mut config, serde_json::from_slice(&patch).unwrap());
+
+  let mut ext = TestExternalities::default();
+  ext.execute_with(|| {
+    assert_ok!(build_state::<RuntimeGenesisConfig>(
+      serde_json::to_vec(&config).unwrap()
+    ));
+    System::set_block_number(1);
+  });
+  ext
+}
+
+/// Build the state of the `dex_dev` preset
+fn dex_dev_ext() -> TestExternalities {
+  preset_ext(&DEX_DEV_PRESET.into())
+}
+
+/// Test that every preset endows the router fee collector, so fees below the existential
+/// deposit can be received from the first swap
+#[test]
+fn test_presets_endow_router_fee_collector() {
+  for id in preset_names() {
+    preset_ext(&id).execute_with(|| {
+      assert_eq!(
+        Balances::free_balance(RouterFeeCollector::get()),
+        EXISTENTIAL_DEPOSIT,
+        "preset {id:?}"
+      );
+    });
+  }
+}
+
+/// Test that the `dex_dev` preset declares assets with metadata and seeds their pools
+#[test]
+fn test_dex_dev_preset_seeds_pools() {
+  dex_dev_ext().execute_with(|| {
+    assert_eq!(<Assets as Inspect<_>>::symbol(1), b"DUSD".to_vec());
+    assert_eq!(<Assets as Inspect<_>>::symbol(2), b"DTKN".to_vec());
+    assert_eq!(<Assets as Inspect<_>>::decimals(2), 12);
+
+    assert_eq!(
+      AssetConversion::get_reserves(AssetKind::Native, AssetKind::Local(1)).unwrap(),
+      (10_000 * UNIT, 10_000 * UNIT)
+    );
+    assert_eq!(
+      AssetConversion::get_reserves(AssetKind::Native, AssetKind::Local(2)).unwrap(),
+      (10_000 * UNIT, 100_000 * UNIT)
+    );
+    assert_ok!(DexRouter::do_try_state());
+  });
+}
+
+/// The sudo key, `pallet_sudo` has no public accessor for it
+fn sudo_key() -> Option<AccountId> {
+  unhashed::get(&storage_prefix(b"Sudo", b"Key"))
+}
+
+/// Test that the `dex_dev` preset keeps the balances, collators and sudo key of `development`
+#[test]
+fn test_dex_dev_preset_keeps_dev_genesis() {
+  let mut dev = preset_ext(&sp_genesis_builder::DEV_RUNTIME_PRESET.into());
+  let (balances, collators, authorities, key) = dev.execute_with(|| {
+    let balances: Vec<_> = Sr25519Keyring::well_known()
+      .map(|k| Balances::free_balance(k.to_account_id()))
+      .collect();
+    (
+      balances,
+      pallet_collator_selection::Invulnerables::<Runtime>::get(),
+      pallet_aura::Authorities::<Runtime>::get(),
+      sudo_key(),
+    )
+  });
+
+  dex_dev_ext().execute_with(|| {
+    // Seeding the pools took native tokens from Alice only
+    for (k, balance) in Sr25519Keyring::well_known().zip(balances) {
+      let free = Balances::free_balance(k.to_account_id());
+      if k == Sr25519Keyring::Alice {
+        assert!(free > 0 && free < balance);
+      } else {
+        assert_eq!(free, balance, "{k:?}");
+      }
+    }
+    assert_eq!(collators.len(), 2);
+    assert!(collators.contains(&Sr25519Keyring::Alice.to_account_id()));
+    assert!(collators.contains(&Sr25519Keyring::Bob.to_account_id()));
+    assert_eq!(
+      pallet_collator_selection::Invulnerables::<Runtime>::get(),
+      collators
+    );
+    assert_eq!(pallet_aura::Authorities::<Runtime>::get(), authorities);
+    assert_eq!(sudo_key(), key);
+    assert_eq!(key, Some(Sr25519Keyring::Alice.to_account_id()));
+  });
+}
+
+/// Test that endowed accounts can trade through the router right away
+#[test]
+fn test_dex_dev_preset_is_tradable() {
+  dex_dev_ext().execute_with(|| {
+    let bob = Sr25519Keyring::Bob.to_account_id();
+    let dusd_before = Assets::balance(1, &bob);
+
+    assert_ok!(DexRouter::swap_exact_tokens_for_tokens(
+      RuntimeOrigin::signed(bob.clone()),
+      BoundedVec::try_from(vec![AssetKind::Native, AssetKind::Local(1)]).unwrap(),
+      10 * UNIT,
+      1,
+      bob.clone(),
+      false,
+    ));
+
+    assert!(Assets::balance(1, &bob) > dusd_before);
+  });
+}
+
+/// Test that LP tokens live in their own instance and cannot collide with local asset ids
+#[test]
+fn test_lp_tokens_do_not_collide_with_local_assets() {
+  dex_dev_ext().execute_with(|| {
+    let alice = Sr25519Keyring::Alice.to_account_id();
+
+    // The seeded pools minted LP tokens 0 and 1
+    assert!(PoolAssets::balance(0, &alice) > 0);
+    assert!(PoolAssets::balance(1, &alice) > 0);
+
+    // Local asset 0 can still be created and pooled
+    assert_ok!(Assets::create(
+      RuntimeOrigin::signed(alice.clone()),
+      0,
+      MultiAddress::Id(alice.clone()),
+      EXISTENTIAL_DEPOSIT,
+    ));
+    assert_ok!(Assets::mint(
+      RuntimeOrigin::signed(alice.clone()),
+      0,
+      MultiAddress::Id(alice.clone()),
+      1_000 * UNIT,
+    ));
+    assert_ok!(AssetConversion::create_pool(
+      RuntimeOrigin::signed(alice.clone()),
+      Box::new(AssetKind::Native),
+      Box::new(AssetKind::Local(0)),
+    ));
+    assert_ok!(AssetConversion::add_liquidity(
+      RuntimeOrigin::signed(alice.clone()),
+      Box::new(AssetKind::Native),
+      Box::new(AssetKind::Local(0)),
+      100 * UNIT,
+      100 * UNIT,
+      1,
+      1,
+      alice.clone(),
+    ));
+    assert!(PoolAssets::balance(2, &alice) > 0);
+  });
+}
@@ -1,4 +1,19 @@
 //! Runtime integration tests for the parachain.
 
+pub mod asset_tx_payment_tests;
 pub mod dex_integration_tests;
 pub mod dex_router_integration_tests;
+pub mod farming_tests;
+pub mod flash_swap_tests;
+pub mod genesis_preset_tests;
+pub mod pool_assets_migration_tests;
+pub mod router_event_tests;
+pub mod router_liquidity_tests;
+pub mod router_migration_tests;
+pub mod router_view_function_tests;
+pub mod router_xcm_tests;
+pub mod treasury_tests;
+pub mod xcm_remote_swap_tests;
+pub mod xcm_simulator_tests;
+pub mod xcm_transactor_tests;
+pub mod xcm_transfer_tests;
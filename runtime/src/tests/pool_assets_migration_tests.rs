@@ -0,0 +1,116 @@
+//! Tests for the migration moving LP tokens from `Assets` to `PoolAssets`.
+//!
+//! The pool created in each test gets LP token 0 in `PoolAssets`; moving it to `Assets` and
+//! removing the storage version of `PoolAssets` first recreates the state of a chain from before
+//! `PoolAssets` was added. The upgrade is then run by `Executive` like on a real chain.
+
+use super::dex_router_integration_tests::{alice, new_test_ext, setup_native_pool, LOCAL_ASSET};
+use crate::{
+  configs::{AssetKind, PoolAssetsInstance},
+  Assets, Balance, DexRouter, Executive, PoolAssets, Runtime, RuntimeOrigin, EXISTENTIAL_DEPOSIT,
+};
+use polkadot_sdk::{
+  frame_support::{
+    assert_ok,
+    storage::unhashed,
+    traits::{fungibles::Inspect, GetStorageVersion, StorageVersion},
+  },
+  pallet_assets,
+  sp_runtime::MultiAddress,
+};
+
+const LP_TOKEN: u32 = 0;
+const LIQUIDITY: Balance = 100_000 * EXISTENTIAL_DEPOSIT;
+
+/// Move LP token 0 from `PoolAssets` back to `Assets` and remove `PoolAssets` from the state
+fn move_lp_token_to_assets() {
+  let details = pallet_assets::Asset::<Runtime, PoolAssetsInstance>::take(LP_TOKEN).unwrap();
+  pallet_assets::Asset::<Runtime>::insert(LP_TOKEN, details);
+  for (who, account) in
+    pallet_assets::Account::<Runtime, PoolAssetsInstance>::drain_prefix(LP_TOKEN)
+  {
+    pallet_assets::Account::<Runtime>::insert(LP_TOKEN, who, account);
+  }
+  unhashed::kill(&StorageVersion::storage_key::<PoolAssets>());
+}
+
+/// Test that LP tokens held in `Assets` are moved to `PoolAssets` with their balances
+#[test]
+fn test_lp_tokens_moved_to_pool_assets() {
+  new_test_ext().execute_with(|| {
+    setup_native_pool(LOCAL_ASSET, LIQUIDITY);
+    let lp_balance = PoolAssets::balance(LP_TOKEN, alice());
+    let lp_supply = PoolAssets::total_issuance(LP_TOKEN);
+    move_lp_token_to_assets();
+    assert_eq!(Assets::balance(LP_TOKEN, alice()), lp_balance);
+
+    Executive::execute_on_runtime_upgrade();
+
+    assert!(!Assets::asset_exists(LP_TOKEN));
+    assert_eq!(Assets::balance(LP_TOKEN, alice()), 0);
+    assert_eq!(PoolAssets::balance(LP_TOKEN, alice()), lp_balance);
+    assert_eq!(PoolAssets::total_issuance(LP_TOKEN), lp_supply);
+    assert_eq!(PoolAssets::on_chain_storage_version(), 1);
+
+    // The pool works with the moved LP tokens
+    assert_ok!(DexRouter::remove_liquidity(
+      RuntimeOrigin::signed(alice()),
+      AssetKind::Native,
+      AssetKind::Local(LOCAL_ASSET),
+      lp_balance / 2,
+      1,
+      1,
+    ));
+    assert_eq!(
+      PoolAssets::balance(LP_TOKEN, alice()),
+      lp_balance - lp_balance / 2
+    );
+  });
+}
+
+/// Test that a second upgrade leaves moved LP tokens and a local asset reusing their id alone
+#[test]
+fn test_lp_token_migration_is_idempotent() {
+  new_test_ext().execute_with(|| {
+    setup_native_pool(LOCAL_ASSET, LIQUIDITY);
+    let lp_balance = PoolAssets::balance(LP_TOKEN, alice());
+    move_lp_token_to_assets();
+    Executive::execute_on_runtime_upgrade();
+
+    // A local asset may take the id of an LP token once the LP token is moved
+    assert_ok!(Assets::create(
+      RuntimeOrigin::signed(alice()),
+      LP_TOKEN,
+      MultiAddress::Id(alice()),
+      EXISTENTIAL_DEPOSIT,
+    ));
+    assert_ok!(Assets::mint(
+      RuntimeOrigin::signed(alice()),
+      LP_TOKEN,
+      MultiAddress::Id(alice()),
+      1_000 * EXISTENTIAL_DEPOSIT,
+    ));
+
+    Executive::execute_on_runtime_upgrade();
+
+    assert_eq!(
+      Assets::balance(LP_TOKEN, alice()),
+      1_000 * EXISTENTIAL_DEPOSIT
+    );
+    assert_eq!(PoolAssets::balance(LP_TOKEN, alice()), lp_balance);
+  });
+}
+
+/// Test that a chain started with `PoolAssets` is not touched by the upgrade
+#[test]
+fn test_lp_token_migration_skips_new_chains() {
+  new_test_ext().execute_with(|| {
+    setup_native_pool(LOCAL_ASSET, LIQUIDITY);
+    let lp_balance = PoolAssets::balance(LP_TOKEN, alice());
+
+    Executive::execute_on_runtime_upgrade();
+
+    assert!(!Assets::asset_exists(LP_TOKEN));
+    assert_eq!(PoolAssets::balance(LP_TOKEN, alice()), lp_balance);
+  });
+}
@@ -0,0 +1,199 @@
+//! Tests for the events indexers build on: pool hops, fee transfers and fee settings.
+
+use super::dex_router_integration_tests::{
+  bob, new_test_ext, router_swap, setup_native_pool, LOCAL_ASSET,
+};
+use crate::{
+  configs::{AssetKind, RouterFeeCollector, TreasuryAccount, TreasuryFeeShare},
+  AssetConversion, Assets, Balance, Balances, DexRouter, Runtime, RuntimeEvent, RuntimeOrigin,
+  System, EXISTENTIAL_DEPOSIT,
+};
+use codec::{Decode, Encode};
+use pallet_dex_router::{AMMType, Event, FeeSettings};
+use polkadot_sdk::{
+  frame_support::{assert_noop, assert_ok},
+  sp_runtime::{DispatchError, FixedU128, Permill},
+};
+
+const LIQUIDITY: Balance = 100_000 * EXISTENTIAL_DEPOSIT;
+const AMOUNT: Balance = 1_000 * EXISTENTIAL_DEPOSIT;
+
+/// Router events deposited since the last reset
+fn router_events() -> Vec<Event<Runtime>> {
+  System::events()
+    .into_iter()
+    .filter_map(|record| match record.event {
+      RuntimeEvent::DexRouter(event) => Some(event),
+      _ => None,
+    })
+    .collect()
+}
+
+/// Test that a swap reports its hop with the pool reserves after the swap
+#[test]
+fn test_swap_emits_hop_executed() {
+  new_test_ext().execute_with(|| {
+    setup_native_pool(LOCAL_ASSET, LIQUIDITY);
+    System::reset_events();
+
+    assert_ok!(router_swap(
+      &bob(),
+      AssetKind::Native,
+      AssetKind::Local(LOCAL_ASSET),
+      AMOUNT
+    ));
+
+    let amount_in = AMOUNT - Permill::from_parts(2000).mul_floor(AMOUNT);
+    let amount_out = Assets::balance(LOCAL_ASSET, bob());
+    let (reserve_in, reserve_out) =
+      AssetConversion::get_reserves(AssetKind::Native, AssetKind::Local(LOCAL_ASSET)).unwrap();
+    let hop = router_events()
+      .into_iter()
+      .find_map(|event| match event {
+        Event::HopExecuted {
+          pool,
+          asset_in,
+          asset_out,
+          amount_in,
+          amount_out,
+          amm,
+          reserve_in,
+          reserve_out,
+          price,
+        } => {
+          assert_eq!(Assets::balance(LOCAL_ASSET, pool), reserve_out);
+          Some((
+            asset_in,
+            asset_out,
+            amount_in,
+            amount_out,
+            amm,
+            reserve_in,
+            reserve_out,
+            price,
+          ))
+        }
+        _ => None,
+      })
+      .expect("the swap reported its hop");
+    assert_eq!(
+      hop,
+      (
+        AssetKind::Native,
+        AssetKind::Local(LOCAL_ASSET),
+        amount_in,
+        amount_out,
+        AMMType::XYK,
+        reserve_in,
+        reserve_out,
+        FixedU128::from_rational(amount_out, amount_in),
+      )
+    );
+    assert_eq!(reserve_out, LIQUIDITY - amount_out);
+  });
+}
+
+/// Test that every part of the router fee is reported with the account it went to
+#[test]
+fn test_swap_emits_fee_collected() {
+  new_test_ext().execute_with(|| {
+    setup_native_pool(LOCAL_ASSET, LIQUIDITY);
+    // The treasury must exist to receive its share, which is below the existential deposit
+    assert_ok!(Balances::transfer_keep_alive(
+      RuntimeOrigin::signed(bob()),
+      TreasuryAccount::get().into(),
+      EXISTENTIAL_DEPOSIT,
+    ));
+    System::reset_events();
+
+    assert_ok!(router_swap(
+      &bob(),
+      AssetKind::Native,
+      AssetKind::Local(LOCAL_ASSET),
+      AMOUNT
+    ));
+
+    let fee = Permill::from_parts(2000).mul_floor(AMOUNT);
+    let treasury_fee = TreasuryFeeShare::get().mul_floor(fee);
+    let fees: Vec<_> = router_events()
+      .into_iter()
+      .filter(|event| matches!(event, Event::FeeCollected { .. }))
+      .collect();
+    assert_eq!(
+      fees,
+      vec![
+        Event::FeeCollected {
+          asset: AssetKind::Native,
+          amount: treasury_fee,
+          destination: TreasuryAccount::get(),
+        },
+        Event::FeeCollected {
+          asset: AssetKind::Native,
+          amount: fee - treasury_fee,
+          destination: RouterFeeCollector::get(),
+        },
+      ]
+    );
+  });
+}
+
+/// Test that fee settings changes are applied and reported, and only by the admin origin
+#[test]
+fn test_fee_settings_changes_reported() {
+  new_test_ext().execute_with(|| {
+    let settings = DexRouter::fee_settings();
+    assert_eq!(
+      settings,
+      FeeSettings {
+        router_fee: Permill::from_parts(2000),
+        treasury_fee_share: Permill::from_percent(20),
+        flash_swap_fee: Permill::from_parts(1000),
+      }
+    );
+    let new_settings = FeeSettings {
+      treasury_fee_share: Permill::from_percent(50),
+      ..settings
+    };
+
+    assert_noop!(
+      DexRouter::set_fee_settings(RuntimeOrigin::signed(bob()), new_settings),
+      DispatchError::BadOrigin
+    );
+    for invalid in [
+      FeeSettings {
+        router_fee: Permill::one(),
+        ..settings
+      },
+      FeeSettings {
+        flash_swap_fee: Permill::one(),
+        ..settings
+      },
+      // Flash swaps would pay all of their input in fees
+      FeeSettings {
+        router_fee: Permill::from_percent(60),
+        flash_swap_fee: Permill::from_percent(40),
+        ..settings
+      },
+    ] {
+      assert_noop!(
+        DexRouter::set_fee_settings(RuntimeOrigin::root(), invalid),
+        pallet_dex_router::Error::<Runtime>::InvalidFeeSettings
+      );
+    }
+    // A treasury share above the whole router fee cannot even be submitted
+    let encoded = (settings.router_fee, 1_000_001u32, settings.flash_swap_fee).encode();
+    assert!(FeeSettings::decode(&mut &encoded[..]).is_err());
+
+    assert_ok!(DexRouter::set_fee_settings(
+      RuntimeOrigin::root(),
+      new_settings
+    ));
+    assert_eq!(DexRouter::fee_settings(), new_settings);
+    assert_eq!(
+      router_events(),
+      vec![Event::FeeSettingsChanged {
+        settings: new_settings
+      }]
+    );
+  });
+}
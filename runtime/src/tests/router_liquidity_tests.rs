@@ -0,0 +1,296 @@
+//! Tests for liquidity management through the DEX router.
+//!
+//! The first pool created in a test gets LP token 0 in `PoolAssets`.
+
+use super::dex_router_integration_tests::{
+  alice, bob, create_test_asset, local, mint_tokens, new_test_ext, setup_native_pool, LOCAL_ASSET,
+};
+use crate::{
+  configs::AssetKind, AssetConversion, Assets, Balance, Balances, DexRouter, PoolAssets, Runtime,
+  RuntimeEvent, RuntimeOrigin, System, EXISTENTIAL_DEPOSIT,
+};
+use polkadot_sdk::frame_support::{assert_noop, assert_ok};
+
+const LP_TOKEN: u32 = 0;
+const LIQUIDITY: Balance = 100_000 * EXISTENTIAL_DEPOSIT;
+const AMOUNT: Balance = 1_000 * EXISTENTIAL_DEPOSIT;
+
+/// Test that adding liquidity through the router creates the pool and mints LP tokens
+#[test]
+fn test_add_and_remove_liquidity() {
+  new_test_ext().execute_with(|| {
+    assert_ok!(create_test_asset(
+      LOCAL_ASSET,
+      &alice(),
+      EXISTENTIAL_DEPOSIT
+    ));
+    assert_ok!(mint_tokens(LOCAL_ASSET, &alice(), &alice(), 2 * LIQUIDITY));
+
+    assert_ok!(DexRouter::add_liquidity(
+      RuntimeOrigin::signed(alice()),
+      AssetKind::Native,
+      local(),
+      LIQUIDITY,
+      LIQUIDITY,
+      1,
+      1,
+    ));
+    assert_eq!(
+      AssetConversion::get_reserves(AssetKind::Native, local()).unwrap(),
+      (LIQUIDITY, LIQUIDITY)
+    );
+    let lp_minted = PoolAssets::balance(LP_TOKEN, alice());
+    assert!(lp_minted > 0);
+    assert_eq!(Assets::balance(LOCAL_ASSET, alice()), LIQUIDITY);
+
+    // Adding to the existing pool takes the amounts at its ratio
+    assert_ok!(DexRouter::add_liquidity(
+      RuntimeOrigin::signed(alice()),
+      AssetKind::Native,
+      local(),
+      2 * AMOUNT,
+      AMOUNT,
+      1,
+      1,
+    ));
+    assert_eq!(
+      AssetConversion::get_reserves(AssetKind::Native, local()).unwrap(),
+      (LIQUIDITY + AMOUNT, LIQUIDITY + AMOUNT)
+    );
+
+    let native_before = Balances::free_balance(alice());
+    assert_ok!(DexRouter::remove_liquidity(
+      RuntimeOrigin::signed(alice()),
+      AssetKind::Native,
+      local(),
+      lp_minted,
+      1,
+      1,
+    ));
+    assert!(Balances::free_balance(alice()) > native_before);
+    assert!(Assets::balance(LOCAL_ASSET, alice()) > LIQUIDITY);
+    assert!(PoolAssets::balance(LP_TOKEN, alice()) < lp_minted);
+  });
+}
+
+/// Test that zapping in deposits a single asset as liquidity with little left over
+#[test]
+fn test_zap_in() {
+  new_test_ext().execute_with(|| {
+    setup_native_pool(LOCAL_ASSET, LIQUIDITY);
+    let native_before = Balances::free_balance(bob());
+
+    assert_ok!(DexRouter::zap_in(
+      RuntimeOrigin::signed(bob()),
+      AssetKind::Native,
+      local(),
+      AMOUNT,
+      1,
+    ));
+
+    let lp_minted = PoolAssets::balance(LP_TOKEN, bob());
+    assert!(lp_minted > 0);
+    // The pool takes nearly all of the input and the swap output, at its ratio
+    let native_spent = native_before - Balances::free_balance(bob());
+    assert!(native_spent <= AMOUNT && native_spent > AMOUNT * 99 / 100);
+    assert!(Assets::balance(LOCAL_ASSET, bob()) < AMOUNT / 100);
+    System::assert_last_event(RuntimeEvent::DexRouter(
+      pallet_dex_router::Event::ZappedIn {
+        who: bob(),
+        asset_in: AssetKind::Native,
+        asset_other: local(),
+        amount_in: AMOUNT,
+        lp_minted,
+      },
+    ));
+  });
+}
+
+/// Test that zapping out withdraws liquidity entirely as the chosen asset
+#[test]
+fn test_zap_out() {
+  new_test_ext().execute_with(|| {
+    setup_native_pool(LOCAL_ASSET, LIQUIDITY);
+    assert_ok!(DexRouter::zap_in(
+      RuntimeOrigin::signed(bob()),
+      AssetKind::Native,
+      local(),
+      AMOUNT,
+      1,
+    ));
+    let lp_minted = PoolAssets::balance(LP_TOKEN, bob());
+    let local_before = Assets::balance(LOCAL_ASSET, bob());
+    let native_before = Balances::free_balance(bob());
+
+    assert_ok!(DexRouter::zap_out(
+      RuntimeOrigin::signed(bob()),
+      AssetKind::Native,
+      local(),
+      lp_minted,
+      AssetKind::Native,
+      1,
+    ));
+
+    assert_eq!(PoolAssets::balance(LP_TOKEN, bob()), 0);
+    assert_eq!(Assets::balance(LOCAL_ASSET, bob()), local_before);
+    let amount_out = Balances::free_balance(bob()) - native_before;
+    // A round trip only loses the fees of the two swaps
+    assert!(amount_out < AMOUNT && amount_out > AMOUNT * 99 / 100);
+    System::assert_last_event(RuntimeEvent::DexRouter(
+      pallet_dex_router::Event::ZappedOut {
+        who: bob(),
+        asset1: AssetKind::Native,
+        asset2: local(),
+        lp_burned: lp_minted,
+        asset_out: AssetKind::Native,
+        amount_out,
+      },
+    ));
+  });
+}
+
+/// Test that zaps reject invalid assets and enforce the requested minimums
+#[test]
+fn test_zap_validation_and_slippage() {
+  new_test_ext().execute_with(|| {
+    setup_native_pool(LOCAL_ASSET, LIQUIDITY);
+
+    assert_noop!(
+      DexRouter::zap_in(
+        RuntimeOrigin::signed(bob()),
+        AssetKind::Native,
+        AssetKind::Native,
+        AMOUNT,
+        1,
+      ),
+      pallet_dex_router::Error::<Runtime>::InvalidZap
+    );
+    assert_noop!(
+      DexRouter::zap_in(
+        RuntimeOrigin::signed(bob()),
+        AssetKind::Native,
+        local(),
+        AMOUNT,
+        AMOUNT,
+      ),
+      pallet_dex_router::Error::<Runtime>::SlippageExceeded
+    );
+
+    let lp_balance = PoolAssets::balance(LP_TOKEN, alice());
+    assert_noop!(
+      DexRouter::zap_out(
+        RuntimeOrigin::signed(alice()),
+        AssetKind::Native,
+        local(),
+        lp_balance / 10,
+        AssetKind::Local(LOCAL_ASSET + 1),
+        1,
+      ),
+      pallet_dex_router::Error::<Runtime>::InvalidZap
+    );
+    assert_noop!(
+      DexRouter::zap_out(
+        RuntimeOrigin::signed(alice()),
+        AssetKind::Native,
+        local(),
+        lp_balance / 100,
+        AssetKind::Native,
+        LIQUIDITY,
+      ),
+      pallet_dex_router::Error::<Runtime>::SlippageExceeded
+    );
+  });
+}
+
+/// Test that zaps are blocked on a paused pair while plain withdrawals are not
+#[test]
+fn test_zaps_respect_pair_pause() {
+  new_test_ext().execute_with(|| {
+    setup_native_pool(LOCAL_ASSET, LIQUIDITY);
+    assert_ok!(DexRouter::set_pair_paused(
+      RuntimeOrigin::root(),
+      AssetKind::Native,
+      local(),
+      true
+    ));
+
+    assert_noop!(
+      DexRouter::zap_in(
+        RuntimeOrigin::signed(bob()),
+        AssetKind::Native,
+        local(),
+        AMOUNT,
+        1,
+      ),
+      pallet_dex_router::Error::<Runtime>::PairPaused
+    );
+    let lp_balance = PoolAssets::balance(LP_TOKEN, alice());
+    assert_noop!(
+      DexRouter::zap_out(
+        RuntimeOrigin::signed(alice()),
+        AssetKind::Native,
+        local(),
+        lp_balance / 10,
+        AssetKind::Native,
+        1,
+      ),
+      pallet_dex_router::Error::<Runtime>::PairPaused
+    );
+
+    assert_ok!(DexRouter::remove_liquidity(
+      RuntimeOrigin::signed(alice()),
+      AssetKind::Native,
+      local(),
+      lp_balance / 10,
+      1,
+      1,
+    ));
+  });
+}
+
+/// Test that liquidity cannot be added while the router or the pair is paused, but can be removed
+#[test]
+fn test_add_liquidity_rejected_while_paused() {
+  new_test_ext().execute_with(|| {
+    setup_native_pool(LOCAL_ASSET, LIQUIDITY);
+    let add = || {
+      DexRouter::add_liquidity(
+        RuntimeOrigin::signed(alice()),
+        AssetKind::Native,
+        local(),
+        AMOUNT,
+        AMOUNT,
+        1,
+        1,
+      )
+    };
+
+    assert_ok!(DexRouter::set_router_paused(RuntimeOrigin::root(), true));
+    assert_noop!(add(), pallet_dex_router::Error::<Runtime>::RouterPaused);
+    assert_ok!(DexRouter::set_router_paused(RuntimeOrigin::root(), false));
+
+    assert_ok!(DexRouter::set_pair_paused(
+      RuntimeOrigin::root(),
+      local(),
+      AssetKind::Native,
+      true
+    ));
+    assert_noop!(add(), pallet_dex_router::Error::<Runtime>::PairPaused);
+    assert_ok!(DexRouter::remove_liquidity(
+      RuntimeOrigin::signed(alice()),
+      AssetKind::Native,
+      local(),
+      PoolAssets::balance(LP_TOKEN, alice()) / 10,
+      1,
+      1,
+    ));
+
+    assert_ok!(DexRouter::set_pair_paused(
+      RuntimeOrigin::root(),
+      local(),
+      AssetKind::Native,
+      false
+    ));
+    assert_ok!(add());
+  });
+}
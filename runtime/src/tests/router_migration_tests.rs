@@ -0,0 +1,35 @@
+//! Tests for the DEX router storage migrations registered in the runtime.
+
+use super::dex_router_integration_tests::new_test_ext;
+use crate::{DexRouter, Migrations, Runtime};
+use pallet_dex_router::migrations::v1::Something;
+use polkadot_sdk::frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+/// Test that the v1 migration removes `Something` and bumps the storage version
+#[test]
+fn test_migration_v1_removes_something() {
+  new_test_ext().execute_with(|| {
+    StorageVersion::new(0).put::<DexRouter>();
+    Something::<Runtime>::put(42);
+
+    Migrations::on_runtime_upgrade();
+
+    assert!(!Something::<Runtime>::exists());
+    assert_eq!(DexRouter::on_chain_storage_version(), 1);
+  });
+}
+
+/// Test that the v1 migration is skipped once the router is at version 1
+#[test]
+fn test_migration_v1_is_idempotent() {
+  new_test_ext().execute_with(|| {
+    StorageVersion::new(1).put::<DexRouter>();
+    Something::<Runtime>::put(42);
+
+    Migrations::on_runtime_upgrade();
+
+    // Not touched, since the versioned migration only runs from version 0
+    assert_eq!(Something::<Runtime>::get(), Some(42));
+    assert_eq!(DexRouter::on_chain_storage_version(), 1);
+  });
+}
@@ -0,0 +1,135 @@
+//! Tests for the router view functions, queried directly and through the generic
+//! view-function runtime API.
+
+use super::dex_router_integration_tests::{
+  alice, local, new_test_ext, setup_native_pool, LOCAL_ASSET,
+};
+use crate::{
+  configs::AssetKind, Assets, Balance, DexRouter, Runtime, RuntimeOrigin, EXISTENTIAL_DEPOSIT,
+};
+use codec::{Decode, Encode};
+use pallet_dex_router::{
+  AMMType, BestRouteViewFunction, FeeSettings, FeeSettingsViewFunction, PauseStatus,
+  PauseStatusViewFunction, QuoteTokensForExactTokensViewFunction, SwapRoute,
+};
+use polkadot_sdk::{
+  frame_support::{assert_ok, view_functions::ViewFunction},
+  sp_runtime::Permill,
+};
+
+const OTHER_ASSET: u32 = 11;
+const LIQUIDITY: Balance = 100_000 * EXISTENTIAL_DEPOSIT;
+const AMOUNT: Balance = 1_000 * EXISTENTIAL_DEPOSIT;
+
+/// Execute a view function through the runtime API, as a client would
+fn query<Q: ViewFunction + Encode, V: Decode>(query: Q) -> V {
+  let output =
+    Runtime::execute_view_function(Q::id(), query.encode()).expect("view function exists");
+  V::decode(&mut &output[..]).expect("output decodes")
+}
+
+/// Test that the best route and both quote directions agree with each other
+#[test]
+fn test_quotes_and_best_route() {
+  new_test_ext().execute_with(|| {
+    assert_eq!(
+      DexRouter::best_route(AssetKind::Native, local(), AMOUNT),
+      None
+    );
+    setup_native_pool(LOCAL_ASSET, LIQUIDITY);
+
+    let amount_out =
+      DexRouter::quote_exact_tokens_for_tokens(AssetKind::Native, local(), AMOUNT).unwrap();
+    assert_eq!(
+      DexRouter::best_route(AssetKind::Native, local(), AMOUNT),
+      Some(SwapRoute {
+        path: vec![AssetKind::Native, local()],
+        amms: vec![AMMType::XYK],
+        amount_out,
+      })
+    );
+
+    // Buying the quoted output costs at most the input it was quoted for
+    let amount_in =
+      DexRouter::quote_tokens_for_exact_tokens(AssetKind::Native, local(), amount_out).unwrap();
+    assert!(amount_in <= AMOUNT);
+  });
+}
+
+/// Test that assets without a pool between them are routed through an asset pooled with both,
+/// and that the route quotes what a swap along it receives
+#[test]
+fn test_best_route_through_pooled_asset() {
+  new_test_ext().execute_with(|| {
+    let other = AssetKind::Local(OTHER_ASSET);
+    setup_native_pool(LOCAL_ASSET, LIQUIDITY);
+    setup_native_pool(OTHER_ASSET, LIQUIDITY);
+
+    let amount_native =
+      DexRouter::quote_exact_tokens_for_tokens(local(), AssetKind::Native, AMOUNT).unwrap();
+    let amount_out =
+      DexRouter::quote_exact_tokens_for_tokens(AssetKind::Native, other.clone(), amount_native)
+        .unwrap();
+    let route = DexRouter::best_route(local(), other.clone(), AMOUNT).unwrap();
+    assert_eq!(
+      route,
+      SwapRoute {
+        path: vec![local(), AssetKind::Native, other.clone()],
+        amms: vec![AMMType::XYK, AMMType::XYK],
+        amount_out,
+      }
+    );
+
+    let other_before = Assets::balance(OTHER_ASSET, alice());
+    assert_ok!(DexRouter::swap_exact_tokens_for_tokens(
+      RuntimeOrigin::signed(alice()),
+      route.path.try_into().unwrap(),
+      AMOUNT,
+      amount_out,
+      alice(),
+      false,
+    ));
+    assert_eq!(
+      Assets::balance(OTHER_ASSET, alice()),
+      other_before + amount_out
+    );
+  });
+}
+
+/// Test that clients get the same answers through the generic view-function API
+#[test]
+fn test_view_functions_through_runtime_api() {
+  new_test_ext().execute_with(|| {
+    setup_native_pool(LOCAL_ASSET, LIQUIDITY);
+
+    let route: Option<SwapRoute<AssetKind, Balance>> = query(
+      BestRouteViewFunction::<Runtime>::new(AssetKind::Native, local(), AMOUNT),
+    );
+    assert_eq!(
+      route,
+      DexRouter::best_route(AssetKind::Native, local(), AMOUNT)
+    );
+
+    let amount_in: Option<Balance> = query(QuoteTokensForExactTokensViewFunction::<Runtime>::new(
+      AssetKind::Native,
+      local(),
+      AMOUNT,
+    ));
+    assert_eq!(
+      amount_in,
+      DexRouter::quote_tokens_for_exact_tokens(AssetKind::Native, local(), AMOUNT)
+    );
+
+    let settings: FeeSettings = query(FeeSettingsViewFunction::<Runtime>::new());
+    assert_eq!(settings.router_fee, Permill::from_parts(2000));
+    assert_eq!(settings, DexRouter::fee_settings());
+
+    assert_ok!(DexRouter::set_router_paused(RuntimeOrigin::root(), true));
+    let status: PauseStatus = query(PauseStatusViewFunction::<Runtime>::new(
+      AssetKind::Native,
+      local(),
+    ));
+    assert!(status.router_paused);
+    assert!(!status.pair_paused);
+  });
+}
@@ -0,0 +1,507 @@
+//! Tests for the XCM `AssetExchanger` backed by the DEX router.
+//!
+//! These drive `RouterAssetExchanger` the way the XCM executor does when processing an
+//! `ExchangeAsset` instruction: assets arrive in holding, are swapped through the router and the
+//! result is handed back to holding for the rest of the program (e.g. `DepositAsset`).
+//!
+//! The XCM `Trader` is exercised the same way, buying execution weight with assets in holding.
+
+use super::dex_router_integration_tests::{
+  bob, new_test_ext, router_swap, setup_foreign_pool, setup_native_pool, LOCAL_ASSET,
+};
+use crate::{
+  configs::{
+    xcm_config::{
+      AssetKindLocationConverter, AssetsPalletIndex, DexExchangeAccount, RouterAssetExchanger,
+      StakingPot, XcmConfig,
+    },
+    AssetKind, TreasuryAccount, TreasuryFeeShare,
+  },
+  AssetConversion, Assets as LocalAssets, Balance, Balances, DexRouter, ForeignAssets,
+  RuntimeOrigin, System, WeightToFee, EXISTENTIAL_DEPOSIT,
+};
+use polkadot_sdk::{
+  frame_support::{
+    assert_ok,
+    weights::{Weight, WeightToFee as _},
+  },
+  sp_runtime::{traits::MaybeEquivalence, MultiAddress, Permill},
+  staging_xcm::latest::prelude::*,
+  staging_xcm_executor::{
+    traits::{AssetExchange, TransactAsset, WeightTrader},
+    AssetsInHolding, Config as XcmExecutorConfig,
+  },
+};
+
+type Trader = <XcmConfig as XcmExecutorConfig>::Trader;
+
+fn fee_collector() -> crate::AccountId {
+  crate::AccountId::from([0u8; 32])
+}
+
+/// Endow `who` with the existential deposit, so it can receive fees in any asset
+fn endow(who: crate::AccountId) {
+  assert_ok!(Balances::transfer_keep_alive(
+    RuntimeOrigin::signed(bob()),
+    who.into(),
+    EXISTENTIAL_DEPOSIT,
+  ));
+}
+
+fn local_location(id: u32) -> Location {
+  Location::new(
+    0,
+    [
+      PalletInstance(AssetsPalletIndex::get()),
+      GeneralIndex(id.into()),
+    ],
+  )
+}
+
+fn holding(location: Location, amount: Balance) -> AssetsInHolding {
+  Asset::from((location, amount)).into()
+}
+
+fn wanted(location: Location, amount: Balance) -> Assets {
+  Asset::from((location, amount)).into()
+}
+
+fn sibling_context() -> XcmContext {
+  XcmContext {
+    origin: Some(Location::new(1, [Parachain(2000)])),
+    message_id: [0u8; 32],
+    topic: None,
+  }
+}
+
+fn fungible_amount(holding: &AssetsInHolding, location: Location) -> Balance {
+  holding
+    .fungible
+    .get(&AssetId(location))
+    .copied()
+    .unwrap_or_default()
+}
+
+/// Test that asset locations map to router asset kinds and back
+#[test]
+fn test_asset_kind_location_conversion() {
+  new_test_ext().execute_with(|| {
+    assert_eq!(
+      AssetKindLocationConverter::convert(&Location::here()),
+      Some(AssetKind::Native)
+    );
+    assert_eq!(
+      AssetKindLocationConverter::convert(&local_location(LOCAL_ASSET)),
+      Some(AssetKind::Local(LOCAL_ASSET))
+    );
+    assert_eq!(
+      AssetKindLocationConverter::convert_back(&AssetKind::Local(LOCAL_ASSET)),
+      Some(local_location(LOCAL_ASSET))
+    );
+
+    // Assets of other consensus systems are foreign assets
+    let sibling_token = Location::new(1, [Parachain(2000)]);
+    assert_eq!(
+      AssetKindLocationConverter::convert(&sibling_token),
+      Some(AssetKind::Foreign(sibling_token.clone()))
+    );
+    assert_eq!(
+      AssetKindLocationConverter::convert_back(&AssetKind::Foreign(sibling_token.clone())),
+      Some(sibling_token)
+    );
+
+    // The relay token is a foreign asset too, never the native token
+    assert_eq!(
+      AssetKindLocationConverter::convert(&Location::parent()),
+      Some(AssetKind::Foreign(Location::parent()))
+    );
+
+    // Other pallets' assets are not routable
+
+    assert_eq!(
+      AssetKindLocationConverter::convert(&Location::new(
+        0,
+        [
+          PalletInstance(AssetsPalletIndex::get() + 1),
+          GeneralIndex(1)
+        ]
+      )),
+      None
+    );
+  });
+}
+
+/// Test that an exchange swaps through the router and applies the router fee
+#[test]
+fn test_exchange_native_for_local_asset() {
+  new_test_ext().execute_with(|| {
+    setup_native_pool(LOCAL_ASSET, 100_000 * EXISTENTIAL_DEPOSIT);
+    let amount_in = 1_000 * EXISTENTIAL_DEPOSIT;
+    let router_fee = Permill::from_parts(2000).mul_floor(amount_in);
+    let treasury_fee = TreasuryFeeShare::get().mul_floor(router_fee);
+    endow(TreasuryAccount::get());
+    let collector_before = Balances::free_balance(fee_collector());
+    let treasury_before = Balances::free_balance(TreasuryAccount::get());
+
+    let quote = DexRouter::quote_exact_tokens_for_tokens(
+      AssetKind::Native,
+      AssetKind::Local(LOCAL_ASSET),
+      amount_in,
+    )
+    .unwrap();
+    assert_eq!(
+      RouterAssetExchanger::quote_exchange_price(
+        &wanted(Location::here(), amount_in),
+        &wanted(local_location(LOCAL_ASSET), 1),
+        true,
+      ),
+      Some(wanted(local_location(LOCAL_ASSET), quote))
+    );
+
+    let result = RouterAssetExchanger::exchange_asset(
+      None,
+      holding(Location::here(), amount_in),
+      &wanted(local_location(LOCAL_ASSET), 1),
+      true,
+    );
+
+    let received = result.expect("exchange should succeed");
+    assert_eq!(
+      fungible_amount(&received, local_location(LOCAL_ASSET)),
+      quote
+    );
+    assert_eq!(
+      Balances::free_balance(fee_collector()),
+      collector_before + router_fee - treasury_fee
+    );
+    assert_eq!(
+      Balances::free_balance(TreasuryAccount::get()),
+      treasury_before + treasury_fee
+    );
+    // Nothing is left behind in the exchange account
+    assert_eq!(
+      LocalAssets::balance(LOCAL_ASSET, DexExchangeAccount::get()),
+      0
+    );
+  });
+}
+
+/// Test that the router fee is collected and split in the local asset when it is given
+#[test]
+fn test_exchange_local_asset_for_native() {
+  new_test_ext().execute_with(|| {
+    setup_native_pool(LOCAL_ASSET, 100_000 * EXISTENTIAL_DEPOSIT);
+    let amount_in = 1_000 * EXISTENTIAL_DEPOSIT;
+    let router_fee = Permill::from_parts(2000).mul_floor(amount_in);
+    let treasury_fee = TreasuryFeeShare::get().mul_floor(router_fee);
+    endow(fee_collector());
+
+    let received = RouterAssetExchanger::exchange_asset(
+      None,
+      holding(local_location(LOCAL_ASSET), amount_in),
+      &wanted(Location::here(), 1),
+      true,
+    )
+    .expect("exchange should succeed");
+
+    assert!(fungible_amount(&received, Location::here()) > 0);
+    assert_eq!(
+      LocalAssets::balance(LOCAL_ASSET, fee_collector()),
+      router_fee - treasury_fee
+    );
+    assert_eq!(
+      LocalAssets::balance(LOCAL_ASSET, TreasuryAccount::get()),
+      treasury_fee
+    );
+  });
+}
+
+/// Test that the exchange account keeps only its genesis endowment, without extra references
+#[test]
+fn test_exchange_account_keeps_endowment() {
+  new_test_ext().execute_with(|| {
+    setup_native_pool(LOCAL_ASSET, 100_000 * EXISTENTIAL_DEPOSIT);
+    let amount_in = 1_000 * EXISTENTIAL_DEPOSIT;
+    endow(fee_collector());
+    let account = DexExchangeAccount::get();
+    let account_before = System::account(&account);
+
+    for (give, want) in [
+      (Location::here(), local_location(LOCAL_ASSET)),
+      (local_location(LOCAL_ASSET), Location::here()),
+    ] {
+      assert!(RouterAssetExchanger::exchange_asset(
+        None,
+        holding(give, amount_in),
+        &wanted(want, 1),
+        true,
+      )
+      .is_ok());
+    }
+
+    let account_after = System::account(&account);
+    assert_eq!(account_after.data.free, EXISTENTIAL_DEPOSIT);
+    assert_eq!(account_after.providers, account_before.providers);
+    assert_eq!(account_after.consumers, 0);
+  });
+}
+
+/// Test that exchanges of non-sufficient assets fail until the exchange account is endowed
+#[test]
+fn test_exchange_requires_endowed_account() {
+  new_test_ext().execute_with(|| {
+    setup_native_pool(LOCAL_ASSET, 100_000 * EXISTENTIAL_DEPOSIT);
+    assert_ok!(Balances::force_set_balance(
+      RuntimeOrigin::root(),
+      MultiAddress::Id(DexExchangeAccount::get()),
+      0,
+    ));
+    let give = holding(local_location(LOCAL_ASSET), 1_000 * EXISTENTIAL_DEPOSIT);
+
+    let result =
+      RouterAssetExchanger::exchange_asset(None, give.clone(), &wanted(Location::here(), 1), true);
+    assert_eq!(result.unwrap_err(), give);
+    assert!(!System::account_exists(&DexExchangeAccount::get()));
+  });
+}
+
+/// Test that failed exchanges hand the original assets back to holding untouched
+#[test]
+fn test_exchange_failure_returns_given_assets() {
+  new_test_ext().execute_with(|| {
+    setup_native_pool(LOCAL_ASSET, 100_000 * EXISTENTIAL_DEPOSIT);
+    let amount_in = 1_000 * EXISTENTIAL_DEPOSIT;
+    let give = holding(Location::here(), amount_in);
+
+    // Minimum output cannot be met
+    let result = RouterAssetExchanger::exchange_asset(
+      None,
+      give.clone(),
+      &wanted(local_location(LOCAL_ASSET), amount_in),
+      true,
+    );
+    assert_eq!(result.unwrap_err(), give);
+    assert_eq!(
+      LocalAssets::balance(LOCAL_ASSET, DexExchangeAccount::get()),
+      0
+    );
+
+    // Exact-out exchanges are not supported
+    let result = RouterAssetExchanger::exchange_asset(
+      None,
+      give.clone(),
+      &wanted(local_location(LOCAL_ASSET), 1),
+      false,
+    );
+    assert_eq!(result.unwrap_err(), give);
+
+    // Unknown assets cannot be routed
+    let result = RouterAssetExchanger::exchange_asset(
+      None,
+      give.clone(),
+      &wanted(Location::parent(), 1),
+      true,
+    );
+    assert_eq!(result.unwrap_err(), give);
+  });
+}
+
+/// Test that exchanges respect the router pause just like signed swaps
+#[test]
+fn test_exchange_respects_router_pause() {
+  new_test_ext().execute_with(|| {
+    setup_native_pool(LOCAL_ASSET, 100_000 * EXISTENTIAL_DEPOSIT);
+    assert_ok!(router_swap(
+      &bob(),
+      AssetKind::Native,
+      AssetKind::Local(LOCAL_ASSET),
+      1_000 * EXISTENTIAL_DEPOSIT
+    ));
+    assert_ok!(DexRouter::set_router_paused(RuntimeOrigin::root(), true));
+
+    let give = holding(Location::here(), 1_000 * EXISTENTIAL_DEPOSIT);
+    let result = RouterAssetExchanger::exchange_asset(
+      Some(&Location::new(1, [Parachain(2000)])),
+      give.clone(),
+      &wanted(local_location(LOCAL_ASSET), 1),
+      true,
+    );
+    assert_eq!(result.unwrap_err(), give);
+  });
+}
+
+/// Test that XCM execution can be paid for with an asset pooled against native
+#[test]
+fn test_trader_buys_weight_with_pooled_asset() {
+  new_test_ext().execute_with(|| {
+    setup_native_pool(LOCAL_ASSET, 100_000 * EXISTENTIAL_DEPOSIT);
+    assert_ok!(Balances::force_set_balance(
+      RuntimeOrigin::root(),
+      MultiAddress::Id(StakingPot::get()),
+      EXISTENTIAL_DEPOSIT,
+    ));
+
+    // A heavy program, so that fees are well above the minimum balances
+    let weight = Weight::from_parts(500_000_000_000, 0);
+    let native_fee = WeightToFee::weight_to_fee(&weight);
+    let asset_fee = DexRouter::quote_tokens_for_exact_tokens(
+      AssetKind::Local(LOCAL_ASSET),
+      AssetKind::Native,
+      native_fee,
+    )
+    .unwrap();
+    // The router fee is paid on top of what the pool takes
+    let pool_fee = AssetConversion::quote_price_tokens_for_exact_tokens(
+      AssetKind::Local(LOCAL_ASSET),
+      AssetKind::Native,
+      native_fee,
+      true,
+    )
+    .unwrap();
+    assert!(asset_fee > pool_fee);
+    let payment = 100 * EXISTENTIAL_DEPOSIT;
+    assert!(asset_fee < payment);
+
+    let mut trader = Trader::new();
+    let unused = trader
+      .buy_weight(
+        weight,
+        holding(local_location(LOCAL_ASSET), payment),
+        &sibling_context(),
+      )
+      .expect("pooled asset should pay for weight");
+
+    // Only what is needed to cover the weight is swapped
+    assert_eq!(
+      fungible_amount(&unused, local_location(LOCAL_ASSET)),
+      payment - asset_fee
+    );
+
+    // Unused weight is refunded in the paying asset
+    let refund = trader
+      .refund_weight(weight / 2, &sibling_context())
+      .expect("unused weight should be refunded");
+    assert_eq!(refund.id, AssetId(local_location(LOCAL_ASSET)));
+    assert!(matches!(refund.fun, Fungible(amount) if amount > 0 && amount < asset_fee));
+
+    // The remaining native fee goes to the collator pot once the trader is dropped
+    drop(trader);
+    let refunded_native = WeightToFee::weight_to_fee(&(weight / 2));
+    assert_eq!(
+      Balances::free_balance(StakingPot::get()),
+      EXISTENTIAL_DEPOSIT + native_fee - refunded_native
+    );
+  });
+}
+
+/// Test that execution cannot be paid for with a pooled asset while the router is paused
+#[test]
+fn test_trader_respects_router_pause() {
+  new_test_ext().execute_with(|| {
+    setup_native_pool(LOCAL_ASSET, 100_000 * EXISTENTIAL_DEPOSIT);
+    assert_ok!(DexRouter::set_router_paused(RuntimeOrigin::root(), true));
+    let weight = Weight::from_parts(500_000_000_000, 0);
+
+    let mut trader = Trader::new();
+    assert!(trader
+      .buy_weight(
+        weight,
+        holding(local_location(LOCAL_ASSET), 100 * EXISTENTIAL_DEPOSIT),
+        &sibling_context(),
+      )
+      .is_err());
+  });
+}
+
+/// Test that assets without a native pool cannot pay for execution
+#[test]
+fn test_trader_rejects_unpooled_asset() {
+  new_test_ext().execute_with(|| {
+    setup_native_pool(LOCAL_ASSET, 100_000 * EXISTENTIAL_DEPOSIT);
+    let weight = Weight::from_parts(500_000_000_000, 0);
+
+    let mut trader = Trader::new();
+    assert!(trader
+      .buy_weight(
+        weight,
+        holding(local_location(LOCAL_ASSET + 1), 100 * EXISTENTIAL_DEPOSIT),
+        &sibling_context(),
+      )
+      .is_err());
+
+    // Locations that are not routable at all are rejected too
+    let mut trader = Trader::new();
+    assert!(trader
+      .buy_weight(
+        weight,
+        holding(
+          Location::new(1, [Parachain(2000)]),
+          100 * EXISTENTIAL_DEPOSIT
+        ),
+        &sibling_context(),
+      )
+      .is_err());
+  });
+}
+
+/// Test that foreign assets arriving over XCM are credited to `ForeignAssets`
+#[test]
+fn test_foreign_asset_transactor_deposits() {
+  new_test_ext().execute_with(|| {
+    let sibling_token = Location::new(1, [Parachain(2000)]);
+    setup_foreign_pool(sibling_token.clone(), 100_000 * EXISTENTIAL_DEPOSIT);
+    let beneficiary = Location::new(
+      0,
+      [AccountId32 {
+        network: None,
+        id: bob().into(),
+      }],
+    );
+    let native_before = Balances::free_balance(bob());
+
+    assert_ok!(
+      <XcmConfig as XcmExecutorConfig>::AssetTransactor::deposit_asset(
+        &Asset::from((sibling_token.clone(), 10 * EXISTENTIAL_DEPOSIT)),
+        &beneficiary,
+        None,
+      )
+    );
+
+    assert_eq!(
+      ForeignAssets::balance(sibling_token, bob()),
+      10 * EXISTENTIAL_DEPOSIT
+    );
+    assert_eq!(Balances::free_balance(bob()), native_before);
+  });
+}
+
+/// Test that foreign assets can be exchanged through the router over XCM
+#[test]
+fn test_exchange_foreign_asset_for_native() {
+  new_test_ext().execute_with(|| {
+    let sibling_token = Location::new(1, [Parachain(2000)]);
+    setup_foreign_pool(sibling_token.clone(), 100_000 * EXISTENTIAL_DEPOSIT);
+    let amount_in = 1_000 * EXISTENTIAL_DEPOSIT;
+    endow(fee_collector());
+
+    let quote = DexRouter::quote_exact_tokens_for_tokens(
+      AssetKind::Foreign(sibling_token.clone()),
+      AssetKind::Native,
+      amount_in,
+    )
+    .unwrap();
+    let received = RouterAssetExchanger::exchange_asset(
+      None,
+      holding(sibling_token.clone(), amount_in),
+      &wanted(Location::here(), 1),
+      true,
+    )
+    .expect("exchange should succeed");
+
+    assert_eq!(fungible_amount(&received, Location::here()), quote);
+    assert_eq!(
+      ForeignAssets::balance(sibling_token, DexExchangeAccount::get()),
+      0
+    );
+  });
+}
@@ -0,0 +1,274 @@
+//! Tests for the treasury and its share of the router fees.
+//!
+//! Spends are approved by root and paid from the treasury account, buying assets it does not
+//! hold through the router.
+
+use super::dex_router_integration_tests::{
+  add_liquidity, alice, bob, create_test_asset, mint_tokens, new_test_ext, router_swap,
+  setup_native_pool, LOCAL_ASSET,
+};
+use crate::{
+  configs::{AssetKind, RouterFeeCollector, TreasuryAccount, TreasuryFeeShare},
+  AccountId, AssetConversion, Assets, Balance, Balances, DexRouter, Runtime, RuntimeOrigin,
+  Treasury, EXISTENTIAL_DEPOSIT,
+};
+use pallet_dex_router::FeeSettings;
+use polkadot_sdk::{
+  frame_support::{assert_noop, assert_ok},
+  pallet_treasury,
+  sp_runtime::{DispatchError, Permill},
+};
+
+const LIQUIDITY: Balance = 100_000 * EXISTENTIAL_DEPOSIT;
+const AMOUNT: Balance = 1_000 * EXISTENTIAL_DEPOSIT;
+
+fn beneficiary() -> AccountId {
+  AccountId::from([9u8; 32])
+}
+
+fn router_fee(amount_in: Balance) -> Balance {
+  Permill::from_parts(2000).mul_floor(amount_in)
+}
+
+/// Fund the treasury with native tokens from alice
+fn fund_treasury(amount: Balance) {
+  assert_ok!(Balances::transfer_keep_alive(
+    RuntimeOrigin::signed(alice()),
+    TreasuryAccount::get().into(),
+    amount,
+  ));
+}
+
+/// Test that the treasury receives its share of the router fee and the collector the rest
+#[test]
+fn test_router_fee_split_with_treasury() {
+  new_test_ext().execute_with(|| {
+    setup_native_pool(LOCAL_ASSET, LIQUIDITY);
+    let collector_before = Balances::free_balance(RouterFeeCollector::get());
+    let treasury_before = Balances::free_balance(TreasuryAccount::get());
+
+    assert_ok!(router_swap(
+      &bob(),
+      AssetKind::Native,
+      AssetKind::Local(LOCAL_ASSET),
+      AMOUNT
+    ));
+
+    let fee = router_fee(AMOUNT);
+    let treasury_fee = TreasuryFeeShare::get().mul_floor(fee);
+    assert!(treasury_fee > 0);
+    assert_eq!(
+      Balances::free_balance(TreasuryAccount::get()),
+      treasury_before + treasury_fee
+    );
+    assert_eq!(
+      Balances::free_balance(RouterFeeCollector::get()),
+      collector_before + fee - treasury_fee
+    );
+  });
+}
+
+/// Test that the treasury share can be changed without a runtime upgrade
+#[test]
+fn test_treasury_fee_share_is_configurable() {
+  new_test_ext().execute_with(|| {
+    setup_native_pool(LOCAL_ASSET, LIQUIDITY);
+    assert_ok!(DexRouter::set_fee_settings(
+      RuntimeOrigin::root(),
+      FeeSettings {
+        treasury_fee_share: Permill::from_percent(100),
+        ..DexRouter::fee_settings()
+      }
+    ));
+    let collector_before = Balances::free_balance(RouterFeeCollector::get());
+    let treasury_before = Balances::free_balance(TreasuryAccount::get());
+
+    assert_ok!(router_swap(
+      &bob(),
+      AssetKind::Native,
+      AssetKind::Local(LOCAL_ASSET),
+      AMOUNT
+    ));
+
+    assert_eq!(
+      Balances::free_balance(TreasuryAccount::get()),
+      treasury_before + router_fee(AMOUNT)
+    );
+    assert_eq!(
+      Balances::free_balance(RouterFeeCollector::get()),
+      collector_before
+    );
+  });
+}
+
+/// Test that pools created through the router let both fee accounts receive fees of any size in
+/// non-sufficient assets
+#[test]
+fn test_router_fee_split_in_local_asset() {
+  new_test_ext().execute_with(|| {
+    setup_native_pool(LOCAL_ASSET, LIQUIDITY);
+    assert_ok!(router_swap(
+      &alice(),
+      AssetKind::Local(LOCAL_ASSET),
+      AssetKind::Native,
+      AMOUNT
+    ));
+
+    // The treasury share is below the minimum balance of the asset, but its account exists
+    let fee = router_fee(AMOUNT);
+    let treasury_fee = TreasuryFeeShare::get().mul_floor(fee);
+    assert!(treasury_fee < EXISTENTIAL_DEPOSIT);
+    assert_eq!(
+      Assets::balance(LOCAL_ASSET, TreasuryAccount::get()),
+      treasury_fee
+    );
+    assert_eq!(
+      Assets::balance(LOCAL_ASSET, RouterFeeCollector::get()),
+      fee - treasury_fee
+    );
+  });
+}
+
+/// Test that a swap whose router fee the fee accounts cannot receive fails rather than skipping
+/// the fee
+#[test]
+fn test_unreceivable_fee_fails_swap() {
+  new_test_ext().execute_with(|| {
+    // A pool created around the router leaves the fee accounts without the asset
+    assert_ok!(create_test_asset(
+      LOCAL_ASSET,
+      &alice(),
+      EXISTENTIAL_DEPOSIT
+    ));
+    assert_ok!(mint_tokens(LOCAL_ASSET, &alice(), &alice(), 2 * LIQUIDITY));
+    assert_ok!(AssetConversion::create_pool(
+      RuntimeOrigin::signed(alice()),
+      Box::new(AssetKind::Native),
+      Box::new(AssetKind::Local(LOCAL_ASSET)),
+    ));
+    assert_ok!(add_liquidity(
+      RuntimeOrigin::signed(alice()),
+      AssetKind::Native,
+      AssetKind::Local(LOCAL_ASSET),
+      (LIQUIDITY, LIQUIDITY),
+      (1, 1),
+      &alice(),
+    ));
+
+    assert_noop!(
+      router_swap(
+        &alice(),
+        AssetKind::Local(LOCAL_ASSET),
+        AssetKind::Native,
+        AMOUNT
+      ),
+      pallet_dex_router::Error::<Runtime>::FeeNotCollectable
+    );
+
+    // Touching the fee accounts makes the swap go through
+    assert_ok!(DexRouter::touch_fee_accounts(
+      &AssetKind::Local(LOCAL_ASSET),
+      &alice()
+    ));
+    assert_ok!(router_swap(
+      &alice(),
+      AssetKind::Local(LOCAL_ASSET),
+      AssetKind::Native,
+      AMOUNT
+    ));
+    assert_eq!(
+      Assets::balance(LOCAL_ASSET, RouterFeeCollector::get()),
+      router_fee(AMOUNT) - TreasuryFeeShare::get().mul_floor(router_fee(AMOUNT))
+    );
+  });
+}
+
+/// Test that root can approve native spends which anyone can then pay out
+#[test]
+fn test_native_spend() {
+  new_test_ext().execute_with(|| {
+    fund_treasury(2 * AMOUNT);
+    assert_noop!(
+      Treasury::spend(
+        RuntimeOrigin::signed(alice()),
+        Box::new(AssetKind::Native),
+        AMOUNT,
+        Box::new(beneficiary()),
+        None,
+      ),
+      DispatchError::BadOrigin
+    );
+    assert_ok!(Treasury::spend(
+      RuntimeOrigin::root(),
+      Box::new(AssetKind::Native),
+      AMOUNT,
+      Box::new(beneficiary()),
+      None,
+    ));
+
+    assert_ok!(Treasury::payout(RuntimeOrigin::signed(bob()), 0));
+    assert_eq!(Balances::free_balance(beneficiary()), AMOUNT);
+  });
+}
+
+/// Test that spends in a local asset the treasury does not hold are bought through the router
+#[test]
+fn test_local_asset_spend_paid_through_router() {
+  new_test_ext().execute_with(|| {
+    setup_native_pool(LOCAL_ASSET, LIQUIDITY);
+    fund_treasury(10 * AMOUNT);
+    // The beneficiary must exist to hold the non-sufficient asset
+    assert_ok!(Balances::transfer_keep_alive(
+      RuntimeOrigin::signed(alice()),
+      beneficiary().into(),
+      EXISTENTIAL_DEPOSIT,
+    ));
+    let treasury = TreasuryAccount::get();
+    let treasury_before = Balances::free_balance(&treasury);
+
+    assert_ok!(Treasury::spend(
+      RuntimeOrigin::root(),
+      Box::new(AssetKind::Local(LOCAL_ASSET)),
+      AMOUNT,
+      Box::new(beneficiary()),
+      None,
+    ));
+    let native_in = DexRouter::quote_tokens_for_exact_tokens(
+      AssetKind::Native,
+      AssetKind::Local(LOCAL_ASSET),
+      AMOUNT,
+    )
+    .unwrap();
+    assert!(native_in > AMOUNT);
+
+    assert_ok!(Treasury::payout(RuntimeOrigin::signed(bob()), 0));
+
+    // Rounding may leave the treasury with dust, which is paid out along with the spend
+    let received = Assets::balance(LOCAL_ASSET, beneficiary());
+    assert!((AMOUNT..AMOUNT + EXISTENTIAL_DEPOSIT).contains(&received));
+    assert_eq!(Assets::balance(LOCAL_ASSET, &treasury), 0);
+    // The treasury gets its share of the router fee of its own swap back
+    let treasury_fee = TreasuryFeeShare::get().mul_floor(router_fee(native_in));
+    assert_eq!(
+      Balances::free_balance(&treasury),
+      treasury_before - native_in + treasury_fee
+    );
+  });
+}
+
+/// Test that spends in assets without a pool cannot be valued and are rejected
+#[test]
+fn test_spend_in_unpooled_asset_fails() {
+  new_test_ext().execute_with(|| {
+    assert_noop!(
+      Treasury::spend(
+        RuntimeOrigin::root(),
+        Box::new(AssetKind::Local(LOCAL_ASSET)),
+        AMOUNT,
+        Box::new(beneficiary()),
+        None,
+      ),
+      pallet_treasury::Error::<Runtime>::FailedToConvertBalance
+    );
+  });
+}
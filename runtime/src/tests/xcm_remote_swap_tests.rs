@@ -0,0 +1,207 @@
+//! Tests for sibling parachains swapping through the router with XCM `Transact`.
+//!
+//! These execute the program a sibling sends to swap on behalf of one of its users: fees and
+//! the swap input come from the sibling's sovereign account, and the output is sent back to the
+//! user on the sibling.
+
+use super::{
+  dex_router_integration_tests::{
+    account_location, alice, bob, new_test_ext, setup_native_pool, LOCAL_ASSET,
+  },
+  xcm_transfer_tests::{connect_sibling, sibling, sibling_sovereign},
+};
+use crate::{
+  configs::{xcm_config::XcmConfig, AssetKind},
+  Assets as LocalAssets, Balance, Balances, DexRouter, RuntimeCall, RuntimeEvent, RuntimeOrigin,
+  System, EXISTENTIAL_DEPOSIT,
+};
+use codec::Encode;
+use polkadot_sdk::{
+  frame_support::{assert_noop, assert_ok},
+  pallet_xcm,
+  sp_runtime::{BoundedVec, DispatchError, MultiAddress},
+  staging_xcm::{latest::prelude::*, VersionedLocation},
+  staging_xcm_executor::XcmExecutor,
+};
+
+const AMOUNT: Balance = 1_000 * EXISTENTIAL_DEPOSIT;
+const FEES: Balance = 100 * EXISTENTIAL_DEPOSIT;
+
+fn remote_swap_call(amount_in: Balance, amount_out_min: Balance) -> RuntimeCall {
+  RuntimeCall::DexRouter(pallet_dex_router::Call::remote_swap {
+    path: BoundedVec::try_from(vec![AssetKind::Native, AssetKind::Local(LOCAL_ASSET)]).unwrap(),
+    amount_in,
+    amount_out_min,
+    beneficiary: Box::new(VersionedLocation::from(account_location(bob()))),
+  })
+}
+
+/// Program a sibling sends to swap from its sovereign account
+fn remote_swap_program(call: RuntimeCall) -> Xcm<RuntimeCall> {
+  Xcm(vec![
+    WithdrawAsset(Asset::from((Location::here(), FEES)).into()),
+    BuyExecution {
+      fees: (Location::here(), FEES).into(),
+      weight_limit: Unlimited,
+    },
+    Transact {
+      origin_kind: OriginKind::Xcm,
+      fallback_max_weight: None,
+      call: call.encode().into(),
+    },
+    RefundSurplus,
+    DepositAsset {
+      assets: Wild(AllCounted(1)),
+      beneficiary: sibling(),
+    },
+  ])
+}
+
+fn execute_from_sibling(message: Xcm<RuntimeCall>) -> Outcome {
+  let mut hash = [0u8; 32];
+  XcmExecutor::<XcmConfig>::prepare_and_execute(
+    sibling(),
+    message,
+    &mut hash,
+    Weight::MAX,
+    Weight::zero(),
+  )
+}
+
+/// Give the sibling's sovereign account native tokens to swap and pay fees with
+fn fund_sibling_sovereign() {
+  connect_sibling();
+  assert_ok!(Balances::force_set_balance(
+    RuntimeOrigin::root(),
+    MultiAddress::Id(sibling_sovereign()),
+    100 * AMOUNT,
+  ));
+}
+
+/// Test that a sibling swaps from its sovereign account and gets the output sent back
+#[test]
+fn test_remote_swap_from_sibling() {
+  new_test_ext().execute_with(|| {
+    setup_native_pool(LOCAL_ASSET, 100_000 * EXISTENTIAL_DEPOSIT);
+    fund_sibling_sovereign();
+    let sovereign_native_before = Balances::free_balance(sibling_sovereign());
+    let quote = DexRouter::quote_exact_tokens_for_tokens(
+      AssetKind::Native,
+      AssetKind::Local(LOCAL_ASSET),
+      AMOUNT,
+    )
+    .unwrap();
+
+    let outcome = execute_from_sibling(remote_swap_program(remote_swap_call(AMOUNT, 1)));
+    assert_ok!(outcome.ensure_complete());
+
+    // The input and execution fees are paid by the sovereign account
+    assert!(Balances::free_balance(sibling_sovereign()) < sovereign_native_before - AMOUNT);
+    // The output stays in the sovereign account as the reserve of what the sibling mints for bob
+    assert_eq!(
+      LocalAssets::balance(LOCAL_ASSET, sibling_sovereign()),
+      quote
+    );
+    let beneficiary = VersionedLocation::from(account_location(bob()));
+    assert!(System::events().iter().any(|record| matches!(
+      &record.event,
+      RuntimeEvent::DexRouter(pallet_dex_router::Event::SwapOutputSent {
+        who,
+        amount,
+        dest,
+        beneficiary: sent_to,
+        ..
+      }) if *who == sibling_sovereign()
+        && *amount == quote
+        && *dest == VersionedLocation::from(sibling())
+        && *sent_to == beneficiary
+    )));
+    assert!(System::events().iter().any(|record| matches!(
+      &record.event,
+      RuntimeEvent::PolkadotXcm(pallet_xcm::Event::Sent { destination, .. })
+        if *destination == sibling()
+    )));
+  });
+}
+
+/// Test that a failing remote swap leaves the sovereign account's input untouched
+#[test]
+fn test_remote_swap_failure_keeps_input() {
+  new_test_ext().execute_with(|| {
+    setup_native_pool(LOCAL_ASSET, 100_000 * EXISTENTIAL_DEPOSIT);
+    fund_sibling_sovereign();
+    let sovereign_native_before = Balances::free_balance(sibling_sovereign());
+
+    // Demand more output than the pool can give. `Transact` reports the dispatch error without
+    // failing the program, so unspent fees are still refunded.
+    let outcome = execute_from_sibling(remote_swap_program(remote_swap_call(AMOUNT, AMOUNT)));
+    assert_ok!(outcome.ensure_complete());
+
+    assert_eq!(LocalAssets::balance(LOCAL_ASSET, sibling_sovereign()), 0);
+    // Only execution fees were spent
+    assert!(Balances::free_balance(sibling_sovereign()) > sovereign_native_before - AMOUNT);
+    assert!(!System::events().iter().any(|record| matches!(
+      record.event,
+      RuntimeEvent::DexRouter(pallet_dex_router::Event::SwapOutputSent { .. })
+    )));
+  });
+}
+
+/// Test that only sibling parachains can call `remote_swap`
+#[test]
+fn test_remote_swap_requires_sibling_origin() {
+  new_test_ext().execute_with(|| {
+    setup_native_pool(LOCAL_ASSET, 100_000 * EXISTENTIAL_DEPOSIT);
+
+    assert_noop!(
+      DexRouter::remote_swap(
+        RuntimeOrigin::signed(alice()),
+        BoundedVec::try_from(vec![AssetKind::Native, AssetKind::Local(LOCAL_ASSET)]).unwrap(),
+        AMOUNT,
+        1,
+        Box::new(VersionedLocation::from(account_location(bob()))),
+      ),
+      DispatchError::BadOrigin
+    );
+
+    // Locations within a sibling are not the sibling itself
+    let mut sibling_user = sibling();
+    sibling_user
+      .push_interior(AccountId32 {
+        network: None,
+        id: bob().into(),
+      })
+      .unwrap();
+    assert_noop!(
+      DexRouter::remote_swap(
+        pallet_xcm::Origin::Xcm(sibling_user).into(),
+        BoundedVec::try_from(vec![AssetKind::Native, AssetKind::Local(LOCAL_ASSET)]).unwrap(),
+        AMOUNT,
+        1,
+        Box::new(VersionedLocation::from(account_location(bob()))),
+      ),
+      DispatchError::BadOrigin
+    );
+  });
+}
+
+/// Test that siblings outside `TrustedSiblings` cannot swap, since their output could not be sent
+/// back to them
+#[test]
+fn test_remote_swap_rejects_untrusted_sibling() {
+  new_test_ext().execute_with(|| {
+    setup_native_pool(LOCAL_ASSET, 100_000 * EXISTENTIAL_DEPOSIT);
+    let untrusted = Location::new(1, [Parachain(3000)]);
+
+    assert_noop!(
+      DexRouter::remote_swap(
+        pallet_xcm::Origin::Xcm(untrusted).into(),
+        BoundedVec::try_from(vec![AssetKind::Native, AssetKind::Local(LOCAL_ASSET)]).unwrap(),
+        AMOUNT,
+        1,
+        Box::new(VersionedLocation::from(account_location(bob()))),
+      ),
+      DispatchError::BadOrigin
+    );
+  });
+}
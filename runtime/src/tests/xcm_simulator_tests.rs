@@ -0,0 +1,595 @@
+//! Tests running this runtime as two parachains of a simulated relay chain.
+//!
+//! Both parachains are the real runtime with their own parachain ids. Messages from the
+//! simulator go through the XCMP and message queues, as the collator would hand them over, and
+//! what a parachain sends to its sibling is taken from the XCMP queue onto the simulator's bus.
+
+use super::dex_router_integration_tests::{
+  account_location, alice, bob, mint_tokens, setup_foreign_pool, setup_native_pool,
+};
+use crate::{
+  configs::{
+    xcm_config::{AssetsPalletIndex, DexExchangeAccount, StakingPot, XcmRouter},
+    AssetKind, RouterFeeCollector, TreasuryAccount,
+  },
+  AccountId, Assets as LocalAssets, Balance, Balances, DexRouter, ForeignAssets, MessageQueue,
+  ParachainInfo, ParachainSystem, Runtime, RuntimeCall, RuntimeEvent, RuntimeOrigin, System,
+  XcmpQueue, EXISTENTIAL_DEPOSIT,
+};
+use codec::Decode;
+use polkadot_sdk::{
+  cumulus_primitives_core::{
+    AggregateMessageOrigin, DmpMessageHandler, ParaId, XcmpMessageFormat, XcmpMessageHandler,
+    XcmpMessageSource,
+  },
+  frame_support::{
+    assert_noop, assert_ok,
+    traits::{fungible::Inspect as _, EnqueueMessage, ServiceQueues},
+    BoundedSlice,
+  },
+  frame_system, pallet_balances, pallet_message_queue, pallet_xcm,
+  sp_io::TestExternalities,
+  sp_runtime::{traits::Dispatchable, BoundedVec, BuildStorage, MultiAddress},
+  staging_parachain_info as parachain_info,
+  staging_xcm::{latest::prelude::*, VersionedAssets, VersionedLocation, VersionedXcm},
+  staging_xcm_executor::traits::ConvertLocation,
+  xcm_simulator::{
+    decl_test_network, decl_test_parachain, decl_test_relay_chain, RelayBlockNumber, TestExt,
+    PARA_MESSAGE_BUS,
+  },
+};
+
+/// The chain running the DEX
+const DEX_PARA: u32 = 2000;
+/// A trusted sibling, like Asset Hub
+const SIBLING_PARA: u32 = 1000;
+/// A sibling that is not trusted
+const UNTRUSTED_PARA: u32 = 3000;
+const INITIAL_BALANCE: Balance = 1_000_000_000 * EXISTENTIAL_DEPOSIT;
+const AMOUNT: Balance = 1_000 * EXISTENTIAL_DEPOSIT;
+
+/// Minimal relay chain, only relaying messages between its parachains
+pub mod relay {
+  use super::RelayChainXcmRouter;
+  use polkadot_sdk::{
+    frame_support::{
+      construct_runtime, derive_impl, parameter_types,
+      traits::{ConstU32, Everything, Nothing, ProcessMessage, ProcessMessageError},
+      weights::{Weight, WeightMeter},
+    },
+    frame_system, pallet_message_queue,
+    sp_io::TestExternalities,
+    sp_runtime::BuildStorage,
+    staging_xcm::latest::prelude::*,
+    staging_xcm_builder::{FixedWeightBounds, FrameTransactionalProcessor, ProcessXcmMessage},
+    staging_xcm_executor::{self as xcm_executor, XcmExecutor},
+    xcm_simulator::{AggregateMessageOrigin, UmpQueueId},
+  };
+
+  type Block = frame_system::mocking::MockBlock<Runtime>;
+
+  construct_runtime!(
+    pub enum Runtime {
+      System: frame_system,
+      MessageQueue: pallet_message_queue,
+    }
+  );
+
+  #[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+  impl frame_system::Config for Runtime {
+    type Block = Block;
+  }
+
+  parameter_types! {
+    pub const UnitWeightCost: Weight = Weight::from_parts(1_000, 1_000);
+    pub const MaxInstructions: u32 = 100;
+    pub UniversalLocation: InteriorLocation = Here;
+    pub const ServiceWeight: Weight = Weight::MAX;
+  }
+
+  impl pallet_message_queue::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type WeightInfo = ();
+    type MessageProcessor = MessageProcessor;
+    type Size = u32;
+    type QueueChangeHandler = ();
+    type QueuePausedQuery = ();
+    type HeapSize = ConstU32<{ 64 * 1024 }>;
+    type MaxStale = ConstU32<8>;
+    type ServiceWeight = ServiceWeight;
+    type IdleMaxServiceWeight = ();
+  }
+
+  /// Executes upward messages with the sending parachain as origin
+  pub struct MessageProcessor;
+  impl ProcessMessage for MessageProcessor {
+    type Origin = AggregateMessageOrigin;
+
+    fn process_message(
+      message: &[u8],
+      origin: Self::Origin,
+      meter: &mut WeightMeter,
+      id: &mut [u8; 32],
+    ) -> Result<bool, ProcessMessageError> {
+      let AggregateMessageOrigin::Ump(UmpQueueId::Para(para)) = origin;
+      ProcessXcmMessage::<Junction, XcmExecutor<XcmConfig>, RuntimeCall>::process_message(
+        message,
+        Parachain(para.into()),
+        meter,
+        id,
+      )
+    }
+  }
+
+  /// Executes nothing: the tests only route messages between the parachains
+  pub struct XcmConfig;
+  impl xcm_executor::Config for XcmConfig {
+    type RuntimeCall = RuntimeCall;
+    type XcmSender = RelayChainXcmRouter;
+    type XcmEventEmitter = ();
+    type AssetTransactor = ();
+    type OriginConverter = ();
+    type IsReserve = ();
+    type IsTeleporter = ();
+    type UniversalLocation = UniversalLocation;
+    type Barrier = ();
+    type Weigher = FixedWeightBounds<UnitWeightCost, RuntimeCall, MaxInstructions>;
+    type Trader = ();
+    type ResponseHandler = ();
+    type AssetTrap = ();
+    type AssetClaims = ();
+    type SubscriptionService = ();
+    type PalletInstancesInfo = ();
+    type MaxAssetsIntoHolding = ConstU32<64>;
+    type AssetLocker = ();
+    type AssetExchanger = ();
+    type FeeManager = ();
+    type MessageExporter = ();
+    type UniversalAliases = Nothing;
+    type CallDispatcher = RuntimeCall;
+    type SafeCallFilter = Everything;
+    type Aliasers = Nothing;
+    type TransactionalProcessor = FrameTransactionalProcessor;
+    type HrmpNewChannelOpenRequestHandler = ();
+    type HrmpChannelAcceptedHandler = ();
+    type HrmpChannelClosingHandler = ();
+    type XcmRecorder = ();
+  }
+
+  pub fn ext() -> TestExternalities {
+    let storage = frame_system::GenesisConfig::<Runtime>::default()
+      .build_storage()
+      .unwrap();
+    let mut ext = TestExternalities::new(storage);
+    ext.execute_with(|| System::set_block_number(1));
+    ext
+  }
+}
+
+/// Hands messages from the simulator to the parachain's queues and services them, then puts
+/// what the parachain sends in turn on the simulator's bus.
+pub struct ParachainQueues;
+
+impl XcmpMessageHandler for ParachainQueues {
+  fn handle_xcmp_messages<'a, I: Iterator<Item = (ParaId, RelayBlockNumber, &'a [u8])>>(
+    iter: I,
+    max_weight: Weight,
+  ) -> Weight {
+    XcmpQueue::handle_xcmp_messages(iter, max_weight).saturating_add(service_queues(max_weight))
+  }
+}
+
+impl DmpMessageHandler for ParachainQueues {
+  fn handle_dmp_messages(
+    iter: impl Iterator<Item = (RelayBlockNumber, Vec<u8>)>,
+    max_weight: Weight,
+  ) -> Weight {
+    for (_, message) in iter {
+      MessageQueue::enqueue_message(
+        BoundedSlice::truncate_from(&message[..]),
+        AggregateMessageOrigin::Parent,
+      );
+    }
+    service_queues(max_weight)
+  }
+}
+
+fn service_queues(max_weight: Weight) -> Weight {
+  let weight = MessageQueue::service_queues(max_weight);
+  forward_outbound_messages();
+  weight
+}
+
+/// Move the messages waiting in the XCMP queue to the simulator's bus
+pub fn forward_outbound_messages() {
+  let sender = ParachainInfo::parachain_id();
+  loop {
+    let pages = XcmpQueue::take_outbound_messages(usize::MAX);
+    if pages.is_empty() {
+      break;
+    }
+    for (recipient, page) in pages {
+      let mut data = &page[..];
+      if XcmpMessageFormat::decode(&mut data) != Ok(XcmpMessageFormat::ConcatenatedVersionedXcm) {
+        continue;
+      }
+      while !data.is_empty() {
+        let message = VersionedXcm::<()>::decode(&mut data).expect("XCMP queue pages decode");
+        let message = Xcm::<()>::try_from(message).expect("sent with the latest version");
+        let destination = Location::new(1, [Parachain(recipient.into())]);
+        PARA_MESSAGE_BUS.with(|bus| bus.borrow_mut().push_back((sender, destination, message)));
+      }
+    }
+  }
+}
+
+/// Genesis of this runtime as parachain `para_id`, with a channel open to `sibling_id`
+fn para_ext(para_id: u32, sibling_id: u32) -> TestExternalities {
+  let mut storage = frame_system::GenesisConfig::<Runtime>::default()
+    .build_storage()
+    .unwrap();
+  pallet_balances::GenesisConfig::<Runtime> {
+    balances: vec![
+      (alice(), INITIAL_BALANCE),
+      (bob(), INITIAL_BALANCE),
+      (RouterFeeCollector::get(), EXISTENTIAL_DEPOSIT),
+      (TreasuryAccount::get(), EXISTENTIAL_DEPOSIT),
+      (DexExchangeAccount::get(), EXISTENTIAL_DEPOSIT),
+    ],
+    dev_accounts: None,
+  }
+  .assimilate_storage(&mut storage)
+  .unwrap();
+  parachain_info::GenesisConfig::<Runtime> {
+    parachain_id: para_id.into(),
+    ..Default::default()
+  }
+  .assimilate_storage(&mut storage)
+  .unwrap();
+  pallet_xcm::GenesisConfig::<Runtime> {
+    safe_xcm_version: Some(XCM_VERSION),
+    ..Default::default()
+  }
+  .assimilate_storage(&mut storage)
+  .unwrap();
+
+  let mut ext = TestExternalities::new(storage);
+  ext.execute_with(|| {
+    System::set_block_number(1);
+    ParachainSystem::open_outbound_hrmp_channel_for_benchmarks_or_tests(sibling_id.into());
+    // Execution fees bought with pooled assets go to the collator pot
+    assert_ok!(Balances::force_set_balance(
+      RuntimeOrigin::root(),
+      MultiAddress::Id(StakingPot::get()),
+      EXISTENTIAL_DEPOSIT,
+    ));
+  });
+  ext
+}
+
+decl_test_parachain! {
+  pub struct DexPara {
+    Runtime = Runtime,
+    XcmpMessageHandler = ParachainQueues,
+    DmpMessageHandler = ParachainQueues,
+    new_ext = para_ext(DEX_PARA, SIBLING_PARA),
+  }
+}
+
+decl_test_parachain! {
+  pub struct SiblingPara {
+    Runtime = Runtime,
+    XcmpMessageHandler = ParachainQueues,
+    DmpMessageHandler = ParachainQueues,
+    new_ext = para_ext(SIBLING_PARA, DEX_PARA),
+  }
+}
+
+decl_test_relay_chain! {
+  pub struct Relay {
+    Runtime = relay::Runtime,
+    RuntimeCall = relay::RuntimeCall,
+    RuntimeEvent = relay::RuntimeEvent,
+    XcmConfig = relay::XcmConfig,
+    MessageQueue = relay::MessageQueue,
+    System = relay::System,
+    new_ext = relay::ext(),
+  }
+}
+
+decl_test_network! {
+  pub struct MockNet {
+    relay_chain = Relay,
+    parachains = vec![
+      (SIBLING_PARA, SiblingPara),
+      (DEX_PARA, DexPara),
+    ],
+  }
+}
+
+fn para_location(para_id: u32) -> Location {
+  Location::new(1, [Parachain(para_id)])
+}
+
+fn sovereign_account(para_id: u32) -> AccountId {
+  crate::configs::xcm_config::LocationToAccountId::convert_location(&para_location(para_id))
+    .unwrap()
+}
+
+/// The DEX chain's native token, as seen from its siblings
+fn dex_token() -> Location {
+  para_location(DEX_PARA)
+}
+
+/// USDT on Asset Hub, played by the trusted sibling
+fn asset_hub_usdt() -> Location {
+  Location::new(
+    1,
+    [
+      Parachain(SIBLING_PARA),
+      PalletInstance(50),
+      GeneralIndex(1984),
+    ],
+  )
+}
+
+/// Reserve transfer native tokens from alice to bob on `para_id`, as alice would dispatch it
+fn reserve_transfer_call(para_id: u32, amount: Balance) -> RuntimeCall {
+  RuntimeCall::PolkadotXcm(pallet_xcm::Call::limited_reserve_transfer_assets {
+    dest: Box::new(VersionedLocation::from(para_location(para_id))),
+    beneficiary: Box::new(VersionedLocation::from(account_location(bob()))),
+    assets: Box::new(VersionedAssets::from(Assets::from(Asset::from((
+      Location::here(),
+      amount,
+    ))))),
+    fee_asset_item: 0,
+    weight_limit: Unlimited,
+  })
+}
+
+/// Test that native tokens reach a trusted sibling, and that untrusted siblings are rejected
+#[test]
+fn test_reserve_transfer_only_to_trusted_siblings() {
+  MockNet::reset();
+
+  // The sibling pools the DEX token, to pay for execution with it
+  SiblingPara::execute_with(|| {
+    setup_foreign_pool(dex_token(), 100_000 * EXISTENTIAL_DEPOSIT);
+  });
+
+  DexPara::execute_with(|| {
+    let alice_before = Balances::balance(&alice());
+
+    assert_noop!(
+      reserve_transfer_call(UNTRUSTED_PARA, AMOUNT).dispatch(RuntimeOrigin::signed(alice())),
+      frame_system::Error::<Runtime>::CallFiltered
+    );
+    assert_eq!(Balances::balance(&alice()), alice_before);
+
+    assert_ok!(reserve_transfer_call(SIBLING_PARA, AMOUNT).dispatch(RuntimeOrigin::signed(alice())));
+    assert_eq!(Balances::balance(&sovereign_account(SIBLING_PARA)), AMOUNT);
+    forward_outbound_messages();
+  });
+
+  SiblingPara::execute_with(|| {
+    // Everything but the execution fee reaches bob
+    let received = ForeignAssets::balance(dex_token(), bob());
+    assert!(received > 0 && received < AMOUNT);
+  });
+}
+
+/// Test that a sibling swaps on the DEX chain through XCM and receives the output
+///
+/// The sibling's sovereign account on the DEX chain holds the local asset to swap and pays for
+/// execution; the native tokens bought are reserve transferred back to bob on the sibling.
+#[test]
+fn test_remote_swap_between_parachains() {
+  const LOCAL_ASSET: u32 = 10;
+  const FEES: Balance = 100 * EXISTENTIAL_DEPOSIT;
+  MockNet::reset();
+
+  SiblingPara::execute_with(|| {
+    setup_foreign_pool(dex_token(), 100_000 * EXISTENTIAL_DEPOSIT);
+  });
+
+  let quote = DexPara::execute_with(|| {
+    setup_native_pool(LOCAL_ASSET, 100_000 * EXISTENTIAL_DEPOSIT);
+    let sovereign = sovereign_account(SIBLING_PARA);
+    assert_ok!(Balances::force_set_balance(
+      RuntimeOrigin::root(),
+      MultiAddress::Id(sovereign.clone()),
+      100 * AMOUNT,
+    ));
+    assert_ok!(mint_tokens(LOCAL_ASSET, &alice(), &sovereign, AMOUNT));
+    DexRouter::quote_exact_tokens_for_tokens(
+      AssetKind::Local(LOCAL_ASSET),
+      AssetKind::Native,
+      AMOUNT,
+    )
+    .unwrap()
+  });
+
+  SiblingPara::execute_with(|| {
+    let call = RuntimeCall::DexRouter(pallet_dex_router::Call::remote_swap {
+      path: BoundedVec::try_from(vec![AssetKind::Local(LOCAL_ASSET), AssetKind::Native]).unwrap(),
+      amount_in: AMOUNT,
+      amount_out_min: 1,
+      beneficiary: Box::new(VersionedLocation::from(account_location(bob()))),
+    });
+    // Asset locations are the DEX chain's view: `Here` is its native token
+    let message = Xcm(vec![
+      WithdrawAsset(Asset::from((Location::here(), FEES)).into()),
+      BuyExecution {
+        fees: (Location::here(), FEES).into(),
+        weight_limit: Unlimited,
+      },
+      Transact {
+        origin_kind: OriginKind::Xcm,
+        fallback_max_weight: None,
+        call: codec::Encode::encode(&call).into(),
+      },
+      RefundSurplus,
+      DepositAsset {
+        assets: Wild(AllCounted(1)),
+        beneficiary: para_location(SIBLING_PARA),
+      },
+    ]);
+    assert_ok!(send_xcm::<XcmRouter>(para_location(DEX_PARA), message));
+    forward_outbound_messages();
+  });
+
+  DexPara::execute_with(|| {
+    let sovereign = sovereign_account(SIBLING_PARA);
+    assert_eq!(LocalAssets::balance(LOCAL_ASSET, sovereign.clone()), 0);
+    assert!(System::events().iter().any(|record| matches!(
+      &record.event,
+      RuntimeEvent::DexRouter(pallet_dex_router::Event::SwapOutputSent {
+        who,
+        asset: AssetKind::Native,
+        amount,
+        ..
+      }) if *who == sovereign && *amount == quote
+    )));
+  });
+
+  SiblingPara::execute_with(|| {
+    // The swap output reaches bob, less the execution fee on the sibling
+    let received = ForeignAssets::balance(dex_token(), bob());
+    assert!(received > 0 && received < quote);
+  });
+}
+
+/// Test that a sibling exchanges a local asset for native tokens with `ExchangeAsset`
+///
+/// The sibling's sovereign account on the DEX chain holds the local asset and pays for
+/// execution. The exchange goes through the router, which takes its fee in the given asset and
+/// splits it between the fee collector and the treasury.
+#[test]
+fn test_exchange_asset_between_parachains() {
+  const LOCAL_ASSET: u32 = 10;
+  const FEES: Balance = 100 * EXISTENTIAL_DEPOSIT;
+  MockNet::reset();
+
+  let (quote, bob_before) = DexPara::execute_with(|| {
+    setup_native_pool(LOCAL_ASSET, 100_000 * EXISTENTIAL_DEPOSIT);
+    let sovereign = sovereign_account(SIBLING_PARA);
+    assert_ok!(Balances::force_set_balance(
+      RuntimeOrigin::root(),
+      MultiAddress::Id(sovereign.clone()),
+      100 * AMOUNT,
+    ));
+    assert_ok!(mint_tokens(LOCAL_ASSET, &alice(), &sovereign, AMOUNT));
+    let quote = DexRouter::quote_exact_tokens_for_tokens(
+      AssetKind::Local(LOCAL_ASSET),
+      AssetKind::Native,
+      AMOUNT,
+    )
+    .unwrap();
+    (quote, Balances::balance(&bob()))
+  });
+
+  SiblingPara::execute_with(|| {
+    // Asset locations are the DEX chain's view: `Here` is its native token
+    let local_asset = Location::new(
+      0,
+      [
+        PalletInstance(AssetsPalletIndex::get()),
+        GeneralIndex(LOCAL_ASSET.into()),
+      ],
+    );
+    let message = Xcm(vec![
+      WithdrawAsset(
+        vec![
+          Asset::from((Location::here(), FEES)),
+          Asset::from((local_asset.clone(), AMOUNT)),
+        ]
+        .into(),
+      ),
+      BuyExecution {
+        fees: (Location::here(), FEES).into(),
+        weight_limit: Unlimited,
+      },
+      ExchangeAsset {
+        give: Definite((local_asset, AMOUNT).into()),
+        want: (Location::here(), 1).into(),
+        maximal: true,
+      },
+      DepositAsset {
+        assets: Wild(AllCounted(1)),
+        beneficiary: account_location(bob()),
+      },
+    ]);
+    assert_ok!(send_xcm::<XcmRouter>(para_location(DEX_PARA), message));
+    forward_outbound_messages();
+  });
+
+  DexPara::execute_with(|| {
+    let fees = DexRouter::fee_settings();
+    let router_fee = fees.router_fee.mul_floor(AMOUNT);
+    let treasury_fee = fees.treasury_fee_share.mul_floor(router_fee);
+
+    assert_eq!(
+      LocalAssets::balance(LOCAL_ASSET, sovereign_account(SIBLING_PARA)),
+      0
+    );
+    // The router fee is split in the given asset
+    assert_eq!(
+      LocalAssets::balance(LOCAL_ASSET, RouterFeeCollector::get()),
+      router_fee - treasury_fee
+    );
+    assert_eq!(
+      LocalAssets::balance(LOCAL_ASSET, TreasuryAccount::get()),
+      treasury_fee
+    );
+    // Bob receives the swap output and the unspent execution fees
+    let received = Balances::balance(&bob()) - bob_before;
+    assert!(received > quote && received < quote + FEES);
+    // Nothing is left behind in the exchange account
+    assert_eq!(
+      LocalAssets::balance(LOCAL_ASSET, DexExchangeAccount::get()),
+      0
+    );
+    assert_eq!(
+      Balances::balance(&DexExchangeAccount::get()),
+      EXISTENTIAL_DEPOSIT
+    );
+  });
+}
+
+/// Test that USDT reserve-transferred from Asset Hub is received into `ForeignAssets`
+///
+/// The trusted sibling sends the program Asset Hub sends for a reserve transfer; the DEX chain
+/// pays for execution with USDT through its pool against the native token.
+#[test]
+fn test_receive_asset_hub_usdt() {
+  const FEES: Balance = 100 * EXISTENTIAL_DEPOSIT;
+  MockNet::reset();
+
+  DexPara::execute_with(|| {
+    setup_foreign_pool(asset_hub_usdt(), 100_000 * EXISTENTIAL_DEPOSIT);
+    assert_eq!(ForeignAssets::balance(asset_hub_usdt(), bob()), 0);
+  });
+
+  SiblingPara::execute_with(|| {
+    let message = Xcm(vec![
+      ReserveAssetDeposited((asset_hub_usdt(), AMOUNT).into()),
+      ClearOrigin,
+      BuyExecution {
+        fees: (asset_hub_usdt(), FEES).into(),
+        weight_limit: Unlimited,
+      },
+      DepositAsset {
+        assets: Wild(AllCounted(1)),
+        beneficiary: account_location(bob()),
+      },
+    ]);
+    assert_ok!(send_xcm::<XcmRouter>(para_location(DEX_PARA), message));
+    forward_outbound_messages();
+  });
+
+  DexPara::execute_with(|| {
+    // Everything but the execution fee reaches bob
+    let received = ForeignAssets::balance(asset_hub_usdt(), bob());
+    assert!(received > AMOUNT - FEES && received < AMOUNT);
+  });
+}
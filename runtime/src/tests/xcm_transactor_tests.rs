@@ -0,0 +1,154 @@
+//! Tests for the XCM asset transactors.
+//!
+//! The native token lives at `Here` and is transacted by `Balances`, while the relay token is a
+//! foreign asset held in `ForeignAssets`. Relay token deposits must never change the native supply.
+
+use super::dex_router_integration_tests::{bob, new_test_ext, setup_foreign_pool};
+use crate::{
+  configs::xcm_config::{CheckingAccount, RelayTokenMinBalance, XcmConfig},
+  Balance, Balances, ForeignAssets, RuntimeOrigin, EXISTENTIAL_DEPOSIT,
+};
+use polkadot_sdk::{
+  frame_support::{assert_ok, weights::Weight},
+  sp_runtime::MultiAddress,
+  staging_xcm::latest::prelude::*,
+  staging_xcm_executor::{
+    traits::{TransactAsset, WeightTrader},
+    AssetsInHolding, Config as XcmExecutorConfig,
+  },
+};
+
+type AssetTransactor = <XcmConfig as XcmExecutorConfig>::AssetTransactor;
+type Trader = <XcmConfig as XcmExecutorConfig>::Trader;
+
+const RELAY_AMOUNT: Balance = 10_000_000_000;
+
+fn bob_location() -> Location {
+  Location::new(
+    0,
+    [AccountId32 {
+      network: None,
+      id: bob().into(),
+    }],
+  )
+}
+
+fn relay_context() -> XcmContext {
+  XcmContext {
+    origin: Some(Location::parent()),
+    message_id: [0u8; 32],
+    topic: None,
+  }
+}
+
+/// Register the relay token the same way the genesis presets do
+fn register_relay_token() {
+  assert_ok!(ForeignAssets::force_create(
+    RuntimeOrigin::root(),
+    Location::parent(),
+    MultiAddress::Id(CheckingAccount::get()),
+    true,
+    RelayTokenMinBalance::get(),
+  ));
+}
+
+/// Test that relay token deposits are credited to `ForeignAssets`, not to native balances
+#[test]
+fn test_relay_token_deposit_does_not_mint_native() {
+  new_test_ext().execute_with(|| {
+    register_relay_token();
+    let issuance_before = Balances::total_issuance();
+    let native_before = Balances::free_balance(bob());
+
+    assert_ok!(AssetTransactor::deposit_asset(
+      &(Location::parent(), RELAY_AMOUNT).into(),
+      &bob_location(),
+      Some(&relay_context()),
+    ));
+
+    assert_eq!(
+      ForeignAssets::balance(Location::parent(), bob()),
+      RELAY_AMOUNT
+    );
+    assert_eq!(Balances::free_balance(bob()), native_before);
+    assert_eq!(Balances::total_issuance(), issuance_before);
+
+    // Sending the relay token back burns it from `ForeignAssets` only
+    assert_ok!(AssetTransactor::withdraw_asset(
+      &(Location::parent(), RELAY_AMOUNT).into(),
+      &bob_location(),
+      Some(&relay_context()),
+    ));
+    assert_eq!(ForeignAssets::balance(Location::parent(), bob()), 0);
+    assert_eq!(Balances::free_balance(bob()), native_before);
+    assert_eq!(Balances::total_issuance(), issuance_before);
+  });
+}
+
+/// Test that relay token deposits fail rather than fall back to the native currency
+#[test]
+fn test_unregistered_relay_token_deposit_fails() {
+  new_test_ext().execute_with(|| {
+    let issuance_before = Balances::total_issuance();
+
+    assert!(AssetTransactor::deposit_asset(
+      &(Location::parent(), RELAY_AMOUNT).into(),
+      &bob_location(),
+      Some(&relay_context()),
+    )
+    .is_err());
+
+    assert_eq!(Balances::total_issuance(), issuance_before);
+  });
+}
+
+/// Test that the native token is transacted at `Here` by `Balances`
+#[test]
+fn test_native_token_transacted_at_here() {
+  new_test_ext().execute_with(|| {
+    let amount = 10 * EXISTENTIAL_DEPOSIT;
+    let issuance_before = Balances::total_issuance();
+    let native_before = Balances::free_balance(bob());
+
+    let withdrawn: AssetsInHolding =
+      AssetTransactor::withdraw_asset(&(Location::here(), amount).into(), &bob_location(), None)
+        .expect("native token should be withdrawable");
+    assert_eq!(
+      withdrawn.fungible.get(&AssetId(Location::here())),
+      Some(&amount)
+    );
+    assert_eq!(Balances::free_balance(bob()), native_before - amount);
+    assert_eq!(Balances::total_issuance(), issuance_before - amount);
+
+    assert_ok!(AssetTransactor::deposit_asset(
+      &(Location::here(), amount).into(),
+      &bob_location(),
+      None,
+    ));
+    assert_eq!(Balances::free_balance(bob()), native_before);
+    assert_eq!(Balances::total_issuance(), issuance_before);
+  });
+}
+
+/// Test that execution can only be bought with the relay token through a native pool
+#[test]
+fn test_trader_swaps_relay_token_for_native() {
+  new_test_ext().execute_with(|| {
+    let weight = Weight::from_parts(500_000_000_000, 0);
+    let payment: AssetsInHolding =
+      Asset::from((Location::parent(), 100 * EXISTENTIAL_DEPOSIT)).into();
+
+    // Without a pool the relay token is not accepted as native
+    let mut trader = Trader::new();
+    assert!(trader
+      .buy_weight(weight, payment.clone(), &relay_context())
+      .is_err());
+
+    setup_foreign_pool(Location::parent(), 100_000 * EXISTENTIAL_DEPOSIT);
+    let issuance_before = Balances::total_issuance();
+
+    let mut trader = Trader::new();
+    assert!(trader.buy_weight(weight, payment, &relay_context()).is_ok());
+    assert_eq!(Balances::total_issuance(), issuance_before);
+  });
+}
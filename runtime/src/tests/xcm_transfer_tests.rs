@@ -0,0 +1,373 @@
+//! Tests for cross-chain transfers of local assets and router swap output.
+//!
+//! The outgoing side goes through `pallet_xcm` with an open HRMP channel to the sibling. The
+//! return side executes the program the sibling sends back, as the XCM queue would.
+
+use super::dex_router_integration_tests::{
+  account_location, alice, bob, new_test_ext, setup_native_pool, LOCAL_ASSET,
+};
+use crate::{
+  configs::{
+    xcm_config::{
+      LocalAssetsPalletLocation, LocalReserveTransfers, LocationToAccountId, StakingPot,
+      TrustedSiblingReserves, XcmConfig,
+    },
+    AssetKind, RuntimeCallFilter,
+  },
+  AccountId, AssetConversion, Assets as LocalAssets, Balance, Balances, DexRouter, ParachainSystem,
+  PolkadotXcm, Runtime, RuntimeCall, RuntimeEvent, RuntimeOrigin, System, EXISTENTIAL_DEPOSIT,
+};
+use polkadot_sdk::{
+  cumulus_primitives_core::ParaId,
+  frame_support::{
+    assert_noop, assert_ok,
+    dispatch::DispatchResult,
+    traits::{Contains, ContainsPair},
+  },
+  pallet_xcm,
+  sp_runtime::{traits::Dispatchable, BoundedVec, MultiAddress},
+  staging_xcm::{latest::prelude::*, VersionedAssets, VersionedLocation},
+  staging_xcm_executor::{traits::ConvertLocation, XcmExecutor},
+};
+
+const SIBLING: u32 = 1000;
+const AMOUNT: Balance = 1_000 * EXISTENTIAL_DEPOSIT;
+
+pub(crate) fn sibling() -> Location {
+  Location::new(1, [Parachain(SIBLING)])
+}
+
+pub(crate) fn sibling_sovereign() -> AccountId {
+  LocationToAccountId::convert_location(&sibling()).unwrap()
+}
+
+fn local_asset(amount: Balance) -> Asset {
+  let mut location = LocalAssetsPalletLocation::get();
+  location
+    .push_interior(GeneralIndex(LOCAL_ASSET.into()))
+    .unwrap();
+  (location, amount).into()
+}
+
+/// Open the channel to the sibling and give its sovereign account a provider
+pub(crate) fn connect_sibling() {
+  ParachainSystem::open_outbound_hrmp_channel_for_benchmarks_or_tests(ParaId::from(SIBLING));
+  assert_ok!(Balances::force_set_balance(
+    RuntimeOrigin::root(),
+    MultiAddress::Id(sibling_sovereign()),
+    EXISTENTIAL_DEPOSIT,
+  ));
+}
+
+/// Reserve transfer `asset` from alice to bob on the sibling, paying remote fees with it
+fn transfer_to_sibling(asset: Asset) -> DispatchResult {
+  PolkadotXcm::limited_reserve_transfer_assets(
+    RuntimeOrigin::signed(alice()),
+    Box::new(VersionedLocation::from(sibling())),
+    Box::new(VersionedLocation::from(account_location(bob()))),
+    Box::new(VersionedAssets::from(Assets::from(asset))),
+    0,
+    Unlimited,
+  )
+}
+
+/// Test that the reserve transfer filter only lets local accounts send known assets
+#[test]
+fn test_reserve_transfer_filter() {
+  new_test_ext().execute_with(|| {
+    let alice_location = account_location(alice());
+    let known = vec![local_asset(AMOUNT), (Location::here(), AMOUNT).into()];
+    assert!(LocalReserveTransfers::contains(&(
+      alice_location.clone(),
+      known.clone()
+    )));
+
+    // Remote origins cannot reserve transfer through `pallet_xcm`
+    assert!(!LocalReserveTransfers::contains(&(sibling(), known)));
+
+    // Assets of other pallets are not transferable
+    let unknown: Asset = (
+      Location::new(0, [PalletInstance(99), GeneralIndex(1)]),
+      AMOUNT,
+    )
+      .into();
+    assert!(!LocalReserveTransfers::contains(&(
+      alice_location,
+      vec![unknown]
+    )));
+  });
+}
+
+/// Test that `pallet_xcm` transfers may only go to the relay chain and trusted siblings
+#[test]
+fn test_transfer_destinations() {
+  new_test_ext().execute_with(|| {
+    let transfer = |dest: Location| {
+      RuntimeCall::PolkadotXcm(pallet_xcm::Call::limited_reserve_transfer_assets {
+        dest: Box::new(VersionedLocation::from(dest)),
+        beneficiary: Box::new(VersionedLocation::from(account_location(bob()))),
+        assets: Box::new(VersionedAssets::from(Assets::from(Asset::from((
+          Location::parent(),
+          AMOUNT,
+        ))))),
+        fee_asset_item: 0,
+        weight_limit: Unlimited,
+      })
+    };
+
+    assert!(RuntimeCallFilter::contains(&transfer(Location::parent())));
+    assert!(RuntimeCallFilter::contains(&transfer(sibling())));
+    assert!(!RuntimeCallFilter::contains(&transfer(Location::new(
+      1,
+      [Parachain(3000)]
+    ))));
+    assert!(!RuntimeCallFilter::contains(&transfer(Location::new(
+      2,
+      [GlobalConsensus(Kusama)]
+    ))));
+  });
+}
+
+/// Test that trusted siblings are reserves of the assets under their location only
+#[test]
+fn test_trusted_sibling_reserves() {
+  let usdt = |para: u32| {
+    Asset::from((
+      Location::new(1, [Parachain(para), PalletInstance(50), GeneralIndex(1984)]),
+      AMOUNT,
+    ))
+  };
+  let para = |id: u32| Location::new(1, [Parachain(id)]);
+
+  assert!(TrustedSiblingReserves::contains(&usdt(SIBLING), &sibling()));
+  assert!(TrustedSiblingReserves::contains(
+    &Asset::from((sibling(), AMOUNT)),
+    &sibling()
+  ));
+  // Not the reserve of another chain's asset
+  assert!(!TrustedSiblingReserves::contains(&usdt(3000), &sibling()));
+  // Untrusted siblings are not reserves, even of their own assets
+  assert!(!TrustedSiblingReserves::contains(&usdt(3000), &para(3000)));
+  assert!(!TrustedSiblingReserves::contains(
+    &Asset::from((Location::parent(), AMOUNT)),
+    &sibling()
+  ));
+}
+
+/// Test that local assets are reserve transferred into the sibling's sovereign account
+#[test]
+fn test_reserve_transfer_local_asset_to_sibling() {
+  new_test_ext().execute_with(|| {
+    setup_native_pool(LOCAL_ASSET, 100_000 * EXISTENTIAL_DEPOSIT);
+    connect_sibling();
+    let alice_before = LocalAssets::balance(LOCAL_ASSET, alice());
+
+    assert_ok!(transfer_to_sibling(local_asset(AMOUNT)));
+
+    assert_eq!(
+      LocalAssets::balance(LOCAL_ASSET, alice()),
+      alice_before - AMOUNT
+    );
+    assert_eq!(
+      LocalAssets::balance(LOCAL_ASSET, sibling_sovereign()),
+      AMOUNT
+    );
+    assert!(System::events().iter().any(|record| matches!(
+      &record.event,
+      RuntimeEvent::PolkadotXcm(pallet_xcm::Event::Sent { destination, .. })
+        if *destination == sibling()
+    )));
+  });
+}
+
+/// Test that reserve transfers fail without a channel to the destination
+#[test]
+fn test_reserve_transfer_requires_channel() {
+  new_test_ext().execute_with(|| {
+    setup_native_pool(LOCAL_ASSET, 100_000 * EXISTENTIAL_DEPOSIT);
+    let alice_before = LocalAssets::balance(LOCAL_ASSET, alice());
+
+    assert!(transfer_to_sibling(local_asset(AMOUNT)).is_err());
+    assert_eq!(LocalAssets::balance(LOCAL_ASSET, alice()), alice_before);
+  });
+}
+
+/// Test that unknown assets are rejected by the reserve transfer filter
+#[test]
+fn test_reserve_transfer_rejects_unknown_assets() {
+  new_test_ext().execute_with(|| {
+    connect_sibling();
+    let unknown: Asset = (
+      Location::new(0, [PalletInstance(99), GeneralIndex(1)]),
+      AMOUNT,
+    )
+      .into();
+
+    assert_noop!(
+      transfer_to_sibling(unknown),
+      pallet_xcm::Error::<Runtime>::Filtered
+    );
+  });
+}
+
+/// Test that local assets sent back by the sibling are released from its sovereign account
+#[test]
+fn test_local_asset_returns_from_sibling() {
+  new_test_ext().execute_with(|| {
+    setup_native_pool(LOCAL_ASSET, 100_000 * EXISTENTIAL_DEPOSIT);
+    connect_sibling();
+    assert_ok!(transfer_to_sibling(local_asset(AMOUNT)));
+    assert_ok!(Balances::force_set_balance(
+      RuntimeOrigin::root(),
+      MultiAddress::Id(StakingPot::get()),
+      EXISTENTIAL_DEPOSIT,
+    ));
+    let bob_before = LocalAssets::balance(LOCAL_ASSET, bob());
+
+    // What the sibling sends when its users withdraw their reserve-backed tokens
+    let message = Xcm(vec![
+      WithdrawAsset(local_asset(AMOUNT).into()),
+      ClearOrigin,
+      BuyExecution {
+        fees: local_asset(AMOUNT),
+        weight_limit: Unlimited,
+      },
+      DepositAsset {
+        assets: Wild(AllCounted(1)),
+        beneficiary: account_location(bob()),
+      },
+    ]);
+    let mut hash = [0u8; 32];
+    let outcome = XcmExecutor::<XcmConfig>::prepare_and_execute(
+      sibling(),
+      message,
+      &mut hash,
+      Weight::MAX,
+      Weight::zero(),
+    );
+    assert_ok!(outcome.ensure_complete());
+
+    assert_eq!(LocalAssets::balance(LOCAL_ASSET, sibling_sovereign()), 0);
+    let received = LocalAssets::balance(LOCAL_ASSET, bob()) - bob_before;
+    // Everything but the execution fee reaches the beneficiary
+    assert!(received > 0 && received < AMOUNT);
+  });
+}
+
+fn swap_and_send_call(amount_in: Balance) -> RuntimeCall {
+  RuntimeCall::DexRouter(pallet_dex_router::Call::swap_and_send {
+    path: BoundedVec::try_from(vec![AssetKind::Native, AssetKind::Local(LOCAL_ASSET)]).unwrap(),
+    amount_in,
+    amount_out_min: 1,
+    dest: Box::new(VersionedLocation::from(sibling())),
+    beneficiary: Box::new(VersionedLocation::from(account_location(bob()))),
+  })
+}
+
+/// Test that the output of a router swap is reserve transferred to the sibling in one call
+#[test]
+fn test_swap_and_send_to_sibling() {
+  new_test_ext().execute_with(|| {
+    setup_native_pool(LOCAL_ASSET, 100_000 * EXISTENTIAL_DEPOSIT);
+    connect_sibling();
+    let alice_local_before = LocalAssets::balance(LOCAL_ASSET, alice());
+
+    let (quote, delivery_fee) = DexRouter::quote_swap_and_send(
+      AssetKind::Native,
+      AssetKind::Local(LOCAL_ASSET),
+      AMOUNT,
+      &VersionedLocation::from(sibling()),
+      &VersionedLocation::from(account_location(bob())),
+    )
+    .expect("route and channel exist");
+    assert_eq!(delivery_fee, 0);
+
+    assert_ok!(swap_and_send_call(AMOUNT).dispatch(RuntimeOrigin::signed(alice())));
+
+    // The whole output ends up in the sibling's sovereign account
+    assert_eq!(
+      LocalAssets::balance(LOCAL_ASSET, alice()),
+      alice_local_before
+    );
+    assert_eq!(
+      LocalAssets::balance(LOCAL_ASSET, sibling_sovereign()),
+      quote
+    );
+    assert!(System::events().iter().any(|record| matches!(
+      &record.event,
+      RuntimeEvent::DexRouter(pallet_dex_router::Event::SwapOutputSent {
+        asset: AssetKind::Local(LOCAL_ASSET),
+        amount,
+        ..
+      }) if *amount == quote
+    )));
+  });
+}
+
+/// Test that the swap is reverted when the output cannot be sent
+#[test]
+fn test_swap_and_send_reverts_when_sending_fails() {
+  new_test_ext().execute_with(|| {
+    setup_native_pool(LOCAL_ASSET, 100_000 * EXISTENTIAL_DEPOSIT);
+    let alice_native_before = Balances::free_balance(alice());
+    let reserves_before =
+      AssetConversion::get_reserves(AssetKind::Native, AssetKind::Local(LOCAL_ASSET)).unwrap();
+
+    // No channel is open to the sibling
+    assert_eq!(
+      DexRouter::quote_swap_and_send(
+        AssetKind::Native,
+        AssetKind::Local(LOCAL_ASSET),
+        AMOUNT,
+        &VersionedLocation::from(sibling()),
+        &VersionedLocation::from(account_location(bob())),
+      ),
+      None
+    );
+    assert!(swap_and_send_call(AMOUNT)
+      .dispatch(RuntimeOrigin::signed(alice()))
+      .is_err());
+
+    assert_eq!(Balances::free_balance(alice()), alice_native_before);
+    assert_eq!(
+      AssetConversion::get_reserves(AssetKind::Native, AssetKind::Local(LOCAL_ASSET)).unwrap(),
+      reserves_before
+    );
+    assert!(!System::events().iter().any(|record| matches!(
+      record.event,
+      RuntimeEvent::DexRouter(pallet_dex_router::Event::SwapExecuted { .. })
+    )));
+  });
+}
+
+/// Test that router swap output cannot be sent to a sibling that is not trusted
+#[test]
+fn test_swap_and_send_rejects_untrusted_sibling() {
+  new_test_ext().execute_with(|| {
+    setup_native_pool(LOCAL_ASSET, 100_000 * EXISTENTIAL_DEPOSIT);
+    let untrusted = Location::new(1, [Parachain(3000)]);
+    ParachainSystem::open_outbound_hrmp_channel_for_benchmarks_or_tests(ParaId::from(3000));
+    let alice_native_before = Balances::free_balance(alice());
+
+    assert_eq!(
+      DexRouter::quote_swap_and_send(
+        AssetKind::Native,
+        AssetKind::Local(LOCAL_ASSET),
+        AMOUNT,
+        &VersionedLocation::from(untrusted.clone()),
+        &VersionedLocation::from(account_location(bob())),
+      ),
+      None
+    );
+    let call = RuntimeCall::DexRouter(pallet_dex_router::Call::swap_and_send {
+      path: BoundedVec::try_from(vec![AssetKind::Native, AssetKind::Local(LOCAL_ASSET)]).unwrap(),
+      amount_in: AMOUNT,
+      amount_out_min: 1,
+      dest: Box::new(VersionedLocation::from(untrusted)),
+      beneficiary: Box::new(VersionedLocation::from(account_location(bob()))),
+    });
+    assert!(call.dispatch(RuntimeOrigin::signed(alice())).is_err());
+
+    assert_eq!(Balances::free_balance(alice()), alice_native_before);
+  });
+}
@@ -19,6 +19,11 @@
 
 pub mod block_weights;
 pub mod extrinsic_weights;
+pub mod pallet_asset_conversion;
+pub mod pallet_asset_conversion_tx_payment;
+pub mod pallet_assets_foreign;
+pub mod pallet_assets_local;
+pub mod pallet_assets_pool;
 pub mod paritydb_weights;
 pub mod rocksdb_weights;
 
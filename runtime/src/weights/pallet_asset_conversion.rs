@@ -0,0 +1,94 @@
+//! Weights for `pallet_asset_conversion`.
+//!
+//! These are placeholders that have not been benchmarked: the storage accesses are counted from
+//! the code, the execution times and proof sizes are estimates. Replace this file with the output
+//! of the benchmarks on reference hardware, from the project root:
+//!
+//! ```sh
+//! ./scripts/benchmark-weights.sh
+//! ```
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use core::marker::PhantomData;
+use polkadot_sdk::*;
+
+use frame_support::{traits::Get, weights::Weight};
+
+/// Placeholder weights for `pallet_asset_conversion`, see the module documentation.
+pub struct WeightInfo<T>(PhantomData<T>);
+impl<T: frame_system::Config> pallet_asset_conversion::WeightInfo for WeightInfo<T> {
+  /// Storage: `AssetConversion::Pools` (r:1 w:1)
+  /// Storage: `System::Account` (r:2 w:2)
+  /// Storage: `Assets::Asset` (r:1 w:0)
+  /// Storage: `AssetConversion::NextPoolAssetId` (r:1 w:1)
+  /// Storage: `PoolAssets::Asset` (r:1 w:1)
+  /// Storage: `PoolAssets::Account` (r:1 w:1)
+  fn create_pool() -> Weight {
+    // Placeholder, not benchmarked
+    Weight::from_parts(95_000_000, 6196)
+      .saturating_add(T::DbWeight::get().reads(7_u64))
+      .saturating_add(T::DbWeight::get().writes(6_u64))
+  }
+  /// Storage: `AssetConversion::Pools` (r:1 w:0)
+  /// Storage: `System::Account` (r:1 w:1)
+  /// Storage: `Assets::Asset` (r:1 w:1)
+  /// Storage: `Assets::Account` (r:2 w:2)
+  /// Storage: `PoolAssets::Asset` (r:1 w:1)
+  /// Storage: `PoolAssets::Account` (r:2 w:2)
+  fn add_liquidity() -> Weight {
+    // Placeholder, not benchmarked
+    Weight::from_parts(141_000_000, 7404)
+      .saturating_add(T::DbWeight::get().reads(8_u64))
+      .saturating_add(T::DbWeight::get().writes(7_u64))
+  }
+  /// Storage: `AssetConversion::Pools` (r:1 w:0)
+  /// Storage: `System::Account` (r:1 w:1)
+  /// Storage: `Assets::Asset` (r:1 w:1)
+  /// Storage: `Assets::Account` (r:2 w:2)
+  /// Storage: `PoolAssets::Asset` (r:1 w:1)
+  /// Storage: `PoolAssets::Account` (r:2 w:2)
+  fn remove_liquidity() -> Weight {
+    // Placeholder, not benchmarked
+    Weight::from_parts(131_000_000, 7404)
+      .saturating_add(T::DbWeight::get().reads(8_u64))
+      .saturating_add(T::DbWeight::get().writes(7_u64))
+  }
+  /// Storage: `Assets::Asset` (r:4 w:4)
+  /// Storage: `Assets::Account` (r:8 w:8)
+  /// Storage: `System::Account` (r:2 w:2)
+  fn swap_exact_tokens_for_tokens(n: u32) -> Weight {
+    // Placeholder, not benchmarked
+    Weight::from_parts(18_000_000, 7404)
+      .saturating_add(Weight::from_parts(66_000_000, 0).saturating_mul(n.into()))
+      .saturating_add(T::DbWeight::get().reads((4_u64).saturating_mul(n.into())))
+      .saturating_add(T::DbWeight::get().writes((4_u64).saturating_mul(n.into())))
+      .saturating_add(Weight::from_parts(0, 3220).saturating_mul(n.into()))
+  }
+  /// Storage: `Assets::Asset` (r:4 w:4)
+  /// Storage: `Assets::Account` (r:8 w:8)
+  /// Storage: `System::Account` (r:2 w:2)
+  fn swap_tokens_for_exact_tokens(n: u32) -> Weight {
+    // Placeholder, not benchmarked
+    Weight::from_parts(18_500_000, 7404)
+      .saturating_add(Weight::from_parts(66_500_000, 0).saturating_mul(n.into()))
+      .saturating_add(T::DbWeight::get().reads((4_u64).saturating_mul(n.into())))
+      .saturating_add(T::DbWeight::get().writes((4_u64).saturating_mul(n.into())))
+      .saturating_add(Weight::from_parts(0, 3220).saturating_mul(n.into()))
+  }
+  /// Storage: `AssetConversion::Pools` (r:1 w:0)
+  /// Storage: `System::Account` (r:1 w:1)
+  /// Storage: `Assets::Asset` (r:2 w:2)
+  /// Storage: `Assets::Account` (r:1 w:1)
+  fn touch(i: u32) -> Weight {
+    // Placeholder, not benchmarked
+    Weight::from_parts(23_000_000, 6196)
+      .saturating_add(Weight::from_parts(19_000_000, 0).saturating_mul(i.into()))
+      .saturating_add(T::DbWeight::get().reads(1_u64))
+      .saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(i.into())))
+      .saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(i.into())))
+      .saturating_add(Weight::from_parts(0, 2609).saturating_mul(i.into()))
+  }
+}
@@ -0,0 +1,44 @@
+//! Weights for `pallet_asset_conversion_tx_payment`.
+//!
+//! These are placeholders that have not been benchmarked: the storage accesses are counted from
+//! the code, the execution times and proof sizes are estimates. Replace this file with the output
+//! of the benchmarks on reference hardware, from the project root:
+//!
+//! ```sh
+//! ./scripts/benchmark-weights.sh
+//! ```
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use core::marker::PhantomData;
+use polkadot_sdk::*;
+
+use frame_support::{traits::Get, weights::Weight};
+
+/// Placeholder weights for `pallet_asset_conversion_tx_payment`, see the module documentation.
+pub struct WeightInfo<T>(PhantomData<T>);
+impl<T: frame_system::Config> pallet_asset_conversion_tx_payment::WeightInfo for WeightInfo<T> {
+  fn charge_asset_tx_payment_zero() -> Weight {
+    // Placeholder, not benchmarked
+    Weight::from_parts(1_300_000, 0)
+  }
+  /// Storage: `System::Account` (r:1 w:1)
+  fn charge_asset_tx_payment_native() -> Weight {
+    // Placeholder, not benchmarked
+    Weight::from_parts(48_000_000, 3593)
+      .saturating_add(T::DbWeight::get().reads(1_u64))
+      .saturating_add(T::DbWeight::get().writes(1_u64))
+  }
+  /// Storage: `AssetConversion::Pools` (r:1 w:0)
+  /// Storage: `System::Account` (r:2 w:2)
+  /// Storage: `Assets::Asset` (r:2 w:2)
+  /// Storage: `Assets::Account` (r:4 w:4)
+  fn charge_asset_tx_payment_asset() -> Weight {
+    // Placeholder, not benchmarked
+    Weight::from_parts(208_000_000, 11426)
+      .saturating_add(T::DbWeight::get().reads(9_u64))
+      .saturating_add(T::DbWeight::get().writes(8_u64))
+  }
+}
@@ -0,0 +1,302 @@
+//! Weights for `pallet_assets` (pool assets instance).
+//!
+//! These are placeholders that have not been benchmarked: they are a copy of
+//! `pallet_assets_local` with the storage of the pool assets instance, as both instances have the
+//! same `u32` keys. Replace this file with the output of the benchmarks on reference hardware,
+//! from the project root:
+//!
+//! ```sh
+//! ./scripts/benchmark-weights.sh
+//! ```
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use core::marker::PhantomData;
+use polkadot_sdk::*;
+
+use frame_support::{traits::Get, weights::Weight};
+
+/// Placeholder weights for `pallet_assets`, see the module documentation.
+pub struct WeightInfo<T>(PhantomData<T>);
+impl<T: frame_system::Config> pallet_assets::WeightInfo for WeightInfo<T> {
+  /// Storage: `PoolAssets::Asset` (r:1 w:1)
+  /// Storage: `System::Account` (r:1 w:1)
+  fn create() -> Weight {
+    // Placeholder, not benchmarked
+    Weight::from_parts(26_000_000, 3675)
+      .saturating_add(T::DbWeight::get().reads(2_u64))
+      .saturating_add(T::DbWeight::get().writes(2_u64))
+  }
+  /// Storage: `PoolAssets::Asset` (r:1 w:1)
+  fn force_create() -> Weight {
+    // Placeholder, not benchmarked
+    Weight::from_parts(11_000_000, 3675)
+      .saturating_add(T::DbWeight::get().reads(1_u64))
+      .saturating_add(T::DbWeight::get().writes(1_u64))
+  }
+  /// Storage: `PoolAssets::Asset` (r:1 w:1)
+  fn start_destroy() -> Weight {
+    // Placeholder, not benchmarked
+    Weight::from_parts(12_000_000, 3675)
+      .saturating_add(T::DbWeight::get().reads(1_u64))
+      .saturating_add(T::DbWeight::get().writes(1_u64))
+  }
+  /// Storage: `PoolAssets::Asset` (r:1 w:1)
+  /// Storage: `PoolAssets::Account` (r:1001 w:1000)
+  /// Storage: `System::Account` (r:1000 w:1000)
+  fn destroy_accounts(c: u32) -> Weight {
+    // Placeholder, not benchmarked
+    Weight::from_parts(15_000_000, 3675)
+      .saturating_add(Weight::from_parts(16_000_000, 0).saturating_mul(c.into()))
+      .saturating_add(T::DbWeight::get().reads(1_u64))
+      .saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(c.into())))
+      .saturating_add(T::DbWeight::get().writes(1_u64))
+      .saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(c.into())))
+      .saturating_add(Weight::from_parts(0, 2609).saturating_mul(c.into()))
+  }
+  /// Storage: `PoolAssets::Asset` (r:1 w:1)
+  /// Storage: `PoolAssets::Approvals` (r:1001 w:1000)
+  fn destroy_approvals(a: u32) -> Weight {
+    // Placeholder, not benchmarked
+    Weight::from_parts(16_000_000, 3675)
+      .saturating_add(Weight::from_parts(16_500_000, 0).saturating_mul(a.into()))
+      .saturating_add(T::DbWeight::get().reads(1_u64))
+      .saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(a.into())))
+      .saturating_add(T::DbWeight::get().writes(1_u64))
+      .saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(a.into())))
+      .saturating_add(Weight::from_parts(0, 2623).saturating_mul(a.into()))
+  }
+  /// Storage: `PoolAssets::Asset` (r:1 w:1)
+  /// Storage: `PoolAssets::Metadata` (r:1 w:0)
+  fn finish_destroy() -> Weight {
+    // Placeholder, not benchmarked
+    Weight::from_parts(13_000_000, 3675)
+      .saturating_add(T::DbWeight::get().reads(1_u64))
+      .saturating_add(T::DbWeight::get().writes(1_u64))
+  }
+  /// Storage: `PoolAssets::Asset` (r:1 w:1)
+  /// Storage: `PoolAssets::Account` (r:1 w:1)
+  fn mint() -> Weight {
+    // Placeholder, not benchmarked
+    Weight::from_parts(23_000_000, 3675)
+      .saturating_add(T::DbWeight::get().reads(2_u64))
+      .saturating_add(T::DbWeight::get().writes(2_u64))
+  }
+  /// Storage: `PoolAssets::Asset` (r:1 w:1)
+  /// Storage: `PoolAssets::Account` (r:1 w:1)
+  fn burn() -> Weight {
+    // Placeholder, not benchmarked
+    Weight::from_parts(30_000_000, 3675)
+      .saturating_add(T::DbWeight::get().reads(2_u64))
+      .saturating_add(T::DbWeight::get().writes(2_u64))
+  }
+  /// Storage: `PoolAssets::Asset` (r:1 w:1)
+  /// Storage: `PoolAssets::Account` (r:2 w:2)
+  /// Storage: `System::Account` (r:1 w:1)
+  fn transfer() -> Weight {
+    // Placeholder, not benchmarked
+    Weight::from_parts(41_000_000, 6208)
+      .saturating_add(T::DbWeight::get().reads(4_u64))
+      .saturating_add(T::DbWeight::get().writes(4_u64))
+  }
+  /// Storage: `PoolAssets::Asset` (r:1 w:1)
+  /// Storage: `PoolAssets::Account` (r:2 w:2)
+  /// Storage: `System::Account` (r:1 w:1)
+  fn transfer_keep_alive() -> Weight {
+    // Placeholder, not benchmarked
+    Weight::from_parts(37_000_000, 6208)
+      .saturating_add(T::DbWeight::get().reads(4_u64))
+      .saturating_add(T::DbWeight::get().writes(4_u64))
+  }
+  /// Storage: `PoolAssets::Asset` (r:1 w:1)
+  /// Storage: `PoolAssets::Account` (r:2 w:2)
+  /// Storage: `System::Account` (r:1 w:1)
+  fn force_transfer() -> Weight {
+    // Placeholder, not benchmarked
+    Weight::from_parts(41_000_000, 6208)
+      .saturating_add(T::DbWeight::get().reads(4_u64))
+      .saturating_add(T::DbWeight::get().writes(4_u64))
+  }
+  /// Storage: `PoolAssets::Asset` (r:1 w:0)
+  /// Storage: `PoolAssets::Account` (r:1 w:1)
+  fn freeze() -> Weight {
+    // Placeholder, not benchmarked
+    Weight::from_parts(16_000_000, 3675)
+      .saturating_add(T::DbWeight::get().reads(2_u64))
+      .saturating_add(T::DbWeight::get().writes(1_u64))
+  }
+  /// Storage: `PoolAssets::Asset` (r:1 w:0)
+  /// Storage: `PoolAssets::Account` (r:1 w:1)
+  fn thaw() -> Weight {
+    // Placeholder, not benchmarked
+    Weight::from_parts(16_000_000, 3675)
+      .saturating_add(T::DbWeight::get().reads(2_u64))
+      .saturating_add(T::DbWeight::get().writes(1_u64))
+  }
+  /// Storage: `PoolAssets::Asset` (r:1 w:1)
+  fn freeze_asset() -> Weight {
+    // Placeholder, not benchmarked
+    Weight::from_parts(12_000_000, 3675)
+      .saturating_add(T::DbWeight::get().reads(1_u64))
+      .saturating_add(T::DbWeight::get().writes(1_u64))
+  }
+  /// Storage: `PoolAssets::Asset` (r:1 w:1)
+  fn thaw_asset() -> Weight {
+    // Placeholder, not benchmarked
+    Weight::from_parts(12_000_000, 3675)
+      .saturating_add(T::DbWeight::get().reads(1_u64))
+      .saturating_add(T::DbWeight::get().writes(1_u64))
+  }
+  /// Storage: `PoolAssets::Asset` (r:1 w:1)
+  /// Storage: `PoolAssets::Metadata` (r:1 w:0)
+  fn transfer_ownership() -> Weight {
+    // Placeholder, not benchmarked
+    Weight::from_parts(14_000_000, 3675)
+      .saturating_add(T::DbWeight::get().reads(2_u64))
+      .saturating_add(T::DbWeight::get().writes(1_u64))
+  }
+  /// Storage: `PoolAssets::Asset` (r:1 w:1)
+  fn set_team() -> Weight {
+    // Placeholder, not benchmarked
+    Weight::from_parts(12_000_000, 3675)
+      .saturating_add(T::DbWeight::get().reads(1_u64))
+      .saturating_add(T::DbWeight::get().writes(1_u64))
+  }
+  /// Storage: `PoolAssets::Asset` (r:1 w:0)
+  /// Storage: `PoolAssets::Metadata` (r:1 w:1)
+  fn set_metadata(n: u32, s: u32) -> Weight {
+    // Placeholder, not benchmarked
+    Weight::from_parts(28_000_000, 3675)
+      .saturating_add(Weight::from_parts(3_000, 0).saturating_mul(n.into()))
+      .saturating_add(Weight::from_parts(2_000, 0).saturating_mul(s.into()))
+      .saturating_add(T::DbWeight::get().reads(2_u64))
+      .saturating_add(T::DbWeight::get().writes(1_u64))
+  }
+  /// Storage: `PoolAssets::Asset` (r:1 w:0)
+  /// Storage: `PoolAssets::Metadata` (r:1 w:1)
+  fn clear_metadata() -> Weight {
+    // Placeholder, not benchmarked
+    Weight::from_parts(27_000_000, 3675)
+      .saturating_add(T::DbWeight::get().reads(2_u64))
+      .saturating_add(T::DbWeight::get().writes(1_u64))
+  }
+  /// Storage: `PoolAssets::Asset` (r:1 w:0)
+  /// Storage: `PoolAssets::Metadata` (r:1 w:1)
+  fn force_set_metadata(n: u32, s: u32) -> Weight {
+    // Placeholder, not benchmarked
+    Weight::from_parts(13_000_000, 3675)
+      .saturating_add(Weight::from_parts(1_000, 0).saturating_mul(n.into()))
+      .saturating_add(Weight::from_parts(1_000, 0).saturating_mul(s.into()))
+      .saturating_add(T::DbWeight::get().reads(2_u64))
+      .saturating_add(T::DbWeight::get().writes(1_u64))
+  }
+  /// Storage: `PoolAssets::Asset` (r:1 w:0)
+  /// Storage: `PoolAssets::Metadata` (r:1 w:1)
+  fn force_clear_metadata() -> Weight {
+    // Placeholder, not benchmarked
+    Weight::from_parts(26_000_000, 3675)
+      .saturating_add(T::DbWeight::get().reads(2_u64))
+      .saturating_add(T::DbWeight::get().writes(1_u64))
+  }
+  /// Storage: `PoolAssets::Asset` (r:1 w:1)
+  fn force_asset_status() -> Weight {
+    // Placeholder, not benchmarked
+    Weight::from_parts(11_000_000, 3675)
+      .saturating_add(T::DbWeight::get().reads(1_u64))
+      .saturating_add(T::DbWeight::get().writes(1_u64))
+  }
+  /// Storage: `PoolAssets::Asset` (r:1 w:1)
+  /// Storage: `PoolAssets::Approvals` (r:1 w:1)
+  fn approve_transfer() -> Weight {
+    // Placeholder, not benchmarked
+    Weight::from_parts(31_000_000, 3675)
+      .saturating_add(T::DbWeight::get().reads(2_u64))
+      .saturating_add(T::DbWeight::get().writes(2_u64))
+  }
+  /// Storage: `PoolAssets::Asset` (r:1 w:1)
+  /// Storage: `PoolAssets::Approvals` (r:1 w:1)
+  /// Storage: `PoolAssets::Account` (r:2 w:2)
+  /// Storage: `System::Account` (r:1 w:1)
+  fn transfer_approved() -> Weight {
+    // Placeholder, not benchmarked
+    Weight::from_parts(63_000_000, 6208)
+      .saturating_add(T::DbWeight::get().reads(5_u64))
+      .saturating_add(T::DbWeight::get().writes(5_u64))
+  }
+  /// Storage: `PoolAssets::Asset` (r:1 w:1)
+  /// Storage: `PoolAssets::Approvals` (r:1 w:1)
+  fn cancel_approval() -> Weight {
+    // Placeholder, not benchmarked
+    Weight::from_parts(32_000_000, 3675)
+      .saturating_add(T::DbWeight::get().reads(2_u64))
+      .saturating_add(T::DbWeight::get().writes(2_u64))
+  }
+  /// Storage: `PoolAssets::Asset` (r:1 w:1)
+  /// Storage: `PoolAssets::Approvals` (r:1 w:1)
+  fn force_cancel_approval() -> Weight {
+    // Placeholder, not benchmarked
+    Weight::from_parts(32_000_000, 3675)
+      .saturating_add(T::DbWeight::get().reads(2_u64))
+      .saturating_add(T::DbWeight::get().writes(2_u64))
+  }
+  /// Storage: `PoolAssets::Asset` (r:1 w:1)
+  fn set_min_balance() -> Weight {
+    // Placeholder, not benchmarked
+    Weight::from_parts(12_000_000, 3675)
+      .saturating_add(T::DbWeight::get().reads(1_u64))
+      .saturating_add(T::DbWeight::get().writes(1_u64))
+  }
+  /// Storage: `PoolAssets::Account` (r:1 w:1)
+  /// Storage: `PoolAssets::Asset` (r:1 w:1)
+  /// Storage: `System::Account` (r:1 w:1)
+  fn touch() -> Weight {
+    // Placeholder, not benchmarked
+    Weight::from_parts(31_000_000, 3675)
+      .saturating_add(T::DbWeight::get().reads(3_u64))
+      .saturating_add(T::DbWeight::get().writes(3_u64))
+  }
+  /// Storage: `PoolAssets::Account` (r:1 w:1)
+  /// Storage: `PoolAssets::Asset` (r:1 w:1)
+  fn touch_other() -> Weight {
+    // Placeholder, not benchmarked
+    Weight::from_parts(29_000_000, 3675)
+      .saturating_add(T::DbWeight::get().reads(2_u64))
+      .saturating_add(T::DbWeight::get().writes(2_u64))
+  }
+  /// Storage: `PoolAssets::Account` (r:1 w:1)
+  /// Storage: `PoolAssets::Asset` (r:1 w:1)
+  /// Storage: `System::Account` (r:1 w:1)
+  fn refund() -> Weight {
+    // Placeholder, not benchmarked
+    Weight::from_parts(30_000_000, 3675)
+      .saturating_add(T::DbWeight::get().reads(3_u64))
+      .saturating_add(T::DbWeight::get().writes(3_u64))
+  }
+  /// Storage: `PoolAssets::Account` (r:1 w:1)
+  /// Storage: `PoolAssets::Asset` (r:1 w:1)
+  fn refund_other() -> Weight {
+    // Placeholder, not benchmarked
+    Weight::from_parts(28_000_000, 3675)
+      .saturating_add(T::DbWeight::get().reads(2_u64))
+      .saturating_add(T::DbWeight::get().writes(2_u64))
+  }
+  /// Storage: `PoolAssets::Asset` (r:1 w:0)
+  /// Storage: `PoolAssets::Account` (r:1 w:1)
+  fn block() -> Weight {
+    // Placeholder, not benchmarked
+    Weight::from_parts(16_000_000, 3675)
+      .saturating_add(T::DbWeight::get().reads(2_u64))
+      .saturating_add(T::DbWeight::get().writes(1_u64))
+  }
+  /// Storage: `PoolAssets::Asset` (r:1 w:1)
+  /// Storage: `PoolAssets::Account` (r:2 w:2)
+  /// Storage: `System::Account` (r:1 w:1)
+  fn transfer_all() -> Weight {
+    // Placeholder, not benchmarked
+    Weight::from_parts(51_000_000, 6208)
+      .saturating_add(T::DbWeight::get().reads(4_u64))
+      .saturating_add(T::DbWeight::get().writes(4_u64))
+  }
+}
@@ -0,0 +1,69 @@
+#!/bin/bash
+
+# Weight generation script for the DEX parachain
+# This script builds the runtime with benchmarks and regenerates the weight files.
+# Run it on reference hardware and commit the output.
+
+set -e
+
+RUNTIME="target/release/wbuild/parachain-template-runtime/parachain_template_runtime.wasm"
+
+# Colors for output
+RED='\033[0;31m'
+GREEN='\033[0;32m'
+NC='\033[0m' # No Color
+
+print_info() {
+    echo -e "${GREEN}[INFO]${NC} $1"
+}
+
+print_error() {
+    echo -e "${RED}[ERROR]${NC} $1"
+}
+
+# Pallet name, weight template and output path, one benchmarked pallet per line
+PALLETS=(
+    "pallet_dex_router .maintain/frame-weight-template.hbs pallets/dex-router/src/weights.rs"
+    "pallet_farming .maintain/frame-weight-template.hbs pallets/farming/src/weights.rs"
+    "pallet_asset_conversion .maintain/runtime-weight-template.hbs runtime/src/weights/"
+    "pallet_asset_conversion_tx_payment .maintain/runtime-weight-template.hbs runtime/src/weights/"
+    "pallet_assets .maintain/runtime-weight-template.hbs runtime/src/weights/"
+    "pallet_treasury .maintain/runtime-weight-template.hbs runtime/src/weights/"
+)
+
+# The bencher names the files of instanced pallets after the instance
+ASSET_INSTANCES=(
+    "pallet_assets_assets.rs pallet_assets_local.rs"
+    "pallet_assets_foreign_assets.rs pallet_assets_foreign.rs"
+    "pallet_assets_pool_assets.rs pallet_assets_pool.rs"
+)
+
+if [ ! -f "Cargo.toml" ]; then
+    print_error "Please run this script from the project root"
+    exit 1
+fi
+
+if ! command -v frame-omni-bencher &> /dev/null; then
+    print_error "frame-omni-bencher is required: cargo install frame-omni-bencher"
+    exit 1
+fi
+
+print_info "Building the runtime with benchmarks..."
+cargo build --release --features runtime-benchmarks
+
+for entry in "${PALLETS[@]}"; do
+    read -r pallet template output <<< "$entry"
+    print_info "Benchmarking $pallet into $output..."
+    frame-omni-bencher v1 benchmark pallet \
+        --runtime "$RUNTIME" \
+        --pallet "$pallet" --extrinsic '*' \
+        --template "$template" \
+        --output "$output"
+done
+
+for entry in "${ASSET_INSTANCES[@]}"; do
+    read -r generated file <<< "$entry"
+    mv "runtime/src/weights/$generated" "runtime/src/weights/$file"
+done
+
+print_info "Weights regenerated, review and commit the changes"